@@ -0,0 +1,89 @@
+// MIT License
+//
+// Copyright (c) 2026 Laffinty
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// Build script: generates the typed translation key enum from the source
+// locale (locales/en.toml), so `translate(Key::MenuFile)` fails to compile
+// when the key does not exist.
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+const SOURCE_LOCALE: &str = "locales/en.toml";
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", SOURCE_LOCALE);
+
+    let text = std::fs::read_to_string(SOURCE_LOCALE)
+        .unwrap_or_else(|e| panic!("failed to read {}: {}", SOURCE_LOCALE, e));
+    let table: toml::Table = text.parse()
+        .unwrap_or_else(|e| panic!("failed to parse {}: {}", SOURCE_LOCALE, e));
+
+    // 保持源文件中的顺序，便于对照
+    let mut keys: Vec<&str> = table.keys().map(|k| k.as_str()).collect();
+    keys.sort_by_key(|k| text.find(&format!("\n{} ", k)).unwrap_or(usize::MAX));
+
+    let mut out = String::new();
+    out.push_str("/// 翻译键 - 由 locales/en.toml 在编译时生成，请勿手动修改\n");
+    out.push_str("#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]\n");
+    out.push_str("pub enum Key {\n");
+    for key in &keys {
+        writeln!(out, "    /// `{}`", key).unwrap();
+        writeln!(out, "    {},", variant_name(key)).unwrap();
+    }
+    out.push_str("}\n\n");
+
+    out.push_str("impl Key {\n");
+    out.push_str("    /// 源语言中定义的全部键\n");
+    out.push_str("    pub const ALL: &'static [Key] = &[\n");
+    for key in &keys {
+        writeln!(out, "        Key::{},", variant_name(key)).unwrap();
+    }
+    out.push_str("    ];\n\n");
+    out.push_str("    /// 资源文件中的键名\n");
+    out.push_str("    pub const fn as_str(self) -> &'static str {\n");
+    out.push_str("        match self {\n");
+    for key in &keys {
+        writeln!(out, "            Key::{} => \"{}\",", variant_name(key), key).unwrap();
+    }
+    out.push_str("        }\n    }\n\n");
+    out.push_str("    pub fn from_name(name: &str) -> Option<Key> {\n");
+    out.push_str("        Key::ALL.iter().copied().find(|key| key.as_str() == name)\n");
+    out.push_str("    }\n}\n");
+
+    let out_dir = std::env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+    std::fs::write(Path::new(&out_dir).join("translation_keys.rs"), out)
+        .expect("failed to write translation_keys.rs");
+}
+
+/// menu_file -> MenuFile
+fn variant_name(key: &str) -> String {
+    key.split(['_', '-', '.'])
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
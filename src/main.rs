@@ -156,6 +156,117 @@ macro_rules! module_init {
     };
 }
 
+// ==============================================================================
+// RUN MODE AND CLI COMMANDS
+// ==============================================================================
+// The framework knows three run modes, selected from the command line:
+// - Gui:    default, every module is loaded
+// - Daemon: `--daemon`, modules that need a display (the UI) stay idle;
+//           each module checks run_mode() in its own initialize()
+// - Test:   `--test`, runs for 60 seconds then exits
+//
+// One-shot CLI commands (e.g. `easnginx systemd-unit`) are collected the same
+// way as modules: a module file calls cli_command! and main() runs the command
+// named by the first argument instead of starting the framework.
+
+/// Framework run mode parsed from command line arguments
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RunMode {
+    Gui,
+    Daemon,
+    Test,
+}
+
+impl RunMode {
+    pub fn from_args(args: &[String]) -> Self {
+        if args.iter().any(|a| a == "--test") {
+            RunMode::Test
+        } else if args.iter().any(|a| a == "--daemon") {
+            RunMode::Daemon
+        } else {
+            RunMode::Gui
+        }
+    }
+}
+
+/// Returns the run mode of the current process
+pub fn run_mode() -> RunMode {
+    let args: Vec<String> = std::env::args().collect();
+    RunMode::from_args(&args)
+}
+
+/// Returns the value following `flag` on the command line (e.g. `--pidfile <path>`)
+pub fn arg_value(flag: &str) -> Option<String> {
+    let args: Vec<String> = std::env::args().collect();
+    args.iter()
+        .position(|a| a == flag)
+        .and_then(|i| args.get(i + 1).cloned())
+}
+
+/// CliCommandInfo stores a one-shot command contributed by a module
+#[derive(Clone, Copy)]
+pub struct CliCommandInfo {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Receives the arguments after the command name
+    pub run_fn: fn(&[String]) -> Result<(), String>,
+}
+
+inventory::collect!(CliCommandInfo);
+
+/// Macro for modules to contribute a CLI command
+///
+/// USAGE:
+///   cli_command!("my-command", "What it does", my_command_fn);
+///
+/// where my_command_fn: fn(&[String]) -> Result<(), String>
+#[macro_export]
+macro_rules! cli_command {
+    ($name:expr, $description:expr, $run_fn:expr) => {
+        const _: () = {
+            #[used]
+            static CLI_COMMAND_INFO: $crate::CliCommandInfo = $crate::CliCommandInfo {
+                name: $name,
+                description: $description,
+                run_fn: $run_fn,
+            };
+            
+            inventory::submit! {
+                CLI_COMMAND_INFO
+            }
+        };
+    };
+}
+
+/// Runs the CLI command named by args[1], if any
+///
+/// RETURNS:
+/// - None if args[1] is not a registered command (start the framework)
+/// - Some(exit_code) after the command ran
+fn run_cli_command(args: &[String]) -> Option<i32> {
+    let name = args.get(1)?;
+    let commands: Vec<_> = inventory::iter::<CliCommandInfo>.into_iter().collect();
+    
+    if name == "help" || name == "--help" {
+        println!("Usage: easnginx [--daemon [--pidfile <path>]] [--test]");
+        println!("       easnginx <command> [args...]\n");
+        println!("Commands:");
+        for command in &commands {
+            println!("  {:<20} {}", command.name, command.description);
+        }
+        return Some(0);
+    }
+    
+    let command = commands.iter().find(|c| c.name == name)?;
+    match (command.run_fn)(&args[2..]) {
+        Ok(()) => Some(0),
+        Err(e) => {
+            eprintln!("{}: {}", command.name, e);
+            Some(1)
+        }
+    }
+}
+
 // ==============================================================================
 // CORE ARCHITECTURE: MESSAGE BUS SYSTEM
 // ==============================================================================
@@ -290,6 +401,7 @@ impl MessageBus {
     ///   bus.subscribe(my_message_type, "my_module".to_string()).await;
     ///
     /// Side effect: Automatically starts a dispatcher for this message type
+    #[allow(clippy::map_entry)]
     pub async fn register_message_type<M: Message>(&self) -> TypeId {
         let type_id = TypeId::of::<M>();
         let mut channels_guard = self.inner.channels.write().await;
@@ -762,7 +874,7 @@ async fn run_message_dispatcher(
 //
 // 框架核心职责（严格遵守）：
 // 1. Setup panic handler for error isolation
-// 2. Parse command line arguments (--test mode)
+// 2. Parse command line arguments (--test / --daemon mode, CLI commands)
 // 3. Create MessageBus and ModuleRegistry - 基础设施初始化
 // 4. Auto-discover and register all modules via inventory - 编译期自动发现
 // 5. Register built-in SystemMessage type - 内置消息类型注册
@@ -786,7 +898,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
 
     // Command line arguments
     let args: Vec<String> = std::env::args().collect();
-    let is_test_mode = args.contains(&"--test".to_string());
+    
+    // One-shot CLI commands run without starting the framework
+    if let Some(code) = run_cli_command(&args) {
+        std::process::exit(code);
+    }
+    
+    let mode = RunMode::from_args(&args);
+    let is_test_mode = mode == RunMode::Test;
 
     eprintln!("=== VIBE_SYNAPSE FRAMEWORK STARTING ===");
     eprintln!("[Main] Current directory: {:?}", std::env::current_dir().unwrap());
@@ -828,6 +947,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     // Confirm framework mode
     if is_test_mode {
         println!("\n=== Vibe_Synapse Framework Test Running ===");
+    } else if mode == RunMode::Daemon {
+        println!("\n=== Vibe_Synapse Framework Running (daemon) ===");
     } else {
         println!("\n=== Vibe_Synapse Framework Running ===");
     }
@@ -879,11 +1000,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let (exit_tx, mut exit_rx) = watch::channel(false);
         registry.set_exit_sender(exit_tx).await;
         
-        // Wait for either Ctrl+C or GUI exit signal
+        // Wait for Ctrl+C, SIGTERM (service manager stop) or GUI exit signal
         tokio::select! {
             _ = tokio::signal::ctrl_c() => {
                 println!("\n[Main] Ctrl+C received, shutting down...");
             }
+            _ = wait_for_terminate() => {
+                println!("\n[Main] SIGTERM received, shutting down...");
+            }
             _ = exit_rx.changed() => {
                 if *exit_rx.borrow() {
                     println!("\n[Main] GUI closed, shutting down...");
@@ -905,6 +1029,22 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     Ok(())
 }
 
+/// Resolves when the process receives SIGTERM (never on non-unix platforms)
+async fn wait_for_terminate() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+                return;
+            }
+            Err(e) => eprintln!("[Main] Failed to install SIGTERM handler: {}", e),
+        }
+    }
+    std::future::pending::<()>().await
+}
+
 // ==============================================================================
 // AI DEVELOPER USAGE GUIDE (INVENTORY-BASED SYSTEM)
 // ==============================================================================
@@ -1039,3 +1179,22 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
 // HAPPY CODING! The framework handles all the boilerplate for you.
 // Just focus on writing your module logic!
 // ==============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn run_mode_from_args() {
+        assert_eq!(RunMode::from_args(&args(&["easnginx"])), RunMode::Gui);
+        assert_eq!(RunMode::from_args(&args(&["easnginx", "--daemon", "--pidfile", "/run/e.pid"])), RunMode::Daemon);
+        assert_eq!(RunMode::from_args(&args(&["easnginx", "--test"])), RunMode::Test);
+        // --test takes precedence over --daemon
+        assert_eq!(RunMode::from_args(&args(&["easnginx", "--daemon", "--test"])), RunMode::Test);
+        assert_eq!(RunMode::from_args(&args(&["easnginx", "daemon"])), RunMode::Gui);
+    }
+}
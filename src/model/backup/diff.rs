@@ -0,0 +1,179 @@
+// MIT License
+// 
+// Copyright (c) 2026 Laffinty
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+
+// ==============================================================================
+// diff - 比较两组文件
+// ==============================================================================
+// 按行求最长公共子序列，只保留改动及其前后 CONTEXT 行，其余折叠为 Skipped。
+// 配置文件通常只有几百行；超过 MAX_CELLS 时整个文件按删除 + 新增显示。
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+const CONTEXT: usize = 3;
+const MAX_CELLS: usize = 4_000_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileStatus {
+    Added,
+    Removed,
+    Modified,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DiffLine {
+    Context(String),
+    Added(String),
+    Removed(String),
+    /// 折叠的未改动行数
+    Skipped(usize),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileDiff {
+    pub path: PathBuf,
+    pub status: FileStatus,
+    pub lines: Vec<DiffLine>,
+}
+
+/// 比较两组文件，只返回有变化的文件
+pub fn diff_trees(old: &BTreeMap<PathBuf, Vec<u8>>, new: &BTreeMap<PathBuf, Vec<u8>>) -> Vec<FileDiff> {
+    let mut paths: Vec<&PathBuf> = old.keys().chain(new.keys()).collect();
+    paths.sort();
+    paths.dedup();
+    paths.into_iter()
+        .filter_map(|path| {
+            let text = |data: Option<&Vec<u8>>| data.map(|data| String::from_utf8_lossy(data).into_owned());
+            let (before, after) = (text(old.get(path)), text(new.get(path)));
+            let status = match (&before, &after) {
+                (None, Some(_)) => FileStatus::Added,
+                (Some(_), None) => FileStatus::Removed,
+                (Some(before), Some(after)) if before != after => FileStatus::Modified,
+                _ => return None,
+            };
+            Some(FileDiff {
+                path: path.clone(),
+                status,
+                lines: diff_lines(before.as_deref().unwrap_or_default(), after.as_deref().unwrap_or_default()),
+            })
+        })
+        .collect()
+}
+
+/// 行级差异，未改动的部分只保留上下文
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    collapse(full_diff(&old, &new))
+}
+
+fn full_diff(old: &[&str], new: &[&str]) -> Vec<DiffLine> {
+    if old.len().saturating_mul(new.len()) > MAX_CELLS {
+        return old.iter().map(|line| DiffLine::Removed(line.to_string()))
+            .chain(new.iter().map(|line| DiffLine::Added(line.to_string())))
+            .collect();
+    }
+    // lcs[i][j]：old[i..] 与 new[j..] 的最长公共子序列长度
+    let width = new.len() + 1;
+    let mut lcs = vec![0usize; (old.len() + 1) * width];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i * width + j] = if old[i] == new[j] {
+                lcs[(i + 1) * width + j + 1] + 1
+            } else {
+                lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut lines = Vec::new();
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            lines.push(DiffLine::Context(old[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[(i + 1) * width + j] >= lcs[i * width + j + 1]) {
+            // 删除的行排在新增的行之前
+            lines.push(DiffLine::Removed(old[i].to_string()));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Added(new[j].to_string()));
+            j += 1;
+        }
+    }
+    lines
+}
+
+/// 距离改动超过 CONTEXT 行的未改动行折叠为 Skipped
+fn collapse(lines: Vec<DiffLine>) -> Vec<DiffLine> {
+    let changed: Vec<usize> = lines.iter().enumerate()
+        .filter(|(_, line)| !matches!(line, DiffLine::Context(_)))
+        .map(|(index, _)| index)
+        .collect();
+    let near_change = |index: usize| changed.iter().any(|&c| c.abs_diff(index) <= CONTEXT);
+    let mut result = Vec::new();
+    let mut skipped = 0;
+    for (index, line) in lines.into_iter().enumerate() {
+        if matches!(line, DiffLine::Context(_)) && !near_change(index) {
+            skipped += 1;
+            continue;
+        }
+        if skipped > 0 {
+            result.push(DiffLine::Skipped(skipped));
+            skipped = 0;
+        }
+        result.push(line);
+    }
+    if skipped > 0 {
+        result.push(DiffLine::Skipped(skipped));
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diffs_lines_with_context() {
+        let old: String = (1..=20).map(|n| format!("line {}\n", n)).collect();
+        let new = old.replace("line 10\n", "line ten\n").replace("line 20\n", "");
+        let lines = diff_lines(&old, &new);
+        let context = |n: usize| DiffLine::Context(format!("line {}", n));
+        assert_eq!(lines, vec![
+            DiffLine::Skipped(6),
+            context(7), context(8), context(9),
+            DiffLine::Removed("line 10".into()),
+            DiffLine::Added("line ten".into()),
+            context(11), context(12), context(13),
+            DiffLine::Skipped(3),
+            context(17), context(18), context(19),
+            DiffLine::Removed("line 20".into()),
+        ]);
+
+        let old = BTreeMap::from([(PathBuf::from("a"), b"x\n".to_vec()), (PathBuf::from("b"), b"y\n".to_vec())]);
+        let new = BTreeMap::from([(PathBuf::from("a"), b"x\n".to_vec()), (PathBuf::from("c"), b"z\n".to_vec())]);
+        let statuses: Vec<(PathBuf, FileStatus)> = diff_trees(&old, &new).into_iter().map(|diff| (diff.path, diff.status)).collect();
+        assert_eq!(statuses, [(PathBuf::from("b"), FileStatus::Removed), (PathBuf::from("c"), FileStatus::Added)]);
+    }
+}
@@ -0,0 +1,347 @@
+// MIT License
+// 
+// Copyright (c) 2026 Laffinty
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+
+// ==============================================================================
+// Backup Module - 配置备份、比较与恢复
+// ==============================================================================
+// 备份的范围是 easyNginx 管理的配置：sites.toml、<config_dir>/nginx 下的全部文件、
+// 接管时导入的站点文件，以及接管时修改的 nginx 主配置文件和接管记录 takeover.toml，
+// 恢复接管之前的备份时它们与站点列表保持一致。备份保存在 <config_dir>/backups（见 store）。
+//
+// site、takeover 模块修改文件之前调用 auto_snapshot；内容没有变化时不会新建备份。
+// 恢复时先备份当前状态，再作为一次事务提交备份中的内容（见 transaction）：
+// 先用 nginx -t 测试暂存的配置，通过后才替换文件并重载，重载失败时自动换回。
+//   BackupListRequest -> BackupsListed
+//   BackupRequest(Create / Diff / Restore) -> BackupResponse [-> BackupsListed]
+
+use async_trait::async_trait;
+use std::any::{Any, TypeId};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use crate::{MessageEnvelope, MessageBus, Module, module_init};
+use crate::model::controller::{parse_test_output, TestReport};
+use crate::model::transaction::ReloadOutcome;
+use crate::model::settings::Settings;
+use crate::model::site::{self, SiteRefreshRequest};
+use crate::model::takeover;
+
+pub mod store;
+pub mod diff;
+
+pub use diff::{DiffLine, FileDiff, FileStatus};
+pub use store::{Snapshot, SnapshotInfo, Store};
+
+/// 请求广播备份列表
+#[derive(Clone, Debug, Default)]
+pub struct BackupListRequest;
+
+impl crate::Message for BackupListRequest {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn message_type(&self) -> TypeId {
+        TypeId::of::<BackupListRequest>()
+    }
+    
+    fn clone_box(&self) -> Box<dyn crate::Message> {
+        Box::new(self.clone())
+    }
+}
+
+/// 备份列表（最新的在前）
+#[derive(Clone, Debug)]
+pub struct BackupsListed {
+    pub snapshots: Vec<Snapshot>,
+}
+
+impl crate::Message for BackupsListed {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn message_type(&self) -> TypeId {
+        TypeId::of::<BackupsListed>()
+    }
+    
+    fn clone_box(&self) -> Box<dyn crate::Message> {
+        Box::new(self.clone())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BackupAction {
+    /// 手动备份，note 为说明
+    Create { note: String },
+    /// 比较两个备份；to 为 None 时与当前文件比较
+    Diff { from: String, to: Option<String> },
+    Restore { id: String },
+}
+
+#[derive(Clone, Debug)]
+pub struct BackupRequest {
+    /// 请求方生成的编号，原样返回在响应中
+    pub id: u64,
+    pub action: BackupAction,
+    pub requester: String,
+}
+
+impl BackupRequest {
+    pub fn new(id: u64, action: BackupAction, requester: &str) -> Self {
+        Self {
+            id,
+            action,
+            requester: requester.to_string(),
+        }
+    }
+}
+
+impl crate::Message for BackupRequest {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn message_type(&self) -> TypeId {
+        TypeId::of::<BackupRequest>()
+    }
+    
+    fn clone_box(&self) -> Box<dyn crate::Message> {
+        Box::new(self.clone())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum BackupResult {
+    Created(Snapshot),
+    Diff(Vec<FileDiff>),
+    Restored,
+}
+
+#[derive(Clone, Debug)]
+pub struct BackupResponse {
+    pub id: u64,
+    pub requester: String,
+    pub result: Result<BackupResult, String>,
+    /// 恢复后 nginx -t 未通过时的报告
+    pub report: Option<TestReport>,
+}
+
+impl crate::Message for BackupResponse {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn message_type(&self) -> TypeId {
+        TypeId::of::<BackupResponse>()
+    }
+    
+    fn clone_box(&self) -> Box<dyn crate::Message> {
+        Box::new(self.clone())
+    }
+}
+
+// ==============================================================================
+// Backups
+// ==============================================================================
+
+/// 默认的备份仓库
+pub fn store() -> Store {
+    Store::new(crate::model::config_dir().join("backups"))
+}
+
+/// easyNginx 生成的配置所在目录
+fn managed_root() -> PathBuf {
+    crate::model::config_dir().join("nginx")
+}
+
+/// 需要备份的文件
+pub fn managed_files() -> Vec<PathBuf> {
+    let mut files = vec![site::store_path(), takeover::record_path()];
+    files.extend(site::discover::main_conf_path(&Settings::load().nginx));
+    collect_files(&managed_root(), &mut files);
+    files.extend(site::load_sites().unwrap_or_default().into_iter().filter_map(|site| site.imported_from));
+    files.sort();
+    files.dedup();
+    files
+}
+
+/// 备份中没有时应当删除的文件和目录
+fn owned_paths() -> Vec<PathBuf> {
+    vec![managed_root(), takeover::record_path()]
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()) {
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else if !path.extension().is_some_and(|ext| ext == "tmp" || ext == "easynginx-tmp") {
+            files.push(path);
+        }
+    }
+}
+
+/// 修改配置前的自动备份
+pub fn auto_snapshot(summary: &str) -> Result<Snapshot, String> {
+    let info = SnapshotInfo { summary: summary.to_string(), note: String::new(), automatic: true };
+    store().snapshot(&managed_files(), info).map_err(|e| format!("backup failed: {}", e))
+}
+
+/// 恢复备份；nginx -t 未通过时不替换任何文件并返回报告
+fn restore(id: &str) -> Result<(), (String, Option<TestReport>)> {
+    let store = store();
+    let target = store.get(id).map_err(|e| (format!("backup {}: {}", id, e), None))?;
+    let transaction = store.checkout(&target, &managed_files(), &owned_paths()).map_err(|e| (e.to_string(), None))?;
+    auto_snapshot(&format!("Before restoring {}", id)).map_err(|e| (e, None))?;
+
+    let settings = Settings::load().nginx;
+    match transaction.commit(&settings).map_err(|e| (e, None))? {
+        ReloadOutcome::Reloaded | ReloadOutcome::NotRunning => Ok(()),
+        ReloadOutcome::TestFailed(output) => Err((
+            "nginx -t failed, the backup has not been restored".into(),
+            Some(parse_test_output(false, &output)),
+        )),
+        ReloadOutcome::RolledBack(error) => Err((format!("nginx stopped serving after the restore, the previous configuration has been put back: {}", error), None)),
+        ReloadOutcome::ReloadFailed(error) => Err((format!("restored, but reloading nginx failed: {}", error), None)),
+    }
+}
+
+fn diff(from: &str, to: Option<&str>) -> Result<Vec<FileDiff>, String> {
+    let store = store();
+    let io_error = |e: std::io::Error| e.to_string();
+    let old = store.contents(&store.get(from).map_err(io_error)?).map_err(io_error)?;
+    let new = match to {
+        Some(to) => store.contents(&store.get(to).map_err(io_error)?).map_err(io_error)?,
+        None => managed_files().into_iter()
+            .filter_map(|path| std::fs::read(&path).ok().map(|data| (path, data)))
+            .collect(),
+    };
+    Ok(diff::diff_trees(&old, &new))
+}
+
+fn execute(action: &BackupAction) -> (Result<BackupResult, String>, Option<TestReport>) {
+    match action {
+        BackupAction::Create { note } => {
+            let info = SnapshotInfo { summary: "Manual backup".into(), note: note.trim().to_string(), automatic: false };
+            (store().snapshot(&managed_files(), info).map(BackupResult::Created).map_err(|e| e.to_string()), None)
+        }
+        BackupAction::Diff { from, to } => (diff(from, to.as_deref()).map(BackupResult::Diff), None),
+        BackupAction::Restore { id } => match restore(id) {
+            Ok(()) => (Ok(BackupResult::Restored), None),
+            Err((error, report)) => (Err(error), report),
+        },
+    }
+}
+
+// ==============================================================================
+// Module
+// ==============================================================================
+
+pub struct BackupModule {
+    name: &'static str,
+    bus: Arc<RwLock<Option<Arc<MessageBus>>>>,
+}
+
+impl BackupModule {
+    pub fn new() -> Self {
+        Self {
+            name: "backup",
+            bus: Arc::new(RwLock::new(None)),
+        }
+    }
+    
+    async fn publish_list(&self, bus: &MessageBus) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let snapshots = tokio::task::spawn_blocking(|| store().list()).await?;
+        bus.publish(BackupsListed { snapshots }).await?;
+        Ok(())
+    }
+}
+
+impl Default for BackupModule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Module for BackupModule {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+    
+    async fn initialize(&mut self, bus: Arc<MessageBus>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        *self.bus.write().await = Some(bus.clone());
+        
+        let list_request_type = bus.register_message_type::<BackupListRequest>().await;
+        let request_type = bus.register_message_type::<BackupRequest>().await;
+        bus.register_message_type::<BackupsListed>().await;
+        bus.register_message_type::<BackupResponse>().await;
+        
+        bus.subscribe(list_request_type, self.name().to_string()).await;
+        bus.subscribe(request_type, self.name().to_string()).await;
+        Ok(())
+    }
+    
+    async fn process_message(&self, envelope: MessageEnvelope) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let Some(bus) = self.bus.read().await.clone() else {
+            return Ok(());
+        };
+        if envelope.message_type == TypeId::of::<BackupListRequest>() {
+            self.publish_list(&bus).await?;
+        } else if envelope.message_type == TypeId::of::<BackupRequest>() {
+            if let Some(msg) = envelope.payload.as_any().downcast_ref::<BackupRequest>() {
+                let action = msg.action.clone();
+                let (result, report) = tokio::task::spawn_blocking(move || execute(&action)).await?;
+                if let Err(error) = &result {
+                    eprintln!("[Backup] {:?} failed: {}", msg.action, error);
+                }
+                // 重载失败时文件也已恢复，总是重新读取站点列表
+                let restored = matches!(msg.action, BackupAction::Restore { .. });
+                let listed = !matches!(msg.action, BackupAction::Diff { .. });
+                bus.publish(BackupResponse {
+                    id: msg.id,
+                    requester: msg.requester.clone(),
+                    result,
+                    report,
+                }).await?;
+                if listed {
+                    self.publish_list(&bus).await?;
+                }
+                if restored {
+                    bus.publish(SiteRefreshRequest).await?;
+                }
+            }
+        }
+        
+        Ok(())
+    }
+    
+    async fn shutdown(&mut self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        Ok(())
+    }
+}
+
+module_init!(BackupModule, "backup");
@@ -0,0 +1,352 @@
+// MIT License
+// 
+// Copyright (c) 2026 Laffinty
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+
+// ==============================================================================
+// store - 内容寻址的备份仓库
+// ==============================================================================
+// 与 git 相同的思路：
+//   objects/ab/cdef…     文件内容，以 SHA-1 命名，zlib 压缩，相同内容只存一份
+//   snapshots/<id>.toml  一次备份：时间、作者、做了什么、为什么，以及每个文件对应的对象
+// id 为 "<六位序号>-<文件列表哈希的前 8 位>"，序号逐次递增，列表按序号从新到旧排序。
+
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use crate::model::transaction::Transaction;
+
+/// 保留的自动备份数量；手动备份不会被清理
+const MAX_AUTOMATIC: usize = 100;
+
+/// 一个文件
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    pub path: PathBuf,
+    /// 内容的 SHA-1
+    pub hash: String,
+}
+
+/// 一次备份
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub id: String,
+    /// 递增的序号 - 同一秒内的备份也按创建顺序排列（旧版本的备份为 0）
+    #[serde(default)]
+    pub sequence: u64,
+    /// Unix 时间（秒）
+    pub created_at: u64,
+    /// 操作系统用户
+    pub author: String,
+    /// 做了什么，如 "Before saving site blog"
+    pub summary: String,
+    /// 为什么（手动备份时用户填写的说明）
+    #[serde(default)]
+    pub note: String,
+    /// 修改站点等操作前自动创建
+    #[serde(default)]
+    pub automatic: bool,
+    /// 与上一次备份相比有变化的文件
+    #[serde(default)]
+    pub changed: Vec<PathBuf>,
+    pub files: Vec<Entry>,
+}
+
+/// 新备份的描述
+#[derive(Clone, Debug, Default)]
+pub struct SnapshotInfo {
+    pub summary: String,
+    pub note: String,
+    pub automatic: bool,
+}
+
+/// 当前操作系统用户
+pub fn current_user() -> String {
+    ["USER", "USERNAME", "LOGNAME"].iter()
+        .find_map(|name| std::env::var(name).ok().filter(|user| !user.is_empty()))
+        .unwrap_or_else(|| "unknown".into())
+}
+
+fn hash(data: &[u8]) -> String {
+    Sha1::digest(data).iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default()
+}
+
+/// 先写临时文件再重命名
+pub(crate) fn write_atomic(path: &Path, data: &[u8]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".easynginx-tmp");
+    std::fs::write(&tmp, data)?;
+    std::fs::rename(&tmp, path)
+}
+
+/// 备份仓库
+#[derive(Clone, Debug)]
+pub struct Store {
+    root: PathBuf,
+}
+
+impl Store {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    fn object_path(&self, hash: &str) -> PathBuf {
+        self.root.join("objects").join(&hash[..2]).join(&hash[2..])
+    }
+
+    fn snapshot_path(&self, id: &str) -> PathBuf {
+        self.root.join("snapshots").join(format!("{}.toml", id))
+    }
+
+    fn put_object(&self, data: &[u8]) -> std::io::Result<String> {
+        let hash = hash(data);
+        let path = self.object_path(&hash);
+        if !path.exists() {
+            let mut encoder = ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(data)?;
+            write_atomic(&path, &encoder.finish()?)?;
+        }
+        Ok(hash)
+    }
+
+    /// 读取对象并校验哈希
+    pub fn object(&self, hash: &str) -> std::io::Result<Vec<u8>> {
+        let mut data = Vec::new();
+        ZlibDecoder::new(std::fs::File::open(self.object_path(hash))?).read_to_end(&mut data)?;
+        if self::hash(&data) != hash {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, format!("object {} is corrupt", hash)));
+        }
+        Ok(data)
+    }
+
+    /// 所有备份，最新的在前
+    pub fn list(&self) -> Vec<Snapshot> {
+        let Ok(entries) = std::fs::read_dir(self.root.join("snapshots")) else {
+            return Vec::new();
+        };
+        let mut snapshots: Vec<Snapshot> = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "toml"))
+            .filter_map(|entry| {
+                let text = std::fs::read_to_string(entry.path()).ok()?;
+                toml::from_str(&text)
+                    .map_err(|e| eprintln!("[Backup] Ignoring invalid {}: {}", entry.path().display(), e.message()))
+                    .ok()
+            })
+            .collect();
+        snapshots.sort_by(|a, b| (b.sequence, b.created_at, &b.id).cmp(&(a.sequence, a.created_at, &a.id)));
+        snapshots
+    }
+
+    pub fn get(&self, id: &str) -> std::io::Result<Snapshot> {
+        let text = std::fs::read_to_string(self.snapshot_path(id))?;
+        toml::from_str(&text).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.message().to_string()))
+    }
+
+    /// 备份指定的文件（不存在的文件跳过）；与最新的备份相同时返回它而不新建
+    pub fn snapshot(&self, paths: &[PathBuf], info: SnapshotInfo) -> std::io::Result<Snapshot> {
+        let mut files = Vec::new();
+        for path in paths {
+            match std::fs::read(path) {
+                Ok(data) => files.push(Entry { path: path.clone(), hash: self.put_object(&data)? }),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(std::io::Error::new(e.kind(), format!("{}: {}", path.display(), e))),
+            }
+        }
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let snapshots = self.list();
+        let sequence = snapshots.iter().map(|snapshot| snapshot.sequence).max().unwrap_or(0) + 1;
+        let latest = snapshots.into_iter().next();
+        if let Some(latest) = latest.as_ref().filter(|latest| latest.files == files) {
+            return Ok(latest.clone());
+        }
+        let previous: BTreeMap<&Path, &str> = latest.iter()
+            .flat_map(|latest| latest.files.iter())
+            .map(|entry| (entry.path.as_path(), entry.hash.as_str()))
+            .collect();
+        let current: BTreeMap<&Path, &str> = files.iter().map(|entry| (entry.path.as_path(), entry.hash.as_str())).collect();
+        let mut changed: Vec<PathBuf> = current.iter()
+            .filter(|(path, hash)| previous.get(*path) != Some(*hash))
+            .map(|(path, _)| path.to_path_buf())
+            .chain(previous.keys().filter(|path| !current.contains_key(*path)).map(|path| path.to_path_buf()))
+            .collect();
+        changed.sort();
+
+        let listing: String = files.iter().map(|entry| format!("{}\0{}\n", entry.path.display(), entry.hash)).collect();
+        let created_at = now();
+        let snapshot = Snapshot {
+            id: format!("{:06}-{}", sequence, &hash(listing.as_bytes())[..8]),
+            sequence,
+            created_at,
+            author: current_user(),
+            summary: info.summary,
+            note: info.note,
+            automatic: info.automatic,
+            changed,
+            files,
+        };
+        let text = toml::to_string_pretty(&snapshot)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        write_atomic(&self.snapshot_path(&snapshot.id), text.as_bytes())?;
+        self.prune()?;
+        Ok(snapshot)
+    }
+
+    /// 备份中各文件的内容
+    pub fn contents(&self, snapshot: &Snapshot) -> std::io::Result<BTreeMap<PathBuf, Vec<u8>>> {
+        snapshot.files.iter()
+            .map(|entry| Ok((entry.path.clone(), self.object(&entry.hash)?)))
+            .collect()
+    }
+
+    /// 把文件恢复为备份中内容的事务：先读出并校验全部对象；
+    /// current 中位于 owned 下（或就是 owned 中的文件）、备份里没有的文件被删除
+    pub fn checkout(&self, snapshot: &Snapshot, current: &[PathBuf], owned: &[PathBuf]) -> std::io::Result<Transaction> {
+        let contents = self.contents(snapshot)?;
+        let mut transaction = Transaction::new();
+        let is_owned = |path: &Path| owned.iter().any(|owned| path.starts_with(owned));
+        for path in current.iter().filter(|path| is_owned(path) && !contents.contains_key(*path)) {
+            transaction.remove(path.clone());
+        }
+        for (path, data) in contents {
+            transaction.write(path, data);
+        }
+        Ok(transaction)
+    }
+
+    /// 只保留最近 MAX_AUTOMATIC 个自动备份，并删除不再使用的对象
+    fn prune(&self) -> std::io::Result<()> {
+        let snapshots = self.list();
+        let mut automatic = 0;
+        let mut kept = Vec::new();
+        for snapshot in snapshots {
+            if snapshot.automatic {
+                automatic += 1;
+                if automatic > MAX_AUTOMATIC {
+                    std::fs::remove_file(self.snapshot_path(&snapshot.id))?;
+                    continue;
+                }
+            }
+            kept.push(snapshot);
+        }
+        if automatic <= MAX_AUTOMATIC {
+            return Ok(());
+        }
+
+        let used: std::collections::HashSet<&str> = kept.iter()
+            .flat_map(|snapshot| snapshot.files.iter())
+            .map(|entry| entry.hash.as_str())
+            .collect();
+        for dir in std::fs::read_dir(self.root.join("objects"))?.filter_map(|entry| entry.ok()) {
+            let prefix = dir.file_name().to_string_lossy().into_owned();
+            for object in std::fs::read_dir(dir.path())?.filter_map(|entry| entry.ok()) {
+                let hash = format!("{}{}", prefix, object.file_name().to_string_lossy());
+                if !used.contains(hash.as_str()) {
+                    std::fs::remove_file(object.path())?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshots_and_checks_out() {
+        let dir = std::env::temp_dir().join(format!("easynginx-backup-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let managed = dir.join("managed");
+        std::fs::create_dir_all(&managed).unwrap();
+        let (a, b) = (managed.join("a.conf"), managed.join("b.conf"));
+        std::fs::write(&a, "server { listen 80; }\n").unwrap();
+        let store = Store::new(dir.join("backups"));
+        let info = |summary: &str| SnapshotInfo { summary: summary.into(), note: String::new(), automatic: true };
+
+        let first = store.snapshot(&[a.clone(), b.clone()], info("first")).unwrap();
+        assert_eq!(first.files.len(), 1);
+        assert_eq!(first.changed, vec![a.clone()]);
+        // 内容没有变化时不新建
+        assert_eq!(store.snapshot(&[a.clone(), b.clone()], info("again")).unwrap(), first);
+
+        std::fs::write(&a, "server { listen 8080; }\n").unwrap();
+        std::fs::write(&b, "server { listen 81; }\n").unwrap();
+        let second = store.snapshot(&[a.clone(), b.clone()], info("second")).unwrap();
+        assert_eq!(second.changed, vec![a.clone(), b.clone()]);
+        assert_eq!(store.list().len(), 2);
+        assert_eq!(store.get(&second.id).unwrap(), second);
+
+        // 备份之后才出现的记录文件（如 takeover.toml）在恢复时删除
+        let record = dir.join("takeover.toml");
+        std::fs::write(&record, "[settings]\n").unwrap();
+        let owned = [managed.clone(), record.clone()];
+        store.checkout(&first, &[a.clone(), b.clone(), record.clone()], &owned).unwrap().apply().unwrap();
+        assert_eq!(std::fs::read_to_string(&a).unwrap(), "server { listen 80; }\n");
+        assert!(!b.exists());
+        assert!(!record.exists());
+
+        // 损坏的对象在写入任何文件之前被发现
+        let object = store.object_path(&second.files[0].hash);
+        std::fs::write(&object, b"garbage").unwrap();
+        assert!(store.checkout(&second, std::slice::from_ref(&a), &owned).is_err());
+        assert_eq!(std::fs::read_to_string(&a).unwrap(), "server { listen 80; }\n");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn orders_snapshots_taken_in_the_same_second() {
+        let dir = std::env::temp_dir().join(format!("easynginx-backup-order-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("a.conf");
+        let store = Store::new(dir.join("backups"));
+        let info = |summary: &str| SnapshotInfo { summary: summary.into(), note: String::new(), automatic: true };
+
+        // A -> B -> A：内容回到 A 时仍新建备份，编号不重复
+        let mut taken = Vec::new();
+        for (summary, content) in [("a", "A"), ("b", "B"), ("a again", "A")] {
+            std::fs::write(&file, content).unwrap();
+            taken.push(store.snapshot(std::slice::from_ref(&file), info(summary)).unwrap());
+        }
+        assert_ne!(taken[0].id, taken[2].id);
+        assert_eq!(taken[2].changed, vec![file.clone()]);
+        let summaries: Vec<String> = store.list().into_iter().map(|snapshot| snapshot.summary).collect();
+        assert_eq!(summaries, ["a again", "b", "a"]);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,324 @@
+// MIT License
+// 
+// Copyright (c) 2026 Laffinty
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+
+// ==============================================================================
+// Controller Module - 启动、停止、重载和测试 nginx
+// ==============================================================================
+// NginxCommandRequest -> [nginx -t] -> 启动 / 停止 / 重载 -> NginxCommandResponse
+// 运行状态由 monitor 模块监视和广播。
+// 具体操作通过 NginxController 完成，默认按设置使用本机 nginx（见 system），
+// 测试中可以换成假的 nginx 脚本。
+
+use async_trait::async_trait;
+use std::any::{Any, TypeId};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use crate::{MessageEnvelope, MessageBus, Module, module_init};
+
+pub mod system;
+
+pub use system::{installed_binaries, locate_binary, parse_build_info, BuildInfo, SystemNginx};
+
+/// nginx 进程状态
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NginxStatus {
+    pub running: bool,
+    /// master 进程
+    pub pid: Option<u32>,
+}
+
+/// nginx -t 报告的问题级别
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IssueLevel {
+    /// emerg、alert、crit、error
+    Error,
+    Warning,
+    Notice,
+}
+
+/// nginx -t 报告的一个问题
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConfigIssue {
+    pub level: IssueLevel,
+    pub message: String,
+    pub file: Option<PathBuf>,
+    pub line: Option<usize>,
+}
+
+/// nginx -t 的结果
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TestReport {
+    pub success: bool,
+    pub issues: Vec<ConfigIssue>,
+    /// nginx 的完整输出
+    pub output: String,
+}
+
+/// 解析 nginx -t 的输出，如
+/// `nginx: [emerg] unknown directive "lisen" in /etc/nginx/conf.d/a.conf:3`
+pub fn parse_test_output(success: bool, output: &str) -> TestReport {
+    let issues = output.lines().filter_map(parse_issue).collect();
+    TestReport { success, issues, output: output.to_string() }
+}
+
+fn parse_issue(line: &str) -> Option<ConfigIssue> {
+    let (level, rest) = line.split_once('[')?.1.split_once(']')?;
+    let level = match level {
+        "emerg" | "alert" | "crit" | "error" => IssueLevel::Error,
+        "warn" => IssueLevel::Warning,
+        "notice" | "info" => IssueLevel::Notice,
+        _ => return None,
+    };
+    let rest = rest.trim();
+    let located = rest.rsplit_once(" in ").and_then(|(message, location)| {
+        let (file, line) = location.rsplit_once(':')?;
+        Some((message, PathBuf::from(file), line.parse().ok()?))
+    });
+    Some(match located {
+        Some((message, file, line)) => ConfigIssue { level, message: message.to_string(), file: Some(file), line: Some(line) },
+        None => ConfigIssue { level, message: rest.to_string(), file: None, line: None },
+    })
+}
+
+/// 控制 nginx 的方式
+pub trait NginxController: Send + Sync {
+    fn status(&self) -> NginxStatus;
+    fn start(&self) -> Result<(), String>;
+    /// 正常退出（QUIT）
+    fn stop(&self) -> Result<(), String>;
+    /// 重新加载配置（HUP）
+    fn reload(&self) -> Result<(), String>;
+    /// nginx -t
+    fn test(&self) -> TestReport;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NginxCommand {
+    Start,
+    Stop,
+    Reload,
+    Test,
+}
+
+#[derive(Clone, Debug)]
+pub struct NginxCommandRequest {
+    /// 请求方生成的编号，原样返回在响应中
+    pub id: u64,
+    pub command: NginxCommand,
+    pub requester: String,
+}
+
+impl NginxCommandRequest {
+    pub fn new(id: u64, command: NginxCommand, requester: &str) -> Self {
+        Self { id, command, requester: requester.to_string() }
+    }
+}
+
+impl crate::Message for NginxCommandRequest {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn message_type(&self) -> TypeId {
+        TypeId::of::<NginxCommandRequest>()
+    }
+    
+    fn clone_box(&self) -> Box<dyn crate::Message> {
+        Box::new(self.clone())
+    }
+}
+
+/// 操作结果
+#[derive(Clone, Debug)]
+pub struct NginxCommandResponse {
+    pub id: u64,
+    pub requester: String,
+    pub command: NginxCommand,
+    pub error: Option<String>,
+    /// 执行前运行的 nginx -t（停止时不测试）
+    pub report: Option<TestReport>,
+}
+
+impl NginxCommandResponse {
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+impl crate::Message for NginxCommandResponse {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn message_type(&self) -> TypeId {
+        TypeId::of::<NginxCommandResponse>()
+    }
+    
+    fn clone_box(&self) -> Box<dyn crate::Message> {
+        Box::new(self.clone())
+    }
+}
+
+/// 执行操作；启动和重载前先测试配置，未通过时不执行
+pub fn execute(controller: &dyn NginxController, command: NginxCommand) -> (Option<String>, Option<TestReport>) {
+    if command == NginxCommand::Stop {
+        return (controller.stop().err(), None);
+    }
+    let report = controller.test();
+    let error = if !report.success {
+        Some("nginx -t failed".to_string())
+    } else {
+        match command {
+            NginxCommand::Start => controller.start().err(),
+            NginxCommand::Reload => controller.reload().err(),
+            NginxCommand::Stop | NginxCommand::Test => None,
+        }
+    };
+    (error, Some(report))
+}
+
+// ==============================================================================
+// Module
+// ==============================================================================
+
+pub struct ControllerModule {
+    name: &'static str,
+    bus: Arc<RwLock<Option<Arc<MessageBus>>>>,
+    /// 为 None 时每次按当前设置创建 SystemNginx
+    controller: Option<Arc<dyn NginxController>>,
+}
+
+impl ControllerModule {
+    pub fn new() -> Self {
+        Self {
+            name: "controller",
+            bus: Arc::new(RwLock::new(None)),
+            controller: None,
+        }
+    }
+    
+    /// 使用指定的控制器（测试用）
+    pub fn with_controller(controller: Arc<dyn NginxController>) -> Self {
+        Self { controller: Some(controller), ..Self::new() }
+    }
+    
+    fn controller(&self) -> Arc<dyn NginxController> {
+        self.controller.clone().unwrap_or_else(|| {
+            let settings = crate::model::settings::Settings::load().nginx;
+            Arc::new(SystemNginx::from_settings(&settings))
+        })
+    }
+}
+
+impl Default for ControllerModule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Module for ControllerModule {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+    
+    async fn initialize(&mut self, bus: Arc<MessageBus>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        *self.bus.write().await = Some(bus.clone());
+        
+        let command_type = bus.register_message_type::<NginxCommandRequest>().await;
+        bus.register_message_type::<NginxCommandResponse>().await;
+        
+        bus.subscribe(command_type, self.name().to_string()).await;
+        Ok(())
+    }
+    
+    async fn process_message(&self, envelope: MessageEnvelope) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if envelope.message_type == TypeId::of::<NginxCommandRequest>() {
+            if let Some(msg) = envelope.payload.as_any().downcast_ref::<NginxCommandRequest>() {
+                let controller = self.controller();
+                let command = msg.command;
+                let (error, report) = tokio::task::spawn_blocking(move || execute(controller.as_ref(), command)).await?;
+                match &error {
+                    Some(error) => eprintln!("[Controller] {:?} failed: {}", command, error),
+                    None => println!("[Controller] {:?} succeeded", command),
+                }
+                let response = NginxCommandResponse {
+                    id: msg.id,
+                    requester: msg.requester.clone(),
+                    command,
+                    error,
+                    report,
+                };
+                if let Some(bus) = &*self.bus.read().await {
+                    bus.publish(response).await?;
+                }
+            }
+        }
+        
+        Ok(())
+    }
+    
+    async fn shutdown(&mut self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        Ok(())
+    }
+}
+
+module_init!(ControllerModule, "controller");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_test_output() {
+        let output = "nginx: [warn] conflicting server name \"a.example\" on 0.0.0.0:80, ignored\n\
+                      nginx: [emerg] unknown directive \"lisen\" in /etc/nginx/conf.d/site one.conf:12\n\
+                      nginx: configuration file /etc/nginx/nginx.conf test failed";
+        let report = parse_test_output(false, output);
+        assert_eq!(report.issues, vec![
+            ConfigIssue {
+                level: IssueLevel::Warning,
+                message: "conflicting server name \"a.example\" on 0.0.0.0:80, ignored".into(),
+                file: None,
+                line: None,
+            },
+            ConfigIssue {
+                level: IssueLevel::Error,
+                message: "unknown directive \"lisen\"".into(),
+                file: Some(PathBuf::from("/etc/nginx/conf.d/site one.conf")),
+                line: Some(12),
+            },
+        ]);
+
+        let info = parse_build_info(
+            "nginx version: nginx/1.24.0\nbuilt with OpenSSL 3.0.13\n\
+             configure arguments: --with-cc-opt='-g -O2' --prefix=/usr/share/nginx --conf-path=/etc/nginx/nginx.conf --pid-path=/run/nginx.pid \
+             --with-http_ssl_module --without-http_gzip_module --with-threads --add-dynamic-module=/build/ngx_brotli",
+        );
+        assert_eq!(info.version, "nginx/1.24.0");
+        assert_eq!(info.prefix, Some(PathBuf::from("/usr/share/nginx")));
+        assert_eq!(info.pid_path, Some(PathBuf::from("/run/nginx.pid")));
+        assert_eq!(info.modules, vec!["http_ssl_module", "ngx_brotli"]);
+    }
+}
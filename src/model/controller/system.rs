@@ -0,0 +1,391 @@
+// MIT License
+// 
+// Copyright (c) 2026 Laffinty
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+
+// ==============================================================================
+// SystemNginx - 通过 nginx 可执行文件和信号控制 nginx
+// ==============================================================================
+// 启动：nginx [-p prefix] [-c conf]，nginx 自行转入后台并写入 pidfile
+// 停止：向 master 进程发送 QUIT（处理完当前请求后退出）
+// 重载：向 master 进程发送 HUP
+// pidfile 依次取自配置文件中的 pid 指令、nginx -V 的 --pid-path、<prefix>/logs/nginx.pid。
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
+use crate::model::settings::NginxSettings;
+use super::{parse_test_output, NginxController, NginxStatus, TestReport};
+
+/// 不在 PATH 中时依次查找的位置
+const FALLBACK_BINARIES: [&str; 5] = [
+    "/usr/sbin/nginx",
+    "/usr/local/sbin/nginx",
+    "/usr/local/nginx/sbin/nginx",
+    "/usr/local/openresty/nginx/sbin/nginx",
+    "/opt/homebrew/bin/nginx",
+];
+
+/// 编译时未指定 --prefix 时 nginx 使用的前缀
+const DEFAULT_PREFIX: &str = "/usr/local/nginx";
+
+/// 等待 nginx 启动或退出的时间
+const WAIT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// nginx -V 中与路径有关的编译参数
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BuildInfo {
+    /// 如 "nginx/1.24.0"
+    pub version: String,
+    pub prefix: Option<PathBuf>,
+    pub conf_path: Option<PathBuf>,
+    pub pid_path: Option<PathBuf>,
+    /// 编译进的模块（--with-*_module）和第三方模块（--add-module、--add-dynamic-module 的目录名）
+    pub modules: Vec<String>,
+}
+
+/// 解析 nginx -V 的输出
+pub fn parse_build_info(output: &str) -> BuildInfo {
+    let mut info = BuildInfo::default();
+    for line in output.lines() {
+        if let Some(version) = line.strip_prefix("nginx version: ") {
+            info.version = version.trim().to_string();
+        } else if let Some(arguments) = line.strip_prefix("configure arguments:") {
+            let value = |name: &str| {
+                arguments.split_whitespace()
+                    .find_map(|arg| arg.strip_prefix(name))
+                    .map(|value| PathBuf::from(value.trim_matches(['\'', '"'])))
+            };
+            info.prefix = value("--prefix=");
+            info.conf_path = value("--conf-path=");
+            info.pid_path = value("--pid-path=");
+            info.modules = arguments.split_whitespace()
+                .filter_map(|arg| {
+                    if let Some(module) = arg.strip_prefix("--with-").filter(|name| name.ends_with("_module")) {
+                        return Some(module.to_string());
+                    }
+                    let path = arg.strip_prefix("--add-module=").or_else(|| arg.strip_prefix("--add-dynamic-module="))?;
+                    Path::new(path.trim_matches(['\'', '"'])).file_name().map(|name| name.to_string_lossy().into_owned())
+                })
+                .collect();
+        }
+    }
+    info
+}
+
+/// 查找 nginx 可执行文件：带路径时直接使用，否则在 PATH 和常见位置中查找
+pub fn locate_binary(name: &str) -> Option<PathBuf> {
+    let candidate = Path::new(name);
+    if candidate.components().count() > 1 {
+        return candidate.is_file().then(|| candidate.to_path_buf());
+    }
+    search_paths(name).into_iter().find(|path| path.is_file())
+}
+
+/// PATH 和常见位置中所有的 nginx 可执行文件（去掉指向同一文件的链接）
+pub fn installed_binaries() -> Vec<PathBuf> {
+    let mut found: Vec<(PathBuf, PathBuf)> = Vec::new();
+    for path in search_paths("nginx").into_iter().filter(|path| path.is_file()) {
+        let real = path.canonicalize().unwrap_or_else(|_| path.clone());
+        if !found.iter().any(|(_, other)| *other == real) {
+            found.push((path, real));
+        }
+    }
+    found.into_iter().map(|(path, _)| path).collect()
+}
+
+fn search_paths(name: &str) -> Vec<PathBuf> {
+    let executable = if cfg!(windows) { format!("{}.exe", name) } else { name.to_string() };
+    std::env::var_os("PATH")
+        .map(|paths| std::env::split_paths(&paths).map(|dir| dir.join(&executable)).collect::<Vec<_>>())
+        .unwrap_or_default()
+        .into_iter()
+        .chain(FALLBACK_BINARIES.iter().map(PathBuf::from))
+        .collect()
+}
+
+/// 使用本机 nginx 的控制器
+#[derive(Clone, Debug)]
+pub struct SystemNginx {
+    binary: PathBuf,
+    prefix: Option<PathBuf>,
+    conf_path: Option<PathBuf>,
+    /// 第一次使用时确定
+    pid_path: std::sync::OnceLock<PathBuf>,
+}
+
+impl SystemNginx {
+    pub fn new(binary: PathBuf, prefix: Option<PathBuf>, conf_path: Option<PathBuf>) -> Self {
+        Self { binary, prefix, conf_path, pid_path: std::sync::OnceLock::new() }
+    }
+
+    /// 按设置创建；找不到可执行文件时仍使用设置中的名称，运行时报告错误
+    pub fn from_settings(settings: &NginxSettings) -> Self {
+        Self::new(
+            locate_binary(&settings.binary).unwrap_or_else(|| PathBuf::from(&settings.binary)),
+            settings.prefix.as_ref().map(PathBuf::from),
+            settings.conf_path.as_ref().map(PathBuf::from),
+        )
+    }
+
+    pub fn binary(&self) -> &Path {
+        &self.binary
+    }
+
+    fn command(&self, conf_path: Option<&Path>) -> Command {
+        let mut command = Command::new(&self.binary);
+        if let Some(prefix) = &self.prefix {
+            command.arg("-p").arg(prefix);
+        }
+        if let Some(conf_path) = conf_path {
+            command.arg("-c").arg(conf_path);
+        }
+        command
+    }
+
+    /// 运行 nginx，返回 (是否成功, stderr 和 stdout)
+    fn run(&self, args: &[&str]) -> Result<(bool, String), String> {
+        self.run_with(self.conf_path.as_deref(), args)
+    }
+
+    fn run_with(&self, conf_path: Option<&Path>, args: &[&str]) -> Result<(bool, String), String> {
+        let output = self.command(conf_path)
+            .args(args)
+            .output()
+            .map_err(|e| format!("failed to run {}: {}", self.binary.display(), e))?;
+        // nginx 把诊断信息写到 stderr
+        let mut text = String::from_utf8_lossy(&output.stderr).into_owned();
+        text.push_str(&String::from_utf8_lossy(&output.stdout));
+        Ok((output.status.success(), text.trim().to_string()))
+    }
+
+    /// nginx -V
+    pub fn build_info(&self) -> Result<BuildInfo, String> {
+        let (_, output) = self.run(&["-V"])?;
+        Ok(parse_build_info(&output))
+    }
+
+    /// master 进程的 pidfile
+    pub fn pid_path(&self) -> &Path {
+        self.pid_path.get_or_init(|| self.find_pid_path())
+    }
+
+    /// 实际使用的前缀目录和主配置文件（未指定时取自 nginx -V）
+    pub fn paths(&self, info: &BuildInfo) -> (PathBuf, PathBuf) {
+        let prefix = self.prefix.clone()
+            .or_else(|| info.prefix.clone())
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_PREFIX));
+        let conf_path = self.conf_path.clone()
+            .or_else(|| info.conf_path.clone())
+            .map(|path| prefix.join(path))
+            .unwrap_or_else(|| prefix.join("conf/nginx.conf"));
+        (prefix, conf_path)
+    }
+
+    fn find_pid_path(&self) -> PathBuf {
+        let info = self.build_info().unwrap_or_default();
+        let (prefix, conf_path) = self.paths(&info);
+        let from_conf = crate::model::nginx::load_file(&conf_path).ok().and_then(|file| {
+            file.config.directives()
+                .find(|directive| directive.name() == "pid")
+                .and_then(|directive| directive.args().into_iter().next())
+        });
+        match from_conf.map(PathBuf::from).or(info.pid_path) {
+            Some(path) => prefix.join(path),
+            None => prefix.join("logs/nginx.pid"),
+        }
+    }
+
+    /// nginx -t -c：测试另一份主配置（如暂存的配置）
+    pub fn test_config(&self, conf_path: &Path) -> TestReport {
+        match self.run_with(Some(conf_path), &["-t"]) {
+            Ok((success, output)) => parse_test_output(success, &output),
+            Err(error) => TestReport { success: false, issues: Vec::new(), output: error },
+        }
+    }
+
+    /// 正在运行的 master 进程
+    pub fn running_pid(&self) -> Option<u32> {
+        let pid: u32 = std::fs::read_to_string(self.pid_path()).ok()?.trim().parse().ok()?;
+        is_alive(pid).then_some(pid)
+    }
+
+    fn wait_until(&self, done: impl Fn(&Self) -> bool) -> bool {
+        let deadline = Instant::now() + WAIT_TIMEOUT;
+        while Instant::now() < deadline {
+            if done(self) {
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        done(self)
+    }
+
+    fn signal(&self, pid: u32, signal: &str) -> Result<(), String> {
+        #[cfg(unix)]
+        {
+            let output = Command::new("kill")
+                .args(["-s", signal, &pid.to_string()])
+                .output()
+                .map_err(|e| format!("failed to run kill: {}", e))?;
+            if output.status.success() {
+                Ok(())
+            } else {
+                Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+            }
+        }
+        #[cfg(not(unix))]
+        {
+            // Windows 上的 nginx 通过 -s 接收控制事件
+            let _ = pid;
+            let name = if signal == "HUP" { "reload" } else { "quit" };
+            match self.run(&["-s", name])? {
+                (true, _) => Ok(()),
+                (false, output) => Err(output),
+            }
+        }
+    }
+}
+
+/// 进程是否存在（僵尸进程视为已退出）
+fn is_alive(pid: u32) -> bool {
+    if cfg!(target_os = "linux") {
+        return std::fs::read_to_string(format!("/proc/{}/stat", pid))
+            .ok()
+            .and_then(|stat| stat.rsplit_once(')').map(|(_, rest)| rest.trim_start().to_string()))
+            .is_some_and(|rest| !rest.starts_with('Z'));
+    }
+    if cfg!(unix) {
+        return Command::new("kill")
+            .args(["-0", &pid.to_string()])
+            .output()
+            .is_ok_and(|output| output.status.success());
+    }
+    true
+}
+
+impl NginxController for SystemNginx {
+    fn status(&self) -> NginxStatus {
+        let pid = self.running_pid();
+        NginxStatus { running: pid.is_some(), pid }
+    }
+
+    fn start(&self) -> Result<(), String> {
+        if let Some(pid) = self.running_pid() {
+            return Err(format!("nginx is already running (pid {})", pid));
+        }
+        match self.run(&[])? {
+            (true, _) => {}
+            (false, output) => return Err(output),
+        }
+        if self.wait_until(|nginx| nginx.running_pid().is_some()) {
+            Ok(())
+        } else {
+            Err(format!("nginx started but did not write {}", self.pid_path().display()))
+        }
+    }
+
+    fn stop(&self) -> Result<(), String> {
+        let pid = self.running_pid().ok_or("nginx is not running")?;
+        self.signal(pid, "QUIT")?;
+        if self.wait_until(|_| !is_alive(pid)) {
+            Ok(())
+        } else {
+            Err(format!("nginx (pid {}) did not exit", pid))
+        }
+    }
+
+    fn reload(&self) -> Result<(), String> {
+        let pid = self.running_pid().ok_or("nginx is not running")?;
+        self.signal(pid, "HUP")
+    }
+
+    fn test(&self) -> TestReport {
+        match self.run(&["-t"]) {
+            Ok((success, output)) => parse_test_output(success, &output),
+            Err(error) => TestReport { success: false, issues: Vec::new(), output: error },
+        }
+    }
+}
+
+// 假的 nginx 依赖 GNU env 的 --default-signal
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    /// 假的 nginx：支持 -V、-t（目录中有 broken 文件时失败），其它情况启动一个
+    /// 记录 HUP、收到 QUIT 后退出的后台进程
+    const FAKE_NGINX: &str = r#"#!/bin/sh
+dir=$(dirname "$0")
+for arg in "$@"; do
+    case "$arg" in
+        -V) echo "nginx version: nginx/1.25.3" >&2
+            echo "configure arguments: --prefix=$dir --pid-path=$dir/nginx.pid" >&2
+            exit 0 ;;
+        -t) if [ -f "$dir/broken" ]; then
+                echo "nginx: [emerg] unknown directive \"lisen\" in $dir/conf.d/a.conf:3" >&2
+                echo "nginx: configuration file $dir/nginx.conf test failed" >&2
+                exit 1
+            fi
+            echo "nginx: configuration file $dir/nginx.conf test is successful" >&2
+            exit 0 ;;
+    esac
+done
+# 非交互 shell 的后台进程忽略 QUIT，用 env 恢复后才能 trap
+# 与 nginx 一样，准备好之后才写入 pidfile
+env --default-signal=QUIT sh -c "trap 'echo reload >> \"$dir/signals\"' HUP; trap 'exit 0' QUIT; echo \$\$ > \"$dir/nginx.pid\"; while :; do sleep 0.05; done" </dev/null >/dev/null 2>&1 &
+"#;
+
+    #[test]
+    fn controls_fake_nginx() {
+        let dir = std::env::temp_dir().join(format!("easynginx-controller-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let binary = dir.join("nginx");
+        std::fs::write(&binary, FAKE_NGINX).unwrap();
+        std::fs::set_permissions(&binary, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let nginx = SystemNginx::new(binary, None, None);
+        assert_eq!(nginx.pid_path(), dir.join("nginx.pid"));
+        assert!(!nginx.status().running);
+        assert!(nginx.test().success);
+
+        nginx.start().unwrap();
+        let status = nginx.status();
+        assert!(status.running && status.pid.is_some());
+        assert!(nginx.start().unwrap_err().contains("already running"));
+
+        nginx.reload().unwrap();
+        assert!(nginx.wait_until(|_| std::fs::read_to_string(dir.join("signals")).is_ok_and(|text| text.contains("reload"))));
+
+        std::fs::write(dir.join("broken"), "").unwrap();
+        let report = nginx.test();
+        assert!(!report.success);
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!((report.issues[0].file.clone(), report.issues[0].line), (Some(dir.join("conf.d/a.conf")), Some(3)));
+
+        nginx.stop().unwrap();
+        assert!(!nginx.status().running);
+        assert!(nginx.reload().is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
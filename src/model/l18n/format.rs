@@ -0,0 +1,579 @@
+// MIT License
+//
+// Copyright (c) 2026 Laffinty
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// ==============================================================================
+// Message formatting (Fluent-style)
+// ==============================================================================
+// 翻译文本中的占位符语法：
+//
+//   {name}                          命名参数，数字按语言格式化（1,234 / 1.234）
+//   {name -> [one] ... *[other] ...}  选择表达式：
+//                                     - 数字参数先匹配精确值 [0]、[1]，再匹配复数类别
+//                                       (zero/one/two/few/many/other)
+//                                     - 文本参数按值匹配变体名
+//                                     - `*` 标记默认变体
+//   {{ / }}                         字面量 { / }
+//
+// 变体内可以嵌套占位符（变体文本中不能出现 '['），例如：
+//   site_count = "{count -> [one] {count} site *[other] {count} sites}"
+//
+// 格式错误的文本原样返回；缺失的参数保留为 {name}。
+
+use super::Language;
+
+/// 翻译参数值
+#[derive(Clone, Debug, PartialEq)]
+pub enum TranslationArg {
+    Number(f64),
+    Text(String),
+}
+
+/// 命名参数集合 - 随 TranslationRequest 传递或直接交给 format_message
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TranslationArgs {
+    args: Vec<(String, TranslationArg)>,
+}
+
+impl TranslationArgs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn number(mut self, name: &str, value: impl Into<f64>) -> Self {
+        self.args.push((name.to_string(), TranslationArg::Number(value.into())));
+        self
+    }
+
+    /// usize 计数参数（站点数量等）
+    pub fn count(self, name: &str, value: usize) -> Self {
+        self.number(name, value as f64)
+    }
+
+    pub fn text(mut self, name: &str, value: &str) -> Self {
+        self.args.push((name.to_string(), TranslationArg::Text(value.to_string())));
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&TranslationArg> {
+        self.args.iter().find(|(n, _)| n == name).map(|(_, v)| v)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.args.is_empty()
+    }
+}
+
+/// 解析后的消息片段
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Part {
+    Text(String),
+    Arg(String),
+    Select {
+        name: String,
+        variants: Vec<(String, Vec<Part>)>,
+        default: usize,
+    },
+}
+
+/// 使用命名参数格式化翻译文本
+pub fn format_message(pattern: &str, language: &Language, args: &TranslationArgs) -> String {
+    // 快速路径：没有占位符
+    if !pattern.contains('{') && !pattern.contains('}') {
+        return pattern.to_string();
+    }
+    match parse_pattern(pattern) {
+        Some(parts) => {
+            let mut out = String::with_capacity(pattern.len());
+            render(&parts, language, args, &mut out);
+            out
+        }
+        None => pattern.to_string(),
+    }
+}
+
+/// 返回文本中引用的全部参数名（包括选择表达式及其变体中的）
+pub fn placeholders(pattern: &str) -> Option<Vec<String>> {
+    fn collect(parts: &[Part], names: &mut Vec<String>) {
+        for part in parts {
+            match part {
+                Part::Text(_) => {}
+                Part::Arg(name) => {
+                    if !names.contains(name) {
+                        names.push(name.clone());
+                    }
+                }
+                Part::Select { name, variants, .. } => {
+                    if !names.contains(name) {
+                        names.push(name.clone());
+                    }
+                    for (_, variant) in variants {
+                        collect(variant, names);
+                    }
+                }
+            }
+        }
+    }
+
+    let parts = parse_pattern(pattern)?;
+    let mut names = Vec::new();
+    collect(&parts, &mut names);
+    names.sort();
+    Some(names)
+}
+
+fn render(parts: &[Part], language: &Language, args: &TranslationArgs, out: &mut String) {
+    for part in parts {
+        match part {
+            Part::Text(text) => out.push_str(text),
+            Part::Arg(name) => match args.get(name) {
+                Some(TranslationArg::Number(n)) => out.push_str(&format_number(language, *n)),
+                Some(TranslationArg::Text(t)) => out.push_str(t),
+                None => {
+                    out.push('{');
+                    out.push_str(name);
+                    out.push('}');
+                }
+            },
+            Part::Select { name, variants, default } => {
+                let index = select_variant(args.get(name), language, variants).unwrap_or(*default);
+                render(&variants[index].1, language, args, out);
+            }
+        }
+    }
+}
+
+fn select_variant(arg: Option<&TranslationArg>, language: &Language, variants: &[(String, Vec<Part>)]) -> Option<usize> {
+    let find = |key: &str| variants.iter().position(|(k, _)| k == key);
+    match arg? {
+        TranslationArg::Number(n) => {
+            // 精确值优先于复数类别
+            find(&format_plain_number(*n)).or_else(|| find(plural_category(language, *n)))
+        }
+        TranslationArg::Text(t) => find(t),
+    }
+}
+
+// ==============================================================================
+// Parser
+// ==============================================================================
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+fn parse_pattern(pattern: &str) -> Option<Vec<Part>> {
+    let mut parser = Parser { chars: pattern.chars().collect(), pos: 0 };
+    let parts = parser.parse_parts(false)?;
+    // 顶层不应有多余的 '}'
+    if parser.peek().is_some() {
+        return None;
+    }
+    Some(parts)
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        Some(c)
+    }
+
+    /// 变体文本止于 '}'、下一个 '[' 或 '*['
+    fn at_variant_end(&self) -> bool {
+        match self.peek() {
+            Some('}') | Some('[') => true,
+            Some('*') => self.peek_at(1) == Some('['),
+            _ => false,
+        }
+    }
+
+    /// 解析文本和占位符，直到结束或（变体内）遇到变体结尾
+    fn parse_parts(&mut self, in_variant: bool) -> Option<Vec<Part>> {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+
+        while let Some(c) = self.peek() {
+            if in_variant && self.at_variant_end() {
+                break;
+            }
+            match c {
+                '{' => {
+                    self.next();
+                    if self.peek() == Some('{') {
+                        self.next();
+                        text.push('{');
+                        continue;
+                    }
+                    if !text.is_empty() {
+                        parts.push(Part::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(self.parse_placeable()?);
+                }
+                '}' => {
+                    self.next();
+                    if self.next() != Some('}') {
+                        return None;
+                    }
+                    text.push('}');
+                }
+                _ => {
+                    text.push(c);
+                    self.next();
+                }
+            }
+        }
+
+        if !text.is_empty() {
+            parts.push(Part::Text(text));
+        }
+        Some(parts)
+    }
+
+    /// 解析 '{' 之后的内容：{name} 或 {name -> variants}
+    fn parse_placeable(&mut self) -> Option<Part> {
+        self.skip_whitespace();
+        let name = self.parse_identifier()?;
+        self.skip_whitespace();
+
+        match self.next()? {
+            '}' => Some(Part::Arg(name)),
+            '-' => {
+                if self.next()? != '>' {
+                    return None;
+                }
+                self.parse_variants(name)
+            }
+            _ => None,
+        }
+    }
+
+    fn parse_variants(&mut self, name: String) -> Option<Part> {
+        let mut variants = Vec::new();
+        let mut default = None;
+
+        loop {
+            self.skip_whitespace();
+            match self.next()? {
+                '}' => break,
+                '*' => {
+                    if default.is_some() || self.next()? != '[' {
+                        return None;
+                    }
+                    default = Some(variants.len());
+                }
+                '[' => {}
+                _ => return None,
+            }
+
+            let key = self.parse_variant_key()?;
+            // 变体名后的一个空格不属于变体文本
+            if self.peek() == Some(' ') {
+                self.next();
+            }
+            let mut value = self.parse_parts(true)?;
+            trim_trailing_whitespace(&mut value);
+            variants.push((key, value));
+        }
+
+        let default = default?;
+        Some(Part::Select { name, variants, default })
+    }
+
+    fn parse_variant_key(&mut self) -> Option<String> {
+        let mut key = String::new();
+        loop {
+            match self.next()? {
+                ']' => break,
+                c if c.is_alphanumeric() || c == '_' || c == '-' || c == '.' => key.push(c),
+                _ => return None,
+            }
+        }
+        if key.is_empty() { None } else { Some(key) }
+    }
+
+    fn parse_identifier(&mut self) -> Option<String> {
+        let mut name = String::new();
+        while let Some(c) = self.peek() {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                name.push(c);
+                self.next();
+            } else {
+                break;
+            }
+        }
+        if name.is_empty() { None } else { Some(name) }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|c| c.is_whitespace()) {
+            self.next();
+        }
+    }
+}
+
+/// 变体文本以下一个变体开头结束；去掉两者之间的空白
+fn trim_trailing_whitespace(parts: &mut Vec<Part>) {
+    if let Some(Part::Text(text)) = parts.last_mut() {
+        let trimmed = text.trim_end().len();
+        text.truncate(trimmed);
+        if text.is_empty() {
+            parts.pop();
+        }
+    }
+}
+
+// ==============================================================================
+// Locale rules
+// ==============================================================================
+
+/// CLDR 基数复数类别（仅覆盖内置语言需要的规则）
+pub fn plural_category(language: &Language, n: f64) -> &'static str {
+    let is_integer = n.fract() == 0.0;
+    match language.primary() {
+        // 中文、日文、韩文没有复数变化
+        "zh" | "ja" | "ko" => "other",
+        // 法语: 0 和 1 都是 one
+        "fr" => {
+            if n.abs() < 2.0 && n.trunc().abs() <= 1.0 {
+                "one"
+            } else {
+                "other"
+            }
+        }
+        // 英语、德语、西班牙语等: 仅整数 1 是 one
+        _ => {
+            if is_integer && n.abs() == 1.0 {
+                "one"
+            } else {
+                "other"
+            }
+        }
+    }
+}
+
+/// 按语言习惯格式化数字（千位分隔符与小数点）
+pub fn format_number(language: &Language, n: f64) -> String {
+    let (group, decimal, min_grouping) = match language.primary() {
+        "de" => (".", ",", 1),
+        // 西班牙语四位数不分组（1000 而非 1.000）
+        "es" => (".", ",", 2),
+        "fr" => ("\u{202F}", ",", 1),
+        _ => (",", ".", 1),
+    };
+
+    let plain = format_plain_number(n);
+    let (sign, plain) = match plain.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", plain.as_str()),
+    };
+    let (int_part, frac_part) = match plain.split_once('.') {
+        Some((i, f)) => (i, Some(f)),
+        None => (plain, None),
+    };
+
+    let mut grouped = String::new();
+    // CLDR minimumGroupingDigits：最高一组至少有 min_grouping 位才分组
+    if int_part.len() >= 3 + min_grouping {
+        for (i, c) in int_part.chars().enumerate() {
+            if i > 0 && (int_part.len() - i) % 3 == 0 {
+                grouped.push_str(group);
+            }
+            grouped.push(c);
+        }
+    } else {
+        grouped.push_str(int_part);
+    }
+
+    match frac_part {
+        Some(frac) => format!("{}{}{}{}", sign, grouped, decimal, frac),
+        None => format!("{}{}", sign, grouped),
+    }
+}
+
+/// 不带分组的数字文本，整数不带小数部分
+fn format_plain_number(n: f64) -> String {
+    if n.fract() == 0.0 && n.abs() < 1e15 {
+        format!("{}", n as i64)
+    } else {
+        format!("{}", n)
+    }
+}
+
+// ==============================================================================
+// Pseudo-localization
+// ==============================================================================
+
+/// 伪本地化：字母替换为带重音的形式并加长约 40%，占位符和选择表达式保持不变
+///
+/// 用于在界面中发现被截断的文本和未走翻译的硬编码字符串，例如
+/// "Sites: {total}" -> "[Šîţéš: {total} ·····]"
+pub fn pseudo_localize(pattern: &str) -> String {
+    let parts = match parse_pattern(pattern) {
+        Some(parts) => parts,
+        None => return pattern.to_string(),
+    };
+
+    let mut body = String::with_capacity(pattern.len() * 2);
+    write_pseudo_parts(&parts, &mut body);
+
+    let padding = (visible_len(&parts) * 2).div_ceil(5).max(1);
+    format!("[{} {}]", body, "·".repeat(padding))
+}
+
+fn write_pseudo_parts(parts: &[Part], out: &mut String) {
+    for part in parts {
+        match part {
+            Part::Text(text) => {
+                for c in text.chars() {
+                    match c {
+                        '{' => out.push_str("{{"),
+                        '}' => out.push_str("}}"),
+                        _ => out.push(accent(c)),
+                    }
+                }
+            }
+            Part::Arg(name) => {
+                out.push('{');
+                out.push_str(name);
+                out.push('}');
+            }
+            Part::Select { name, variants, default } => {
+                out.push('{');
+                out.push_str(name);
+                out.push_str(" ->");
+                for (i, (key, variant)) in variants.iter().enumerate() {
+                    out.push(' ');
+                    if i == *default {
+                        out.push('*');
+                    }
+                    out.push('[');
+                    out.push_str(key);
+                    out.push_str("] ");
+                    write_pseudo_parts(variant, out);
+                }
+                out.push('}');
+            }
+        }
+    }
+}
+
+/// 显示出来的大致字符数（选择表达式按默认分支计算，参数按名称长度计算）
+fn visible_len(parts: &[Part]) -> usize {
+    parts.iter()
+        .map(|part| match part {
+            Part::Text(text) => text.chars().filter(|c| !c.is_whitespace()).count(),
+            Part::Arg(name) => name.len(),
+            Part::Select { variants, default, .. } => visible_len(&variants[*default].1),
+        })
+        .sum()
+}
+
+fn accent(c: char) -> char {
+    const PLAIN: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+    const ACCENTED: &str = "åƀçđéƒĝĥîĵķļɱñöþǫŕšţûṽŵẋýžÅƁÇĐÉƑĜĤÎĴĶĻṀÑÖÞǪŔŠŢÛṼŴẊÝŽ";
+    PLAIN.chars()
+        .position(|p| p == c)
+        .and_then(|i| ACCENTED.chars().nth(i))
+        .unwrap_or(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lang(tag: &str) -> Language {
+        Language::parse(tag).unwrap()
+    }
+
+    #[test]
+    fn plural_categories() {
+        let cases = [
+            ("en", 0.0, "other"), ("en", 1.0, "one"), ("en", 2.0, "other"), ("en", 1.5, "other"),
+            ("fr", 0.0, "one"), ("fr", 1.0, "one"), ("fr", 1.5, "one"), ("fr", 2.0, "other"),
+            ("zh", 0.0, "other"), ("zh", 1.0, "other"), ("zh", 2.0, "other"),
+        ];
+        for (tag, n, expected) in cases {
+            assert_eq!(plural_category(&lang(tag), n), expected, "{} {}", tag, n);
+        }
+    }
+
+    #[test]
+    fn groups_numbers_per_locale() {
+        let nnbsp = "\u{202F}";
+        let cases = [
+            ("en", 1234.0, "1,234".to_string()), ("en", 12345.0, "12,345".into()), ("en", 1234567.0, "1,234,567".into()),
+            ("de", 1234.0, "1.234".into()), ("de", 12345.0, "12.345".into()), ("de", 1234567.0, "1.234.567".into()),
+            ("fr", 1234.0, format!("1{}234", nnbsp)), ("fr", 12345.0, format!("12{}345", nnbsp)),
+            ("fr", 1234567.0, format!("1{0}234{0}567", nnbsp)),
+            // 西班牙语四位数不分组，五位数开始分组
+            ("es", 1234.0, "1234".into()), ("es", 12345.0, "12.345".into()), ("es", 100000.0, "100.000".into()),
+            ("es", 1234567.0, "1.234.567".into()),
+            ("en", -1234.5, "-1,234.5".into()), ("de", 1234.5, "1.234,5".into()), ("en", 999.0, "999".into()),
+        ];
+        for (tag, n, expected) in cases {
+            assert_eq!(format_number(&lang(tag), n), expected, "{} {}", tag, n);
+        }
+    }
+
+    #[test]
+    fn formats_select_expressions() {
+        let pattern = "{count -> [0] No sites [one] {count} site *[other] {count} sites}";
+        let en = lang("en");
+        let format = |language: &Language, n: usize| format_message(pattern, language, &TranslationArgs::new().count("count", n));
+        assert_eq!(format(&en, 0), "No sites");
+        assert_eq!(format(&en, 1), "1 site");
+        assert_eq!(format(&en, 1234), "1,234 sites");
+        // 精确值 [0] 优先于法语的 one 类别
+        let fr = lang("fr");
+        let pattern_fr = "{count -> [one] {count} site *[other] {count} sites}";
+        assert_eq!(format_message(pattern_fr, &fr, &TranslationArgs::new().count("count", 0)), "0 site");
+
+        let kind = "{kind -> [proxy] Reverse proxy *[other] Website}";
+        assert_eq!(format_message(kind, &en, &TranslationArgs::new().text("kind", "proxy")), "Reverse proxy");
+        assert_eq!(format_message(kind, &en, &TranslationArgs::new().text("kind", "php")), "Website");
+        // 缺少参数时使用默认变体
+        assert_eq!(format_message(kind, &en, &TranslationArgs::new()), "Website");
+        assert_eq!(placeholders(pattern), Some(vec!["count".to_string()]));
+    }
+
+    #[test]
+    fn escapes_and_malformed_patterns() {
+        let en = lang("en");
+        let args = TranslationArgs::new().text("name", "blog");
+        assert_eq!(format_message("{{literal}} {name}", &en, &args), "{literal} blog");
+        assert_eq!(format_message("Hello {missing}", &en, &args), "Hello {missing}");
+        // 格式错误的文本原样返回
+        for pattern in ["{name", "a } b", "{name -> [one] x}", "{ -> *[other] x}"] {
+            assert_eq!(format_message(pattern, &en, &args), pattern);
+            assert_eq!(placeholders(pattern), None);
+        }
+    }
+}
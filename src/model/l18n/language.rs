@@ -0,0 +1,318 @@
+// MIT License
+//
+// Copyright (c) 2026 Laffinty
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// ==============================================================================
+// Language - BCP-47 language tags
+// ==============================================================================
+// 支持 language[-Script][-REGION] 形式的标签（如 en、zh-CN、zh-Hant-TW），
+// 大小写会被规范化，'_' 视为 '-'（便于直接解析 zh_TW 这类写法）。
+//
+// 回退链：先去掉地区并补全文字（zh-TW -> zh-Hant），再退到语言本身（zh），
+// 最后是源语言 en。例如 zh-TW -> zh-Hant -> zh -> en。
+
+use std::sync::Arc;
+
+/// 源语言 - 所有键都必须在源语言中定义，也是回退链的终点
+pub const SOURCE_LANGUAGE_TAG: &str = "en";
+
+/// 未检测到语言时的默认语言
+pub const DEFAULT_LANGUAGE_TAG: &str = "zh-CN";
+
+/// 伪本地化语言 - 由源语言自动生成，用于检查截断和硬编码字符串
+pub const PSEUDO_LANGUAGE_TAG: &str = "en-XA";
+
+/// 规范化的 BCP-47 语言标签
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Language {
+    tag: Arc<str>,
+}
+
+impl Language {
+    /// 解析并规范化语言标签，非法标签返回 None
+    pub fn parse(tag: &str) -> Option<Self> {
+        let mut subtags = tag.split(['-', '_']);
+
+        let language = subtags.next()?;
+        if !(2..=3).contains(&language.len()) || !language.chars().all(|c| c.is_ascii_alphabetic()) {
+            return None;
+        }
+        let mut normalized = language.to_ascii_lowercase();
+        let mut seen_script = false;
+        let mut seen_region = false;
+
+        for subtag in subtags {
+            let is_alpha = subtag.chars().all(|c| c.is_ascii_alphabetic());
+            let is_digit = subtag.chars().all(|c| c.is_ascii_digit());
+            normalized.push('-');
+
+            if subtag.len() == 4 && is_alpha && !seen_script && !seen_region {
+                // Script: Titlecase
+                seen_script = true;
+                normalized.push_str(&subtag[..1].to_ascii_uppercase());
+                normalized.push_str(&subtag[1..].to_ascii_lowercase());
+            } else if ((subtag.len() == 2 && is_alpha) || (subtag.len() == 3 && is_digit)) && !seen_region {
+                // Region: UPPERCASE
+                seen_region = true;
+                normalized.push_str(&subtag.to_ascii_uppercase());
+            } else if is_variant(subtag) {
+                // Variant: lowercase
+                normalized.push_str(&subtag.to_ascii_lowercase());
+            } else {
+                return None;
+            }
+        }
+
+        Some(Self { tag: normalized.into() })
+    }
+
+    /// 源语言 (en)
+    pub fn source() -> Self {
+        Self::parse(SOURCE_LANGUAGE_TAG).expect("valid source language tag")
+    }
+
+    /// 伪本地化语言 (en-XA)
+    pub fn pseudo() -> Self {
+        Self::parse(PSEUDO_LANGUAGE_TAG).expect("valid pseudo language tag")
+    }
+
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// 语言子标签（zh-Hant-TW -> zh）
+    pub fn primary(&self) -> &str {
+        self.tag.split('-').next().unwrap_or(&self.tag)
+    }
+
+    pub fn script(&self) -> Option<&str> {
+        self.tag.split('-').skip(1).find(|s| s.len() == 4 && s.chars().all(|c| c.is_ascii_alphabetic()))
+    }
+
+    pub fn region(&self) -> Option<&str> {
+        self.tag.split('-').skip(1).find(|s| {
+            (s.len() == 2 && s.chars().all(|c| c.is_ascii_uppercase())) || (s.len() == 3 && s.chars().all(|c| c.is_ascii_digit()))
+        })
+    }
+
+    /// 推断文字（仅覆盖需要区分文字的语言）
+    fn likely_script(&self) -> Option<&str> {
+        if let Some(script) = self.script() {
+            return Some(script);
+        }
+        match (self.primary(), self.region()) {
+            ("zh", Some("TW")) | ("zh", Some("HK")) | ("zh", Some("MO")) => Some("Hant"),
+            ("zh", _) => Some("Hans"),
+            _ => None,
+        }
+    }
+
+    /// 查找翻译时的回退链，从最具体到源语言
+    pub fn fallback_chain(&self) -> Vec<Language> {
+        let mut chain = vec![self.clone()];
+        let mut push = |tag: String| {
+            if let Some(language) = Language::parse(&tag) {
+                if !chain.contains(&language) {
+                    chain.push(language);
+                }
+            }
+        };
+
+        if let Some(script) = self.likely_script() {
+            push(format!("{}-{}", self.primary(), script));
+        }
+        push(self.primary().to_string());
+        push(SOURCE_LANGUAGE_TAG.to_string());
+        chain
+    }
+    
+    /// 从 POSIX locale 解析语言（zh_TW.UTF-8、en_US@euro 等），C/POSIX 返回 None
+    pub fn from_posix_locale(locale: &str) -> Option<Self> {
+        let locale = locale.split(['.', '@']).next().unwrap_or_default();
+        if locale.is_empty() || locale == "C" || locale == "POSIX" {
+            return None;
+        }
+        Self::parse(locale)
+    }
+    
+    /// 系统语言：依次读取 LC_ALL、LC_MESSAGES、LANG 中第一个非空值
+    pub fn system() -> Option<Self> {
+        Self::from_environment(|name| std::env::var(name).ok())
+    }
+    
+    fn from_environment(var: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        ["LC_ALL", "LC_MESSAGES", "LANG"].iter()
+            .filter_map(|name| var(name))
+            .find(|value| !value.is_empty())
+            .and_then(|value| Self::from_posix_locale(&value))
+    }
+    
+    /// 在可用语言中选择最接近的一个
+    ///
+    /// 依次尝试：完全匹配、回退链（不含源语言）、同语言同文字、同语言
+    /// 例如 zh-SG 匹配 zh-CN（同为简体），zh-HK 匹配 zh-TW（同为繁体）
+    pub fn best_match(&self, available: &[Language]) -> Option<Language> {
+        let chain = self.fallback_chain();
+        chain.iter()
+            .filter(|language| language.primary() == self.primary())
+            .find(|language| available.contains(language))
+            .or_else(|| available.iter().find(|candidate| {
+                candidate.primary() == self.primary() && candidate.likely_script() == self.likely_script()
+            }))
+            .or_else(|| available.iter().find(|candidate| candidate.primary() == self.primary()))
+            .cloned()
+    }
+}
+
+/// 变体子标签：5-8 位字母数字，或以数字开头的 4 位（如 1996）
+fn is_variant(subtag: &str) -> bool {
+    let alphanumeric = subtag.chars().all(|c| c.is_ascii_alphanumeric());
+    alphanumeric && ((5..=8).contains(&subtag.len()) || (subtag.len() == 4 && subtag.starts_with(|c: char| c.is_ascii_digit())))
+}
+
+impl Default for Language {
+    fn default() -> Self {
+        Self::parse(DEFAULT_LANGUAGE_TAG).expect("valid default language tag")
+    }
+}
+
+impl std::fmt::Debug for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.tag)
+    }
+}
+
+impl std::fmt::Display for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.tag)
+    }
+}
+
+/// 可选语言 - 语言标签及其自称（来自资源中的 language_name 键）
+#[derive(Clone, Debug, PartialEq)]
+pub struct LanguageInfo {
+    pub language: Language,
+    pub name: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_system_language() {
+        let detect = |vars: &[(&str, &str)]| {
+            Language::from_environment(|name| vars.iter().find(|(n, _)| *n == name).map(|(_, v)| v.to_string()))
+                .map(|language| language.tag().to_string())
+        };
+        // LC_ALL > LC_MESSAGES > LANG，空值跳过
+        assert_eq!(detect(&[("LC_ALL", "de_DE.UTF-8"), ("LANG", "fr_FR.UTF-8")]).as_deref(), Some("de-DE"));
+        assert_eq!(detect(&[("LC_ALL", ""), ("LC_MESSAGES", "zh_TW.UTF-8"), ("LANG", "en_US")]).as_deref(), Some("zh-TW"));
+        assert_eq!(detect(&[("LANG", "ja_JP.eucJP")]).as_deref(), Some("ja-JP"));
+        // 第一个非空值为 C 时不再继续查找
+        assert_eq!(detect(&[("LC_ALL", "C"), ("LANG", "de_DE.UTF-8")]), None);
+        assert_eq!(detect(&[]), None);
+    }
+
+    fn tags(languages: &[Language]) -> Vec<&str> {
+        languages.iter().map(Language::tag).collect()
+    }
+
+    #[test]
+    fn parses_and_normalizes_tags() {
+        let cases = [
+            ("en", Some("en")),
+            ("EN", Some("en")),
+            ("zh_cn", Some("zh-CN")),
+            ("zh-hant-tw", Some("zh-Hant-TW")),
+            ("ZH_HANS", Some("zh-Hans")),
+            ("es-419", Some("es-419")),
+            ("de-CH-1996", Some("de-CH-1996")),
+            ("sr-Latn-RS", Some("sr-Latn-RS")),
+            ("", None),
+            ("e", None),
+            ("english", None),
+            ("en--US", None),
+            ("en-US-GB", None),
+            ("zh-TW-Hant", None),
+            ("12", None),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(Language::parse(input).as_ref().map(Language::tag), expected, "{:?}", input);
+        }
+        let language = Language::parse("zh_hant_tw").unwrap();
+        assert_eq!((language.primary(), language.script(), language.region()), ("zh", Some("Hant"), Some("TW")));
+    }
+
+    #[test]
+    fn builds_fallback_chains() {
+        let cases: [(&str, &[&str]); 6] = [
+            ("en", &["en"]),
+            ("en-GB", &["en-GB", "en"]),
+            ("de-AT", &["de-AT", "de", "en"]),
+            ("zh-TW", &["zh-TW", "zh-Hant", "zh", "en"]),
+            ("zh-SG", &["zh-SG", "zh-Hans", "zh", "en"]),
+            ("zh-Hant-HK", &["zh-Hant-HK", "zh-Hant", "zh", "en"]),
+        ];
+        for (tag, expected) in cases {
+            assert_eq!(tags(&Language::parse(tag).unwrap().fallback_chain()), expected, "{}", tag);
+        }
+    }
+
+    #[test]
+    fn matches_closest_available_language() {
+        let available: Vec<Language> = ["en", "zh-CN", "zh-TW", "de", "pt-BR"].iter().map(|tag| Language::parse(tag).unwrap()).collect();
+        let cases = [
+            ("de", Some("de")),
+            ("de-CH", Some("de")),
+            ("en-AU", Some("en")),
+            ("zh-CN", Some("zh-CN")),
+            ("zh-SG", Some("zh-CN")),
+            ("zh-HK", Some("zh-TW")),
+            ("zh-Hant", Some("zh-TW")),
+            ("pt-PT", Some("pt-BR")),
+            ("fr", None),
+        ];
+        for (tag, expected) in cases {
+            let found = Language::parse(tag).unwrap().best_match(&available);
+            assert_eq!(found.as_ref().map(Language::tag), expected, "{}", tag);
+        }
+    }
+
+    #[test]
+    fn parses_posix_locales() {
+        let cases = [
+            ("zh_CN.UTF-8", Some("zh-CN")),
+            ("zh_CN.UTF-8@pinyin", Some("zh-CN")),
+            ("de_DE@euro", Some("de-DE")),
+            ("en_US", Some("en-US")),
+            ("fr", Some("fr")),
+            ("C", None),
+            ("C.UTF-8", None),
+            ("POSIX", None),
+            ("", None),
+            (".UTF-8", None),
+        ];
+        for (locale, expected) in cases {
+            assert_eq!(Language::from_posix_locale(locale).as_ref().map(Language::tag), expected, "{:?}", locale);
+        }
+    }
+}
@@ -0,0 +1,402 @@
+// MIT License
+//
+// Copyright (c) 2026 Laffinty
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// ==============================================================================
+// Translation resource loader
+// ==============================================================================
+// 翻译资源为每个语言一个 TOML 文件（locales/<tag>.toml），格式为扁平的
+// `key = "value"`。编译时嵌入 locales/ 下的文件作为默认值，运行时用户目录
+// （<config_dir>/locales 或 EASYNGINX_LOCALE_DIR）中的同名文件可覆盖其中的键。
+//
+// en.toml 是源语言：其它语言中出现 en.toml 没有的键会被报告为未知键。
+// 重复键由 TOML 解析器拒绝，整个文件会被报告并跳过。
+//
+// 可用语言由资源文件决定：在用户目录放入合法标签命名的文件（如 ja.toml）
+// 即可增加新语言，无需修改代码。每个资源应定义 language_name（语言自称）。
+//
+// 伪本地化语言 en-XA 不需要资源文件，加载时由源语言生成（见 format::pseudo_localize）。
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use super::{Language, LanguageInfo, SOURCE_LANGUAGE_TAG};
+use super::format::pseudo_localize;
+
+/// 编译时嵌入的默认翻译资源：(语言标签, 文件名, 内容)
+const EMBEDDED_BUNDLES: &[(&str, &str, &str)] = &[
+    ("en", "locales/en.toml", include_str!("../../../locales/en.toml")),
+    ("zh-CN", "locales/zh-CN.toml", include_str!("../../../locales/zh-CN.toml")),
+    ("zh-TW", "locales/zh-TW.toml", include_str!("../../../locales/zh-TW.toml")),
+    ("ja", "locales/ja.toml", include_str!("../../../locales/ja.toml")),
+    ("de", "locales/de.toml", include_str!("../../../locales/de.toml")),
+    ("fr", "locales/fr.toml", include_str!("../../../locales/fr.toml")),
+    ("es", "locales/es.toml", include_str!("../../../locales/es.toml")),
+];
+
+/// 每个资源中记录语言自称的键，用于生成语言菜单
+pub const LANGUAGE_NAME_KEY: &str = "language_name";
+
+/// 伪本地化语言在菜单中的名称（保持原样，便于辨认）
+const PSEUDO_LANGUAGE_NAME: &str = "Pseudo-locale (en-XA)";
+
+/// 加载过程中发现的问题（未知键、重复键、解析错误等）
+#[derive(Clone, Debug)]
+pub struct LoadIssue {
+    /// 出问题的资源文件
+    pub source: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for LoadIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.source, self.message)
+    }
+}
+
+/// 加载结果：(key, language) -> 文本、可用语言，以及加载时发现的问题
+pub struct LoadedTranslations {
+    pub translations: HashMap<(String, Language), String>,
+    /// 按标签排序的可用语言
+    pub available: Vec<LanguageInfo>,
+    /// 资源中定义了、但源语言中没有的键（不会被使用）
+    pub unknown_keys: Vec<(Language, String)>,
+    pub issues: Vec<LoadIssue>,
+}
+
+/// 用户覆盖目录：EASYNGINX_LOCALE_DIR，否则为 <config_dir>/locales
+pub fn user_locale_dir() -> PathBuf {
+    std::env::var_os("EASYNGINX_LOCALE_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| crate::model::config_dir().join("locales"))
+}
+
+/// 加载嵌入的默认翻译，再应用用户目录中的覆盖
+pub fn load_translations() -> LoadedTranslations {
+    load_translations_from(&user_locale_dir())
+}
+
+/// 加载嵌入的默认翻译，再应用 dir 中的覆盖
+pub fn load_translations_from(dir: &Path) -> LoadedTranslations {
+    let mut loaded = LoadedTranslations {
+        translations: HashMap::new(),
+        available: Vec::new(),
+        unknown_keys: Vec::new(),
+        issues: Vec::new(),
+    };
+    let mut languages: Vec<Language> = Vec::new();
+
+    // 源语言的键集合，用于检查其它语言中的未知键
+    let source_keys: Vec<String> = EMBEDDED_BUNDLES.iter()
+        .find(|(tag, _, _)| *tag == SOURCE_LANGUAGE_TAG)
+        .and_then(|(_, name, text)| parse_bundle(name, text, &mut loaded.issues))
+        .map(|messages| messages.into_iter().map(|(key, _)| key).collect())
+        .unwrap_or_default();
+
+    for (tag, name, text) in EMBEDDED_BUNDLES {
+        let language = Language::parse(tag).expect("embedded bundle has a valid tag");
+        if let Some(messages) = parse_bundle(name, text, &mut loaded.issues) {
+            merge_bundle(&mut loaded, &language, name, messages, &source_keys);
+            languages.push(language);
+        }
+    }
+
+    for (language, path) in user_bundle_files(dir, &mut loaded.issues) {
+        let name = path.display().to_string();
+        match std::fs::read_to_string(&path) {
+            Ok(text) => {
+                if let Some(messages) = parse_bundle(&name, &text, &mut loaded.issues) {
+                    println!("[I18n] Loaded translations for {} from {}", language, name);
+                    merge_bundle(&mut loaded, &language, &name, messages, &source_keys);
+                    languages.push(language);
+                }
+            }
+            Err(e) => loaded.issues.push(LoadIssue { source: name, message: e.to_string() }),
+        }
+    }
+
+    // 用户提供了 en-XA.toml 时以用户的为准
+    let pseudo = Language::pseudo();
+    if !languages.contains(&pseudo) {
+        add_pseudo_locale(&mut loaded, &pseudo);
+        languages.push(pseudo);
+    }
+
+    languages.sort();
+    languages.dedup();
+    loaded.available = languages.into_iter()
+        .map(|language| {
+            let name = loaded.translations
+                .get(&(LANGUAGE_NAME_KEY.to_string(), language.clone()))
+                .cloned()
+                .unwrap_or_else(|| language.tag().to_string());
+            LanguageInfo { language, name }
+        })
+        .collect();
+
+    loaded
+}
+
+/// 源语言资源中每个键所属的注释（键上方最近的注释块），供翻译导出使用
+pub fn source_comments() -> HashMap<String, String> {
+    let text = EMBEDDED_BUNDLES.iter()
+        .find(|(tag, _, _)| *tag == SOURCE_LANGUAGE_TAG)
+        .map(|(_, _, text)| *text)
+        .unwrap_or_default();
+
+    let mut comments = HashMap::new();
+    let mut block: Vec<&str> = Vec::new();
+    let mut current = String::new();
+    for line in text.lines().map(str::trim) {
+        if let Some(comment) = line.strip_prefix('#') {
+            block.push(comment.trim());
+        } else {
+            if !block.is_empty() {
+                current = block.join(" ");
+                block.clear();
+            }
+            if let Some((key, _)) = line.split_once('=') {
+                comments.insert(key.trim().to_string(), current.clone());
+            }
+        }
+    }
+    comments
+}
+
+/// 可用资源对应的语言（只看文件名，不解析内容）
+pub fn bundle_languages() -> Vec<Language> {
+    let mut languages: Vec<Language> = EMBEDDED_BUNDLES.iter()
+        .filter_map(|(tag, _, _)| Language::parse(tag))
+        .collect();
+    languages.push(Language::pseudo());
+    let mut ignored = Vec::new();
+    languages.extend(user_bundle_files(&user_locale_dir(), &mut ignored).into_iter().map(|(language, _)| language));
+    languages.sort();
+    languages.dedup();
+    languages
+}
+
+/// 用户目录中资源文件的状态：(路径, 修改时间, 大小)，用于检测变化以便热重载
+pub type BundleStamp = Vec<(PathBuf, Option<std::time::SystemTime>, u64)>;
+
+pub fn user_bundle_stamp(dir: &Path) -> BundleStamp {
+    let mut ignored = Vec::new();
+    user_bundle_files(dir, &mut ignored).into_iter()
+        .map(|(_, path)| {
+            let metadata = std::fs::metadata(&path).ok();
+            let modified = metadata.as_ref().and_then(|m| m.modified().ok());
+            let len = metadata.map(|m| m.len()).unwrap_or_default();
+            (path, modified, len)
+        })
+        .collect()
+}
+
+/// 列出用户目录中可识别的 <tag>.toml 文件
+fn user_bundle_files(dir: &Path, issues: &mut Vec<LoadIssue>) -> Vec<(Language, PathBuf)> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut files = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("toml") {
+            continue;
+        }
+        let tag = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+        match Language::parse(tag) {
+            Some(language) => files.push((language, path)),
+            None => issues.push(LoadIssue {
+                source: path.display().to_string(),
+                message: format!("'{}' is not a valid language tag", tag),
+            }),
+        }
+    }
+    files.sort_by(|a, b| a.1.cmp(&b.1));
+    files
+}
+
+/// 解析单个资源文件，返回其中的 (key, value)
+fn parse_bundle(name: &str, text: &str, issues: &mut Vec<LoadIssue>) -> Option<Vec<(String, String)>> {
+    let table: toml::Table = match text.parse() {
+        Ok(table) => table,
+        Err(e) => {
+            // 重复键也在这里报告（TOML 不允许重复键）
+            let message = match e.span() {
+                Some(span) => format!("line {}: {}", text[..span.start].matches('\n').count() + 1, e.message()),
+                None => e.message().to_string(),
+            };
+            issues.push(LoadIssue { source: name.to_string(), message });
+            return None;
+        }
+    };
+
+    let mut messages = Vec::with_capacity(table.len());
+    for (key, value) in table {
+        match value {
+            toml::Value::String(text) => messages.push((key, text)),
+            other => issues.push(LoadIssue {
+                source: name.to_string(),
+                message: format!("key '{}' must be a string, found {}", key, other.type_str()),
+            }),
+        }
+    }
+    Some(messages)
+}
+
+fn merge_bundle(
+    loaded: &mut LoadedTranslations,
+    language: &Language,
+    name: &str,
+    messages: Vec<(String, String)>,
+    source_keys: &[String],
+) {
+    for (key, value) in messages {
+        if !source_keys.contains(&key) {
+            loaded.issues.push(LoadIssue {
+                source: name.to_string(),
+                message: format!("unknown key '{}'", key),
+            });
+            loaded.unknown_keys.push((language.clone(), key));
+            continue;
+        }
+        loaded.translations.insert((key, language.clone()), value);
+    }
+}
+
+/// 由源语言生成伪本地化文本
+fn add_pseudo_locale(loaded: &mut LoadedTranslations, pseudo: &Language) {
+    let source = Language::source();
+    let messages: Vec<(String, String)> = loaded.translations.iter()
+        .filter(|((_, language), _)| *language == source)
+        .map(|((key, _), value)| {
+            let text = if key == LANGUAGE_NAME_KEY {
+                PSEUDO_LANGUAGE_NAME.to_string()
+            } else {
+                pseudo_localize(value)
+            };
+            (key.clone(), text)
+        })
+        .collect();
+    for (key, text) in messages {
+        loaded.translations.insert((key, pseudo.clone()), text);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::l18n::Key;
+
+    fn embedded(tag: &str) -> Vec<(String, String)> {
+        let (_, name, text) = EMBEDDED_BUNDLES.iter()
+            .find(|(t, _, _)| *t == tag)
+            .expect("embedded bundle");
+        let mut issues = Vec::new();
+        let messages = parse_bundle(name, text, &mut issues).expect("bundle parses");
+        assert!(issues.is_empty(), "{}: {:?}", name, issues);
+        messages
+    }
+
+    #[test]
+    fn every_locale_defines_every_key() {
+        for (tag, name, _) in EMBEDDED_BUNDLES {
+            let messages = embedded(tag);
+            let missing: Vec<&str> = Key::ALL.iter()
+                .map(|key| key.as_str())
+                .filter(|key| !messages.iter().any(|(k, _)| k == key))
+                .collect();
+            assert!(missing.is_empty(), "{} is missing keys: {:?}", name, missing);
+        }
+    }
+
+    #[test]
+    fn no_locale_defines_unknown_keys() {
+        for (tag, name, _) in EMBEDDED_BUNDLES {
+            let unknown: Vec<String> = embedded(tag).into_iter()
+                .map(|(key, _)| key)
+                .filter(|key| Key::from_name(key).is_none())
+                .collect();
+            assert!(unknown.is_empty(), "{} defines unknown keys: {:?}", name, unknown);
+        }
+    }
+
+    #[test]
+    fn placeholders_match_source_locale() {
+        let source = embedded(SOURCE_LANGUAGE_TAG);
+        for (tag, name, _) in EMBEDDED_BUNDLES {
+            for (key, value) in embedded(tag) {
+                let (_, source_value) = source.iter().find(|(k, _)| *k == key).expect("source key");
+                assert_eq!(
+                    crate::model::l18n::format::placeholders(&value),
+                    crate::model::l18n::format::placeholders(source_value),
+                    "{}: placeholders of '{}' differ from the source locale", name, key
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn pseudo_locale_keeps_placeholders() {
+        for (key, value) in embedded(SOURCE_LANGUAGE_TAG) {
+            let pseudo = pseudo_localize(&value);
+            assert_ne!(pseudo, value, "'{}' was not pseudo-localized", key);
+            assert_eq!(
+                crate::model::l18n::format::placeholders(&pseudo),
+                crate::model::l18n::format::placeholders(&value),
+                "pseudo-localization changed the placeholders of '{}'", key
+            );
+        }
+    }
+
+    #[test]
+    fn merges_user_overrides_and_reports_problems() {
+        let dir = std::env::temp_dir().join(format!("easynginx-locales-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("de.toml"), "menu_file = \"Datei (eigene)\"\nnot_a_key = \"x\"\n").unwrap();
+        // 重复键使整个文件解析失败并被跳过
+        std::fs::write(dir.join("fr.toml"), "menu_file = \"A\"\nmenu_file = \"B\"\nmenu_help = \"C\"\n").unwrap();
+        std::fs::write(dir.join("eo.toml"), "language_name = \"Esperanto\"\nmenu_file = \"Dosiero\"\n").unwrap();
+
+        let loaded = load_translations_from(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+        let text = |key: &str, tag: &str| loaded.translations.get(&(key.to_string(), Language::parse(tag).unwrap())).cloned();
+
+        // 覆盖只替换给出的键，其余保留嵌入的翻译
+        assert_eq!(text("menu_file", "de").as_deref(), Some("Datei (eigene)"));
+        assert_eq!(text("menu_help", "de").as_deref(), Some("Hilfe"));
+        assert_eq!(text("not_a_key", "de"), None);
+        assert_eq!(loaded.unknown_keys, vec![(Language::parse("de").unwrap(), "not_a_key".to_string())]);
+
+        assert_eq!(text("menu_file", "fr").as_deref(), Some("Fichier"));
+        assert_eq!(text("menu_help", "fr").as_deref(), Some("Aide"));
+
+        assert_eq!(text("menu_file", "eo").as_deref(), Some("Dosiero"));
+        assert!(loaded.available.iter().any(|info| info.language.tag() == "eo" && info.name == "Esperanto"));
+
+        assert_eq!(loaded.issues.len(), 2, "{:?}", loaded.issues);
+        let issue = |file: &str| loaded.issues.iter()
+            .find(|issue| issue.source.ends_with(file))
+            .unwrap_or_else(|| panic!("no issue for {}", file));
+        assert!(issue("de.toml").message.contains("unknown key 'not_a_key'"));
+        assert!(issue("fr.toml").message.starts_with("line 2:"), "{}", issue("fr.toml"));
+    }
+}
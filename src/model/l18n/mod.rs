@@ -22,35 +22,82 @@
 
 use async_trait::async_trait;
 use std::any::{Any, TypeId};
+use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use crate::{MessageEnvelope, MessageBus, Module, module_init};
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
-pub enum Language {
-    English,
-    ChineseSimplified,
+pub mod loader;
+pub mod format;
+pub mod language;
+pub mod report;
+pub mod po;
+
+/// 编译时由 locales/en.toml 生成的翻译键（见 build.rs）
+pub mod keys {
+    include!(concat!(env!("OUT_DIR"), "/translation_keys.rs"));
 }
 
-impl Default for Language {
-    fn default() -> Self {
-        Language::ChineseSimplified
+pub use keys::Key;
+pub use format::{format_message, TranslationArg, TranslationArgs};
+pub use language::{Language, LanguageInfo, SOURCE_LANGUAGE_TAG, DEFAULT_LANGUAGE_TAG, PSEUDO_LANGUAGE_TAG};
+pub use report::LocaleReport;
+
+use crate::model::settings::Settings;
+
+/// 启动时使用的语言
+///
+/// 优先使用设置文件中保存的选择，其次是系统 locale（LC_ALL / LC_MESSAGES / LANG）
+/// 匹配到的最接近的可用语言，都没有时使用默认语言
+pub fn initial_language() -> Language {
+    choose_language(Settings::load().language.as_deref(), Language::system(), &loader::bundle_languages())
+}
+
+fn choose_language(saved: Option<&str>, system: Option<Language>, available: &[Language]) -> Language {
+    let saved = saved
+        .and_then(Language::parse)
+        .filter(|language| available.contains(language));
+    if let Some(language) = saved {
+        return language;
     }
+    
+    system
+        .and_then(|system| system.best_match(available))
+        .unwrap_or_default()
 }
 
 #[derive(Clone, Debug)]
 pub struct TranslationRequest {
     pub key: String,
     pub language: Language,
+    /// 命名参数 - 响应中的译文已填充参数
+    pub args: TranslationArgs,
+    /// 请求者模块名称，用于路由响应
+    pub requester: String,
+    /// 请求方缓存的版本，原样返回在响应中（用于丢弃过期响应）
+    pub generation: u64,
 }
 
 impl TranslationRequest {
-    pub fn new(key: &str, language: Language) -> Self {
+    pub fn new(key: Key, language: Language, requester: &str) -> Self {
         Self {
-            key: key.to_string(),
+            key: key.as_str().to_string(),
             language,
+            args: TranslationArgs::new(),
+            requester: requester.to_string(),
+            generation: 0,
         }
     }
+    
+    pub fn with_args(mut self, args: TranslationArgs) -> Self {
+        self.args = args;
+        self
+    }
+    
+    pub fn with_generation(mut self, generation: u64) -> Self {
+        self.generation = generation;
+        self
+    }
 }
 
 impl crate::Message for TranslationRequest {
@@ -72,14 +119,21 @@ pub struct TranslationResponse {
     pub key: String,
     pub translation: String,
     pub language: Language,
+    pub requester: String,
+    /// 译文已按请求的参数填充，不是可以缓存的原始文本
+    pub formatted: bool,
+    pub generation: u64,
 }
 
 impl TranslationResponse {
-    pub fn new(key: &str, translation: &str, language: Language) -> Self {
+    pub fn new(key: &str, translation: &str, language: Language, requester: &str, generation: u64) -> Self {
         Self {
             key: key.to_string(),
             translation: translation.to_string(),
             language,
+            requester: requester.to_string(),
+            formatted: false,
+            generation,
         }
     }
 }
@@ -131,16 +185,24 @@ pub struct BatchTranslationRequest {
     pub keys: Vec<String>,
     pub language: Language,
     pub requester: String,  // 请求者模块名称，用于路由响应
+    /// 请求方缓存的版本，原样返回在响应中（用于丢弃过期响应）
+    pub generation: u64,
 }
 
 impl BatchTranslationRequest {
-    pub fn new(keys: Vec<&str>, language: Language, requester: &str) -> Self {
+    pub fn new(keys: &[Key], language: Language, requester: &str) -> Self {
         Self {
-            keys: keys.iter().map(|&s| s.to_string()).collect(),
+            keys: keys.iter().map(|key| key.as_str().to_string()).collect(),
             language,
             requester: requester.to_string(),
+            generation: 0,
         }
     }
+    
+    pub fn with_generation(mut self, generation: u64) -> Self {
+        self.generation = generation;
+        self
+    }
 }
 
 impl crate::Message for BatchTranslationRequest {
@@ -158,19 +220,23 @@ impl crate::Message for BatchTranslationRequest {
 }
 
 /// 批量翻译响应 - 返回所有请求的翻译
+///
+/// 返回未填充参数的原始文本，调用方用 format_message 填充参数
 #[derive(Clone, Debug)]
 pub struct BatchTranslationResponse {
     pub translations: HashMap<String, String>,
     pub language: Language,
     pub requester: String,
+    pub generation: u64,
 }
 
 impl BatchTranslationResponse {
-    pub fn new(translations: HashMap<String, String>, language: Language, requester: &str) -> Self {
+    pub fn new(translations: HashMap<String, String>, language: Language, requester: &str, generation: u64) -> Self {
         Self {
             translations,
             language,
             requester: requester.to_string(),
+            generation,
         }
     }
 }
@@ -189,169 +255,285 @@ impl crate::Message for BatchTranslationResponse {
     }
 }
 
+/// 可用语言查询 - UI 用于生成语言菜单
+#[derive(Clone, Debug)]
+pub struct AvailableLanguagesRequest {
+    pub requester: String,
+}
+
+impl AvailableLanguagesRequest {
+    pub fn new(requester: &str) -> Self {
+        Self {
+            requester: requester.to_string(),
+        }
+    }
+}
+
+impl crate::Message for AvailableLanguagesRequest {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn message_type(&self) -> TypeId {
+        TypeId::of::<AvailableLanguagesRequest>()
+    }
+    
+    fn clone_box(&self) -> Box<dyn crate::Message> {
+        Box::new(self.clone())
+    }
+}
+
+/// 可用语言列表 - 由已加载的翻译资源决定
+#[derive(Clone, Debug)]
+pub struct AvailableLanguagesResponse {
+    pub languages: Vec<LanguageInfo>,
+    pub requester: String,
+}
+
+impl crate::Message for AvailableLanguagesResponse {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn message_type(&self) -> TypeId {
+        TypeId::of::<AvailableLanguagesResponse>()
+    }
+    
+    fn clone_box(&self) -> Box<dyn crate::Message> {
+        Box::new(self.clone())
+    }
+}
+
+/// 翻译完整性报告查询
+#[derive(Clone, Debug)]
+pub struct TranslationReportRequest {
+    pub requester: String,
+}
+
+impl TranslationReportRequest {
+    pub fn new(requester: &str) -> Self {
+        Self {
+            requester: requester.to_string(),
+        }
+    }
+}
+
+impl crate::Message for TranslationReportRequest {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn message_type(&self) -> TypeId {
+        TypeId::of::<TranslationReportRequest>()
+    }
+    
+    fn clone_box(&self) -> Box<dyn crate::Message> {
+        Box::new(self.clone())
+    }
+}
+
+/// 每个可用语言的缺失 / 未使用键，以及加载资源时发现的问题
+#[derive(Clone, Debug)]
+pub struct TranslationReportResponse {
+    pub reports: Vec<LocaleReport>,
+    pub issues: Vec<String>,
+    pub requester: String,
+}
+
+impl crate::Message for TranslationReportResponse {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn message_type(&self) -> TypeId {
+        TypeId::of::<TranslationReportResponse>()
+    }
+    
+    fn clone_box(&self) -> Box<dyn crate::Message> {
+        Box::new(self.clone())
+    }
+}
+
+/// 翻译资源已重新加载（用户目录中的文件发生变化）
+///
+/// 收到后应丢弃已缓存的翻译并重新请求
+#[derive(Clone, Debug)]
+pub struct TranslationsReloaded {
+    pub languages: Vec<LanguageInfo>,
+    /// 重新加载时发现的资源问题
+    pub issues: Vec<String>,
+}
+
+impl crate::Message for TranslationsReloaded {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn message_type(&self) -> TypeId {
+        TypeId::of::<TranslationsReloaded>()
+    }
+    
+    fn clone_box(&self) -> Box<dyn crate::Message> {
+        Box::new(self.clone())
+    }
+}
+
+/// 检查用户翻译目录变化的间隔
+const RELOAD_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+#[derive(Clone)]
 pub struct I18nModule {
     name: &'static str,
     bus: Arc<RwLock<Option<Arc<MessageBus>>>>,
     current_language: Arc<RwLock<Language>>,
     translations: Arc<RwLock<HashMap<(String, Language), String>>>,
+    /// 已加载资源对应的语言
+    available: Arc<RwLock<Vec<LanguageInfo>>>,
+    /// 资源中定义了但程序不使用的键
+    unknown_keys: Arc<RwLock<Vec<(Language, String)>>>,
+    /// 加载资源时发现的问题
+    issues: Arc<RwLock<Vec<String>>>,
+    /// 运行期间请求过、但在请求的语言中没有定义的键
+    runtime_misses: Arc<RwLock<HashMap<Language, BTreeSet<String>>>>,
+    /// 用户翻译目录（见 loader::user_locale_dir）
+    locale_dir: PathBuf,
+    /// 监视用户翻译目录的任务
+    watcher_task: Arc<RwLock<Option<tokio::task::JoinHandle<()>>>>,
 }
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 
 impl I18nModule {
     pub fn new() -> Self {
-        let mut translations = HashMap::new();
-        
-        // ==================== English translations ====================
-        // Menu
-        translations.insert(("menu_file".to_string(), Language::English), "File".to_string());
-        translations.insert(("menu_operation".to_string(), Language::English), "Operation".to_string());
-        translations.insert(("menu_language".to_string(), Language::English), "Language".to_string());
-        translations.insert(("menu_help".to_string(), Language::English), "Help".to_string());
-        // File menu
-        translations.insert(("menu_takeover_nginx".to_string(), Language::English), "Takeover Nginx".to_string());
-        translations.insert(("menu_startup_on_boot".to_string(), Language::English), "Startup on Boot".to_string());
-        translations.insert(("menu_new_proxy".to_string(), Language::English), "New Proxy".to_string());
-        translations.insert(("menu_new_php".to_string(), Language::English), "New PHP".to_string());
-        translations.insert(("menu_new_static".to_string(), Language::English), "New Static".to_string());
-        translations.insert(("menu_exit".to_string(), Language::English), "Exit".to_string());
-        // Operation menu
-        translations.insert(("menu_start_nginx".to_string(), Language::English), "Start Nginx".to_string());
-        translations.insert(("menu_stop_nginx".to_string(), Language::English), "Stop Nginx".to_string());
-        translations.insert(("menu_reload_config".to_string(), Language::English), "Reload Config".to_string());
-        translations.insert(("menu_refresh_sites".to_string(), Language::English), "Refresh Sites".to_string());
-        translations.insert(("menu_test_config".to_string(), Language::English), "Test Config".to_string());
-        translations.insert(("menu_backup_config".to_string(), Language::English), "Backup Config".to_string());
-        // Language menu
-        translations.insert(("menu_english".to_string(), Language::English), "English".to_string());
-        translations.insert(("menu_chinese".to_string(), Language::English), "Chinese".to_string());
-        // Help menu
-        translations.insert(("menu_about".to_string(), Language::English), "About".to_string());
-        
-        // Site list
-        translations.insert(("site_list_site".to_string(), Language::English), "Site".to_string());
-        translations.insert(("site_list_type".to_string(), Language::English), "Type".to_string());
-        translations.insert(("site_list_port".to_string(), Language::English), "Port".to_string());
-        translations.insert(("site_list_domain".to_string(), Language::English), "Domain".to_string());
-        translations.insert(("site_list_https".to_string(), Language::English), "HTTPS".to_string());
-        translations.insert(("site_list_https_yes".to_string(), Language::English), "Yes".to_string());
-        translations.insert(("site_list_https_no".to_string(), Language::English), "No".to_string());
-        translations.insert(("site_list_edit".to_string(), Language::English), "Edit".to_string());
-        translations.insert(("site_list_delete".to_string(), Language::English), "Delete".to_string());
-        
-        // Status bar
-        translations.insert(("status_nginx_stopped".to_string(), Language::English), "Nginx: Stopped".to_string());
-        translations.insert(("status_nginx_running".to_string(), Language::English), "Nginx: Running".to_string());
-        translations.insert(("status_sites".to_string(), Language::English), "Sites: Total {total}, Static {static}, PHP {php}, Proxy {proxy}".to_string());
-        
-        // About dialog
-        translations.insert(("about_title".to_string(), Language::English), "About".to_string());
-        translations.insert(("about_app_name".to_string(), Language::English), "easyNginx".to_string());
-        translations.insert(("about_version".to_string(), Language::English), "Version 1.0.0".to_string());
-        translations.insert(("about_description".to_string(), Language::English), "A simple and intuitive Nginx management tool".to_string());
-        translations.insert(("about_author_label".to_string(), Language::English), "Author:".to_string());
-        translations.insert(("about_author".to_string(), Language::English), "Laffinty".to_string());
-        translations.insert(("about_license_label".to_string(), Language::English), "License:".to_string());
-        translations.insert(("about_license".to_string(), Language::English), "MIT License".to_string());
-        translations.insert(("about_website_label".to_string(), Language::English), "Website:".to_string());
-        translations.insert(("about_website".to_string(), Language::English), "GitHub".to_string());
-        translations.insert(("about_copyright".to_string(), Language::English), "© 2026 Laffinty. All rights reserved.".to_string());
-        translations.insert(("about_ok".to_string(), Language::English), "OK".to_string());
-        
-        // Site type
-        translations.insert(("site_type_static".to_string(), Language::English), "Static".to_string());
-        translations.insert(("site_type_php".to_string(), Language::English), "PHP".to_string());
-        translations.insert(("site_type_proxy".to_string(), Language::English), "Proxy".to_string());
-        
-        // ==================== Chinese Simplified translations ====================
-        // Menu
-        translations.insert(("menu_file".to_string(), Language::ChineseSimplified), "文件".to_string());
-        translations.insert(("menu_operation".to_string(), Language::ChineseSimplified), "操作".to_string());
-        translations.insert(("menu_language".to_string(), Language::ChineseSimplified), "语言".to_string());
-        translations.insert(("menu_help".to_string(), Language::ChineseSimplified), "帮助".to_string());
-        // File menu
-        translations.insert(("menu_takeover_nginx".to_string(), Language::ChineseSimplified), "接管 Nginx".to_string());
-        translations.insert(("menu_startup_on_boot".to_string(), Language::ChineseSimplified), "开机启动".to_string());
-        translations.insert(("menu_new_proxy".to_string(), Language::ChineseSimplified), "新建代理".to_string());
-        translations.insert(("menu_new_php".to_string(), Language::ChineseSimplified), "新建 PHP".to_string());
-        translations.insert(("menu_new_static".to_string(), Language::ChineseSimplified), "新建静态".to_string());
-        translations.insert(("menu_exit".to_string(), Language::ChineseSimplified), "退出".to_string());
-        // Operation menu
-        translations.insert(("menu_start_nginx".to_string(), Language::ChineseSimplified), "启动 Nginx".to_string());
-        translations.insert(("menu_stop_nginx".to_string(), Language::ChineseSimplified), "停止 Nginx".to_string());
-        translations.insert(("menu_reload_config".to_string(), Language::ChineseSimplified), "重载配置".to_string());
-        translations.insert(("menu_refresh_sites".to_string(), Language::ChineseSimplified), "刷新站点".to_string());
-        translations.insert(("menu_test_config".to_string(), Language::ChineseSimplified), "测试配置".to_string());
-        translations.insert(("menu_backup_config".to_string(), Language::ChineseSimplified), "备份配置".to_string());
-        // Language menu
-        translations.insert(("menu_english".to_string(), Language::ChineseSimplified), "English".to_string());
-        translations.insert(("menu_chinese".to_string(), Language::ChineseSimplified), "中文".to_string());
-        // Help menu
-        translations.insert(("menu_about".to_string(), Language::ChineseSimplified), "关于".to_string());
-        
-        // Site list
-        translations.insert(("site_list_site".to_string(), Language::ChineseSimplified), "站点".to_string());
-        translations.insert(("site_list_type".to_string(), Language::ChineseSimplified), "类型".to_string());
-        translations.insert(("site_list_port".to_string(), Language::ChineseSimplified), "端口".to_string());
-        translations.insert(("site_list_domain".to_string(), Language::ChineseSimplified), "域名".to_string());
-        translations.insert(("site_list_https".to_string(), Language::ChineseSimplified), "HTTPS".to_string());
-        translations.insert(("site_list_https_yes".to_string(), Language::ChineseSimplified), "是".to_string());
-        translations.insert(("site_list_https_no".to_string(), Language::ChineseSimplified), "否".to_string());
-        translations.insert(("site_list_edit".to_string(), Language::ChineseSimplified), "编辑".to_string());
-        translations.insert(("site_list_delete".to_string(), Language::ChineseSimplified), "删除".to_string());
-        
-        // Status bar
-        translations.insert(("status_nginx_stopped".to_string(), Language::ChineseSimplified), "Nginx: 已停止".to_string());
-        translations.insert(("status_nginx_running".to_string(), Language::ChineseSimplified), "Nginx: 运行中".to_string());
-        translations.insert(("status_sites".to_string(), Language::ChineseSimplified), "站点: 总计 {total}, 静态 {static}, PHP {php}, 代理 {proxy}".to_string());
-        
-        // About dialog
-        translations.insert(("about_title".to_string(), Language::ChineseSimplified), "关于".to_string());
-        translations.insert(("about_app_name".to_string(), Language::ChineseSimplified), "easyNginx".to_string());
-        translations.insert(("about_version".to_string(), Language::ChineseSimplified), "版本 1.0.0".to_string());
-        translations.insert(("about_description".to_string(), Language::ChineseSimplified), "简单直观的 Nginx 管理工具".to_string());
-        translations.insert(("about_author_label".to_string(), Language::ChineseSimplified), "作者：".to_string());
-        translations.insert(("about_author".to_string(), Language::ChineseSimplified), "Laffinty".to_string());
-        translations.insert(("about_license_label".to_string(), Language::ChineseSimplified), "许可证：".to_string());
-        translations.insert(("about_license".to_string(), Language::ChineseSimplified), "MIT 许可证".to_string());
-        translations.insert(("about_website_label".to_string(), Language::ChineseSimplified), "网站：".to_string());
-        translations.insert(("about_website".to_string(), Language::ChineseSimplified), "GitHub".to_string());
-        translations.insert(("about_copyright".to_string(), Language::ChineseSimplified), "© 2026 Laffinty. 保留所有权利。".to_string());
-        translations.insert(("about_ok".to_string(), Language::ChineseSimplified), "确定".to_string());
-        
-        // Site type
-        translations.insert(("site_type_static".to_string(), Language::ChineseSimplified), "静态".to_string());
-        translations.insert(("site_type_php".to_string(), Language::ChineseSimplified), "PHP".to_string());
-        translations.insert(("site_type_proxy".to_string(), Language::ChineseSimplified), "代理".to_string());
+        Self::with_locale_dir(loader::user_locale_dir())
+    }
+    
+    /// 从指定的用户翻译目录加载覆盖
+    pub fn with_locale_dir(locale_dir: PathBuf) -> Self {
+        let loaded = loader::load_translations_from(&locale_dir);
+        for issue in &loaded.issues {
+            eprintln!("[I18n] Translation resource problem: {}", issue);
+        }
         
         Self {
             name: "l18n",
             bus: Arc::new(RwLock::new(None)),
-            current_language: Arc::new(RwLock::new(Language::ChineseSimplified)),
-            translations: Arc::new(RwLock::new(translations)),
+            current_language: Arc::new(RwLock::new(initial_language())),
+            translations: Arc::new(RwLock::new(loaded.translations)),
+            available: Arc::new(RwLock::new(loaded.available)),
+            unknown_keys: Arc::new(RwLock::new(loaded.unknown_keys)),
+            issues: Arc::new(RwLock::new(loaded.issues.iter().map(|issue| issue.to_string()).collect())),
+            runtime_misses: Arc::new(RwLock::new(HashMap::new())),
+            locale_dir,
+            watcher_task: Arc::new(RwLock::new(None)),
         }
     }
     
-    async fn translate(&self, key: &str, language: Option<Language>) -> String {
+    async fn translate(&self, key: &str, language: Option<Language>, args: &TranslationArgs) -> String {
         let lang = match language {
             Some(l) => l,
-            None => *self.current_language.read().await,
+            None => self.current_language.read().await.clone(),
         };
+        
+        match self.lookup(key, &lang).await {
+            // 没有参数时返回原始文本，由调用方稍后填充（与批量翻译一致）
+            Some(translation) if args.is_empty() => translation,
+            Some(translation) => format_message(&translation, &lang, args),
+            None => key.to_string(),
+        }
+    }
+    
+    /// 沿回退链查找原始文本（zh-TW -> zh-Hant -> zh -> en）
+    ///
+    /// 请求的语言本身没有该键时记录到 runtime_misses
+    async fn lookup(&self, key: &str, language: &Language) -> Option<String> {
+        let translations = self.translations.read().await;
+        let chain = language.fallback_chain();
+        let found = chain.iter()
+            .position(|lang| translations.contains_key(&(key.to_string(), lang.clone())));
+        
+        if found != Some(0) {
+            let newly_missed = self.runtime_misses.write().await
+                .entry(language.clone())
+                .or_default()
+                .insert(key.to_string());
+            if newly_missed && found.is_none() {
+                eprintln!("[I18n] No translation for '{}' in {} or its fallbacks", key, language);
+            }
+        }
+        
+        found.and_then(|index| translations.get(&(key.to_string(), chain[index].clone())).cloned())
+    }
+    
+    async fn report(&self) -> Vec<LocaleReport> {
         let translations = self.translations.read().await;
+        let languages: Vec<Language> = self.available.read().await.iter()
+            .map(|info| info.language.clone())
+            .collect();
+        let runtime_misses = self.runtime_misses.read().await;
+        report::build_reports(&translations, &languages, &self.unknown_keys.read().await, &runtime_misses)
+    }
+    
+    /// 重新加载全部翻译资源并通知其它模块
+    async fn reload(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let loaded = loader::load_translations_from(&self.locale_dir);
+        let issues: Vec<String> = loaded.issues.iter().map(|issue| issue.to_string()).collect();
+        for issue in &issues {
+            eprintln!("[I18n] Translation resource problem: {}", issue);
+        }
+        
+        *self.translations.write().await = loaded.translations;
+        *self.available.write().await = loaded.available.clone();
+        *self.unknown_keys.write().await = loaded.unknown_keys;
+        *self.issues.write().await = issues.clone();
+        self.runtime_misses.write().await.clear();
+        println!("[I18n] Translations reloaded from {}", self.locale_dir.display());
         
-        if let Some(translation) = translations.get(&(key.to_string(), lang)) {
-            translation.clone()
-        } else {
-            key.to_string()
+        if let Some(bus) = &*self.bus.read().await {
+            bus.publish(TranslationsReloaded { languages: loaded.available, issues }).await?;
+        }
+        Ok(())
+    }
+    
+    /// 轮询用户翻译目录，文件增删改后重新加载
+    async fn watch_user_locales(self) {
+        let mut stamp = loader::user_bundle_stamp(&self.locale_dir);
+        let mut ticker = tokio::time::interval(RELOAD_POLL_INTERVAL);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.reload_if_changed(&mut stamp).await {
+                eprintln!("[I18n] Failed to publish reloaded translations: {}", e);
+            }
         }
     }
     
+    /// 用户翻译目录与 stamp 不同时重新加载；返回是否重新加载
+    async fn reload_if_changed(&self, stamp: &mut loader::BundleStamp) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let current = loader::user_bundle_stamp(&self.locale_dir);
+        if current == *stamp {
+            return Ok(false);
+        }
+        *stamp = current;
+        self.reload().await?;
+        Ok(true)
+    }
+    
+    /// 未填充参数的原始文本（批量翻译使用）
+    async fn raw_translation(&self, key: &str, language: &Language) -> String {
+        self.lookup(key, language).await.unwrap_or_else(|| key.to_string())
+    }
+    
     async fn set_language(&self, language: Language) {
+        // 保存用户的选择，下次启动时恢复
+        let tag = language.tag().to_string();
+        if let Err(e) = Settings::update(|settings| settings.language = Some(tag)) {
+            eprintln!("[I18n] Failed to save language setting: {}", e);
+        }
         *self.current_language.write().await = language;
     }
     
     async fn get_current_language(&self) -> Language {
-        *self.current_language.read().await
+        self.current_language.read().await.clone()
     }
 }
 
@@ -374,11 +556,23 @@ impl Module for I18nModule {
         let translation_request_type = bus.register_message_type::<TranslationRequest>().await;
         let language_change_request_type = bus.register_message_type::<LanguageChangeRequest>().await;
         let batch_translation_request_type = bus.register_message_type::<BatchTranslationRequest>().await;
+        let available_languages_request_type = bus.register_message_type::<AvailableLanguagesRequest>().await;
+        bus.register_message_type::<AvailableLanguagesResponse>().await;
+        let translation_report_request_type = bus.register_message_type::<TranslationReportRequest>().await;
+        bus.register_message_type::<TranslationReportResponse>().await;
+        bus.register_message_type::<TranslationsReloaded>().await;
         
         // Subscribe to messages
         bus.subscribe(translation_request_type, self.name().to_string()).await;
         bus.subscribe(language_change_request_type, self.name().to_string()).await;
         bus.subscribe(batch_translation_request_type, self.name().to_string()).await;
+        bus.subscribe(available_languages_request_type, self.name().to_string()).await;
+        bus.subscribe(translation_report_request_type, self.name().to_string()).await;
+        
+        println!("[I18n] Current language: {}", self.get_current_language().await);
+        
+        let handle = tokio::spawn(self.clone().watch_user_locales());
+        *self.watcher_task.write().await = Some(handle);
         
         Ok(())
     }
@@ -386,8 +580,11 @@ impl Module for I18nModule {
     async fn process_message(&self, envelope: MessageEnvelope) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         if envelope.message_type == TypeId::of::<TranslationRequest>() {
             if let Some(msg) = envelope.payload.as_any().downcast_ref::<TranslationRequest>() {
-                let translation = self.translate(&msg.key, Some(msg.language)).await;
-                let response = TranslationResponse::new(&msg.key, &translation, msg.language);
+                let translation = self.translate(&msg.key, Some(msg.language.clone()), &msg.args).await;
+                let response = TranslationResponse {
+                    formatted: !msg.args.is_empty(),
+                    ..TranslationResponse::new(&msg.key, &translation, msg.language.clone(), &msg.requester, msg.generation)
+                };
                 
                 if let Some(bus) = &*self.bus.read().await {
                     bus.publish(response).await?;
@@ -395,17 +592,40 @@ impl Module for I18nModule {
             }
         } else if envelope.message_type == TypeId::of::<LanguageChangeRequest>() {
             if let Some(msg) = envelope.payload.as_any().downcast_ref::<LanguageChangeRequest>() {
-                self.set_language(msg.language).await;
-                println!("[I18n] Language changed to: {:?}", msg.language);
+                self.set_language(msg.language.clone()).await;
+                println!("[I18n] Language changed to: {:?}", self.get_current_language().await);
             }
         } else if envelope.message_type == TypeId::of::<BatchTranslationRequest>() {
             if let Some(msg) = envelope.payload.as_any().downcast_ref::<BatchTranslationRequest>() {
                 let mut translations = HashMap::new();
                 for key in &msg.keys {
-                    let translation = self.translate(key, Some(msg.language)).await;
+                    let translation = self.raw_translation(key, &msg.language).await;
                     translations.insert(key.clone(), translation);
                 }
-                let response = BatchTranslationResponse::new(translations, msg.language, &msg.requester);
+                let response = BatchTranslationResponse::new(translations, msg.language.clone(), &msg.requester, msg.generation);
+                
+                if let Some(bus) = &*self.bus.read().await {
+                    bus.publish(response).await?;
+                }
+            }
+        } else if envelope.message_type == TypeId::of::<AvailableLanguagesRequest>() {
+            if let Some(msg) = envelope.payload.as_any().downcast_ref::<AvailableLanguagesRequest>() {
+                let response = AvailableLanguagesResponse {
+                    languages: self.available.read().await.clone(),
+                    requester: msg.requester.clone(),
+                };
+                
+                if let Some(bus) = &*self.bus.read().await {
+                    bus.publish(response).await?;
+                }
+            }
+        } else if envelope.message_type == TypeId::of::<TranslationReportRequest>() {
+            if let Some(msg) = envelope.payload.as_any().downcast_ref::<TranslationReportRequest>() {
+                let response = TranslationReportResponse {
+                    reports: self.report().await,
+                    issues: self.issues.read().await.clone(),
+                    requester: msg.requester.clone(),
+                };
                 
                 if let Some(bus) = &*self.bus.read().await {
                     bus.publish(response).await?;
@@ -417,8 +637,94 @@ impl Module for I18nModule {
     }
     
     async fn shutdown(&mut self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if let Some(handle) = self.watcher_task.write().await.take() {
+            handle.abort();
+        }
+        for (language, keys) in self.runtime_misses.read().await.iter() {
+            println!("[I18n] {} key(s) fell back from {}: {}", keys.len(), language,
+                keys.iter().cloned().collect::<Vec<_>>().join(", "));
+        }
         Ok(())
     }
 }
 
 module_init!(I18nModule, "l18n");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chooses_startup_language() {
+        let available: Vec<Language> = ["en", "zh-CN", "zh-TW", "de"].iter().map(|tag| Language::parse(tag).unwrap()).collect();
+        let system = |tag: &str| Language::parse(tag);
+        let choose = |saved: Option<&str>, system: Option<Language>| choose_language(saved, system, &available).tag().to_string();
+
+        // 保存的选择优先于系统语言
+        assert_eq!(choose(Some("de"), system("zh-TW")), "de");
+        // 保存的语言不可用或无法解析时使用系统语言
+        assert_eq!(choose(Some("ko"), system("zh-HK")), "zh-TW");
+        assert_eq!(choose(Some("??"), system("de-AT")), "de");
+        // 系统语言没有匹配时使用默认语言
+        assert_eq!(choose(None, system("fr-FR")), DEFAULT_LANGUAGE_TAG);
+        assert_eq!(choose(None, None), DEFAULT_LANGUAGE_TAG);
+    }
+
+    #[tokio::test]
+    async fn reloads_edited_user_locale() {
+        let dir = std::env::temp_dir().join(format!("easynginx-locales-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("de.toml"), "about_title = \"Info\"\n").unwrap();
+        let module = I18nModule::with_locale_dir(dir.clone());
+        let de = Language::parse("de").unwrap();
+        let args = TranslationArgs::new();
+        assert_eq!(module.translate("about_title", Some(de.clone()), &args).await, "Info");
+
+        let mut stamp = loader::user_bundle_stamp(&dir);
+        assert!(!module.reload_if_changed(&mut stamp).await.unwrap());
+        // 改变长度，修改时间精度不够时也能检测到
+        std::fs::write(dir.join("de.toml"), "about_title = \"Info easyNginx\"\n").unwrap();
+        assert!(module.reload_if_changed(&mut stamp).await.unwrap());
+        assert_eq!(module.translate("about_title", Some(de.clone()), &args).await, "Info easyNginx");
+
+        // 删除覆盖后恢复内置的译文
+        std::fs::remove_file(dir.join("de.toml")).unwrap();
+        assert!(module.reload_if_changed(&mut stamp).await.unwrap());
+        assert_eq!(module.translate("about_title", Some(de), &args).await, "Über");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn selects_plural_through_translation_request() {
+        let module = I18nModule::with_locale_dir(std::env::temp_dir().join("easynginx-no-locales"));
+        let bus = MessageBus::new();
+        bus.register_message_type::<TranslationResponse>().await;
+        *module.bus.write().await = Some(bus.clone());
+        let mut responses = bus.get_receiver(&TypeId::of::<TranslationResponse>()).await.unwrap();
+        let mut respond = async |request: TranslationRequest| {
+            module.process_message(MessageEnvelope::new(request)).await.unwrap();
+            let envelope = responses.recv().await.unwrap();
+            envelope.payload.as_any().downcast_ref::<TranslationResponse>().unwrap().clone()
+        };
+
+        let (en, fr) = (Language::source(), Language::parse("fr").unwrap());
+        let workers = |language: &Language, count: usize| TranslationRequest::new(Key::StatusTooltipWorkers, language.clone(), "tray")
+            .with_args(TranslationArgs::new().count("count", count));
+        for (language, count, expected) in [(&en, 1, "1 worker process"), (&en, 2, "2 worker processes"), (&en, 0, "0 worker processes")] {
+            let response = respond(workers(language, count)).await;
+            assert_eq!(response.translation, expected);
+            // 已填充参数，UI 不会把它当作原始文本缓存
+            assert!(response.formatted);
+            assert_eq!(response.requester, "tray");
+        }
+        // 法语的 0 属于 one 类别
+        let one = respond(workers(&fr, 1)).await.translation;
+        assert_eq!(respond(workers(&fr, 0)).await.translation, one.replace('1', "0"));
+
+        // 没有参数时返回原始文本
+        let response = respond(TranslationRequest::new(Key::StatusTooltipWorkers, en, "ui")).await;
+        assert!(!response.formatted);
+        assert!(response.translation.starts_with("{count -> [one]"));
+    }
+}
@@ -0,0 +1,366 @@
+// MIT License
+//
+// Copyright (c) 2026 Laffinty
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+
+// ==============================================================================
+// Gettext PO export / import
+// ==============================================================================
+// 翻译外包使用 gettext PO 文件。每个键导出为一个条目：
+//   #. <源语言资源中的注释>
+//   msgctxt "<key>"
+//   msgid "<英文原文>"
+//   msgstr "<目标语言当前译文，未翻译时为空>"
+//
+// 原文中的占位符 {name} 和复数选择表达式需原样保留在译文中，导入时会校验
+// 占位符与原文一致。导入结果写入用户翻译目录的 <tag>.toml，与该文件中已有的
+// 键合并；运行中的程序会自动重新加载。
+//
+//   easnginx i18n-export <locale> [output.po]
+//   easnginx i18n-import <input.po> [--locale <tag>]
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use super::loader::{self, LANGUAGE_NAME_KEY};
+use super::{format, Key, Language};
+use crate::cli_command;
+
+/// PO 文件中的一个条目
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PoEntry {
+    pub comment: String,
+    pub context: String,
+    pub source: String,
+    pub translation: String,
+}
+
+/// 导出目标语言的全部键
+pub fn export(language: &Language) -> String {
+    let loaded = loader::load_translations();
+    let comments = loader::source_comments();
+    let source = Language::source();
+
+    let mut out = String::new();
+    out.push_str("# easyNginx translations\n");
+    out.push_str("# Keep placeholders such as {total} and select expressions unchanged.\n");
+    out.push_str("msgid \"\"\nmsgstr \"\"\n");
+    out.push_str(&format!("\"Language: {}\\n\"\n", language));
+    out.push_str("\"MIME-Version: 1.0\\n\"\n");
+    out.push_str("\"Content-Type: text/plain; charset=UTF-8\\n\"\n");
+    out.push_str("\"Content-Transfer-Encoding: 8bit\\n\"\n");
+
+    for key in Key::ALL {
+        let lookup = |language: &Language| loaded.translations
+            .get(&(key.as_str().to_string(), language.clone()))
+            .cloned()
+            .unwrap_or_default();
+        let entry = PoEntry {
+            comment: comments.get(key.as_str()).cloned().unwrap_or_default(),
+            context: key.as_str().to_string(),
+            source: lookup(&source),
+            translation: lookup(language),
+        };
+        out.push('\n');
+        write_entry(&entry, &mut out);
+    }
+    out
+}
+
+fn write_entry(entry: &PoEntry, out: &mut String) {
+    if !entry.comment.is_empty() {
+        out.push_str(&format!("#. {}\n", entry.comment));
+    }
+    out.push_str(&format!("msgctxt \"{}\"\n", escape(&entry.context)));
+    out.push_str(&format!("msgid \"{}\"\n", escape(&entry.source)));
+    out.push_str(&format!("msgstr \"{}\"\n", escape(&entry.translation)));
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape(text: &str) -> Result<String, String> {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('\\') => unescaped.push('\\'),
+            Some('"') => unescaped.push('"'),
+            Some('n') => unescaped.push('\n'),
+            Some('t') => unescaped.push('\t'),
+            Some(other) => return Err(format!("unsupported escape '\\{}'", other)),
+            None => return Err("trailing backslash".to_string()),
+        }
+    }
+    Ok(unescaped)
+}
+
+/// 解析 PO 文件，返回 (头部中的语言, 条目)
+///
+/// 支持多行字符串（续行以 " 开头），忽略 #~ 等注释和复数条目之外的扩展
+pub fn parse(text: &str) -> Result<(Option<String>, Vec<PoEntry>), String> {
+    #[derive(Clone, Copy, PartialEq)]
+    enum Field { Context, Source, Translation }
+
+    let mut entries = Vec::new();
+    let mut entry = PoEntry::default();
+    let mut field: Option<Field> = None;
+    let mut has_content = false;
+
+    let mut finish = |entry: &mut PoEntry, has_content: &mut bool| {
+        if *has_content {
+            entries.push(std::mem::take(entry));
+        }
+        *entry = PoEntry::default();
+        *has_content = false;
+    };
+
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        let line_error = |message: String| format!("line {}: {}", index + 1, message);
+
+        if line.is_empty() {
+            finish(&mut entry, &mut has_content);
+            field = None;
+            continue;
+        }
+        if let Some(comment) = line.strip_prefix("#.") {
+            entry.comment = comment.trim().to_string();
+            continue;
+        }
+        if line.starts_with('#') {
+            continue;
+        }
+
+        let (keyword, rest) = match line.split_once(char::is_whitespace) {
+            Some((keyword, rest)) if !line.starts_with('"') => (Some(keyword), rest.trim()),
+            _ => (None, line),
+        };
+        let value = rest.strip_prefix('"')
+            .and_then(|rest| rest.strip_suffix('"'))
+            .ok_or_else(|| line_error(format!("expected a quoted string, found '{}'", rest)))?;
+        let value = unescape(value).map_err(line_error)?;
+
+        let next = match keyword {
+            Some("msgctxt") => {
+                // msgctxt 开始一个新条目（条目之间可能没有空行）
+                if field.is_some() {
+                    let comment = std::mem::take(&mut entry.comment);
+                    finish(&mut entry, &mut has_content);
+                    entry.comment = comment;
+                }
+                Field::Context
+            }
+            Some("msgid") => {
+                if field == Some(Field::Translation) {
+                    finish(&mut entry, &mut has_content);
+                }
+                Field::Source
+            }
+            Some("msgstr") => Field::Translation,
+            Some(other) => return Err(line_error(format!("unsupported keyword '{}'", other))),
+            None => field.ok_or_else(|| line_error("string continuation outside an entry".to_string()))?,
+        };
+        let target = match next {
+            Field::Context => &mut entry.context,
+            Field::Source => &mut entry.source,
+            Field::Translation => &mut entry.translation,
+        };
+        target.push_str(&value);
+        field = Some(next);
+        has_content = true;
+    }
+    finish(&mut entry, &mut has_content);
+
+    // 头部条目（msgid ""）提供 Language
+    let mut language = None;
+    entries.retain(|entry| {
+        if entry.context.is_empty() && entry.source.is_empty() {
+            language = entry.translation.lines()
+                .find_map(|line| line.strip_prefix("Language:"))
+                .map(|tag| tag.trim().to_string());
+            false
+        } else {
+            true
+        }
+    });
+    Ok((language, entries))
+}
+
+/// 校验条目并返回 key -> 译文；任何条目有问题时返回全部问题
+///
+/// 空译文被跳过（未翻译）
+pub fn validate(entries: &[PoEntry]) -> Result<BTreeMap<String, String>, Vec<String>> {
+    let loaded = loader::load_translations();
+    let source = Language::source();
+    let mut translations = BTreeMap::new();
+    let mut problems = Vec::new();
+
+    for entry in entries {
+        if entry.translation.is_empty() {
+            continue;
+        }
+        let Some(key) = Key::from_name(&entry.context) else {
+            problems.push(format!("unknown key '{}'", entry.context));
+            continue;
+        };
+        let source_text = loaded.translations
+            .get(&(key.as_str().to_string(), source.clone()))
+            .cloned()
+            .unwrap_or_default();
+
+        match (format::placeholders(&entry.translation), format::placeholders(&source_text)) {
+            (None, _) => problems.push(format!("{}: translation is not a valid message pattern", key.as_str())),
+            (Some(found), Some(expected)) if found != expected => problems.push(format!(
+                "{}: placeholders {{{}}} do not match the source {{{}}}",
+                key.as_str(), found.join("}, {"), expected.join("}, {"),
+            )),
+            _ => {
+                translations.insert(key.as_str().to_string(), entry.translation.clone());
+            }
+        }
+    }
+
+    if problems.is_empty() {
+        Ok(translations)
+    } else {
+        Err(problems)
+    }
+}
+
+/// 将译文合并进用户翻译目录中的 <tag>.toml，返回写入的文件
+pub fn import(language: &Language, translations: &BTreeMap<String, String>) -> Result<std::path::PathBuf, String> {
+    let dir = loader::user_locale_dir();
+    let path = dir.join(format!("{}.toml", language));
+
+    let mut merged: BTreeMap<String, String> = match std::fs::read_to_string(&path) {
+        Ok(text) => text.parse::<toml::Table>()
+            .map_err(|e| format!("{}: {}", path.display(), e.message()))?
+            .into_iter()
+            .filter_map(|(key, value)| value.as_str().map(|value| (key, value.to_string())))
+            .collect(),
+        Err(_) => BTreeMap::new(),
+    };
+    merged.extend(translations.iter().map(|(key, value)| (key.clone(), value.clone())));
+
+    // 按源语言中的顺序写出，language_name 在最前
+    let mut text = format!("# easyNginx translations - {}\n", language);
+    text.push_str("# Imported from a PO file; edit with `easnginx i18n-export` / `i18n-import`.\n\n");
+    let keys = std::iter::once(LANGUAGE_NAME_KEY)
+        .chain(Key::ALL.iter().map(|key| key.as_str()).filter(|key| *key != LANGUAGE_NAME_KEY));
+    for key in keys {
+        if let Some(value) = merged.get(key) {
+            text.push_str(&format!("{} = {}\n", key, toml::Value::String(value.clone())));
+        }
+    }
+
+    std::fs::create_dir_all(&dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    let tmp = path.with_extension("toml.tmp");
+    std::fs::write(&tmp, text).map_err(|e| format!("{}: {}", tmp.display(), e))?;
+    std::fs::rename(&tmp, &path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(path)
+}
+
+// ==============================================================================
+// CLI commands
+// ==============================================================================
+
+fn cli_i18n_export(args: &[String]) -> Result<(), String> {
+    let tag = args.first().ok_or("usage: i18n-export <locale> [output.po]")?;
+    let language = Language::parse(tag).ok_or_else(|| format!("'{}' is not a valid language tag", tag))?;
+    let po = export(&language);
+
+    match args.get(1) {
+        Some(output) => {
+            std::fs::write(output, po).map_err(|e| format!("{}: {}", output, e))?;
+            println!("Exported {} keys for {} to {}", Key::ALL.len(), language, output);
+        }
+        None => print!("{}", po),
+    }
+    Ok(())
+}
+
+fn cli_i18n_import(args: &[String]) -> Result<(), String> {
+    let input = args.first().ok_or("usage: i18n-import <input.po> [--locale <tag>]")?;
+    let text = std::fs::read_to_string(Path::new(input)).map_err(|e| format!("{}: {}", input, e))?;
+    let (header_language, entries) = parse(&text).map_err(|e| format!("{}: {}", input, e))?;
+
+    let tag = args.iter()
+        .position(|arg| arg == "--locale")
+        .and_then(|index| args.get(index + 1).cloned())
+        .or(header_language)
+        .ok_or("the PO file has no Language header; pass --locale <tag>")?;
+    let language = Language::parse(&tag).ok_or_else(|| format!("'{}' is not a valid language tag", tag))?;
+    if language == Language::source() {
+        return Err(format!("{} is the source locale; edit locales/{}.toml instead", language, language));
+    }
+
+    let translations = validate(&entries).map_err(|problems| {
+        format!("{} problem(s), nothing imported:\n  {}", problems.len(), problems.join("\n  "))
+    })?;
+    let path = import(&language, &translations)?;
+    println!("Imported {} translations for {} into {}", translations.len(), language, path.display());
+    Ok(())
+}
+
+cli_command!("i18n-export", "Export translations for a locale as a gettext PO file", cli_i18n_export);
+cli_command!("i18n-import", "Import a translated PO file into the user locale directory", cli_i18n_import);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn export_round_trips_through_parse() {
+        let language = Language::parse("zh-CN").expect("valid tag");
+        let (header_language, entries) = parse(&export(&language)).expect("exported PO parses");
+        assert_eq!(header_language.as_deref(), Some("zh-CN"));
+        assert_eq!(entries.len(), Key::ALL.len());
+
+        let translations = validate(&entries).expect("embedded translations are valid");
+        assert_eq!(translations.len(), Key::ALL.len());
+    }
+
+    #[test]
+    fn import_rejects_mismatched_placeholders() {
+        let entries = vec![PoEntry {
+            context: "status_sites".to_string(),
+            translation: "Sites: {count}".to_string(),
+            ..PoEntry::default()
+        }];
+        let problems = validate(&entries).expect_err("placeholders differ");
+        assert!(problems[0].contains("status_sites"), "{:?}", problems);
+    }
+}
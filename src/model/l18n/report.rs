@@ -0,0 +1,179 @@
+// MIT License
+//
+// Copyright (c) 2026 Laffinty
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+
+// ==============================================================================
+// Translation report - 缺失 / 未使用的键
+// ==============================================================================
+// 每个语言统计三类问题：
+// - missing:  源语言中有、但该语言没有定义的键（会回退到其它语言显示）
+// - unused:   该语言资源中定义了、但程序不使用的键（源语言中没有）
+// - runtime_misses: 运行期间实际请求过、但在该语言中找不到的键
+//
+// 报告可通过总线 (TranslationReportRequest) 或命令行 `easnginx i18n-report` 获取。
+
+use std::collections::{BTreeSet, HashMap};
+use super::loader::LoadedTranslations;
+use super::{Key, Language};
+use crate::cli_command;
+
+/// 单个语言的翻译完整性报告
+#[derive(Clone, Debug, PartialEq)]
+pub struct LocaleReport {
+    pub language: Language,
+    /// 该语言定义了的键数量
+    pub translated: usize,
+    pub missing: Vec<String>,
+    pub unused: Vec<String>,
+    pub runtime_misses: Vec<String>,
+}
+
+impl LocaleReport {
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty() && self.unused.is_empty() && self.runtime_misses.is_empty()
+    }
+}
+
+impl std::fmt::Display for LocaleReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}/{} keys translated", self.language, self.translated, Key::ALL.len())?;
+        for (label, keys) in [
+            ("missing", &self.missing),
+            ("unused", &self.unused),
+            ("missed at runtime", &self.runtime_misses),
+        ] {
+            if !keys.is_empty() {
+                write!(f, "\n  {} ({}): {}", label, keys.len(), keys.join(", "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 为每个可用语言生成报告
+///
+/// runtime_misses 为运行期间记录的 (语言 -> 请求失败的键)，命令行下为空
+pub fn build_reports(
+    translations: &HashMap<(String, Language), String>,
+    languages: &[Language],
+    unknown_keys: &[(Language, String)],
+    runtime_misses: &HashMap<Language, BTreeSet<String>>,
+) -> Vec<LocaleReport> {
+    languages.iter()
+        .map(|language| {
+            let (translated, missing): (Vec<&str>, Vec<&str>) = Key::ALL.iter()
+                .map(|key| key.as_str())
+                .partition(|key| translations.contains_key(&(key.to_string(), language.clone())));
+
+            let mut unused: Vec<String> = unknown_keys.iter()
+                .filter(|(lang, _)| lang == language)
+                .map(|(_, key)| key.clone())
+                .collect();
+            unused.sort();
+            unused.dedup();
+
+            LocaleReport {
+                language: language.clone(),
+                translated: translated.len(),
+                missing: missing.into_iter().map(str::to_string).collect(),
+                unused,
+                runtime_misses: runtime_misses.get(language)
+                    .map(|keys| keys.iter().cloned().collect())
+                    .unwrap_or_default(),
+            }
+        })
+        .collect()
+}
+
+// ==============================================================================
+// CLI command
+// ==============================================================================
+
+/// easnginx i18n-report [--strict]
+///
+/// --strict: 有任何缺失键、未使用键或资源错误时返回失败（用于 CI）
+fn cli_i18n_report(args: &[String]) -> Result<(), String> {
+    let strict = args.iter().any(|arg| arg == "--strict");
+    let loaded: LoadedTranslations = super::loader::load_translations();
+    let languages: Vec<Language> = loaded.available.iter().map(|info| info.language.clone()).collect();
+    let reports = build_reports(&loaded.translations, &languages, &loaded.unknown_keys, &HashMap::new());
+
+    for report in &reports {
+        println!("{}", report);
+    }
+    if !loaded.issues.is_empty() {
+        println!("\nResource problems:");
+        for issue in &loaded.issues {
+            println!("  {}", issue);
+        }
+    }
+
+    let incomplete = reports.iter().filter(|report| !report.is_complete()).count();
+    if strict && (incomplete > 0 || !loaded.issues.is_empty()) {
+        return Err(format!("{} incomplete locale(s), {} resource problem(s)", incomplete, loaded.issues.len()));
+    }
+    Ok(())
+}
+
+cli_command!("i18n-report", "Report missing and unused translation keys per locale", cli_i18n_report);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_missing_unused_and_runtime_misses() {
+        let en = Language::parse("en").unwrap();
+        let de = Language::parse("de").unwrap();
+        let mut translations = HashMap::new();
+        for key in Key::ALL {
+            translations.insert((key.as_str().to_string(), en.clone()), "text".to_string());
+        }
+        // de 只翻译了前两个键
+        for key in &Key::ALL[..2] {
+            translations.insert((key.as_str().to_string(), de.clone()), "Text".to_string());
+        }
+        let unknown_keys = vec![
+            (de.clone(), "old_key".to_string()),
+            (de.clone(), "another_old_key".to_string()),
+            (de.clone(), "old_key".to_string()),
+        ];
+        let runtime_misses = HashMap::from([(de.clone(), BTreeSet::from([Key::ALL[2].as_str().to_string()]))]);
+
+        let reports = build_reports(&translations, &[en.clone(), de.clone()], &unknown_keys, &runtime_misses);
+        assert_eq!(reports.len(), 2);
+
+        assert_eq!(reports[0].language, en);
+        assert_eq!(reports[0].translated, Key::ALL.len());
+        assert!(reports[0].is_complete());
+
+        let report = &reports[1];
+        assert_eq!(report.language, de);
+        assert_eq!(report.translated, 2);
+        let missing: Vec<String> = Key::ALL[2..].iter().map(|key| key.as_str().to_string()).collect();
+        assert_eq!(report.missing, missing);
+        assert_eq!(report.unused, vec!["another_old_key", "old_key"]);
+        assert_eq!(report.runtime_misses, vec![Key::ALL[2].as_str()]);
+        assert!(!report.is_complete());
+        assert!(report.to_string().starts_with(&format!("de: 2/{} keys translated", Key::ALL.len())));
+    }
+}
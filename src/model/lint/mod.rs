@@ -0,0 +1,305 @@
+// MIT License
+// 
+// Copyright (c) 2026 Laffinty
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+
+
+// ==============================================================================
+// Lint Module - 配置检查
+// ==============================================================================
+// 检查 nginx 配置和 easyNginx 管理的站点中常见的冲突和错误（见 rules），
+// 例如重复的 server_name、被其它程序占用的端口、缺少的证书和网站根目录。
+// 管理的站点没有被 nginx.conf 加载时，按生成的配置检查。
+//   LintRequest -> LintFinished
+// 命令行：easnginx lint [--strict]
+
+use async_trait::async_trait;
+use std::any::{Any, TypeId};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use crate::{cli_command, MessageEnvelope, MessageBus, Module, module_init};
+use crate::model::l18n::Key;
+use crate::model::nginx::{self, Config, ConfigFile};
+use crate::model::settings::{NginxSettings, Settings};
+use crate::model::site::{self, config, discover};
+
+pub mod ports;
+pub mod rules;
+
+/// 问题的严重程度
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// nginx 无法使用这份配置
+    Error,
+    /// 配置可用，但结果很可能不是想要的
+    Warning,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        })
+    }
+}
+
+/// 检查规则
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Rule {
+    DuplicateServerName,
+    DuplicateDefaultServer,
+    PortInUse,
+    MissingCertificate,
+    MissingRoot,
+    ProxyPassUri,
+    IfInLocation,
+    AddHeaderInheritance,
+}
+
+impl Rule {
+    /// 命令行输出中的名称
+    pub fn id(&self) -> &'static str {
+        match self {
+            Rule::DuplicateServerName => "duplicate-server-name",
+            Rule::DuplicateDefaultServer => "duplicate-default-server",
+            Rule::PortInUse => "port-in-use",
+            Rule::MissingCertificate => "missing-certificate",
+            Rule::MissingRoot => "missing-root",
+            Rule::ProxyPassUri => "proxy-pass-uri",
+            Rule::IfInLocation => "if-in-location",
+            Rule::AddHeaderInheritance => "add-header-inheritance",
+        }
+    }
+
+    pub fn translation_key(&self) -> Key {
+        match self {
+            Rule::DuplicateServerName => Key::LintRuleDuplicateServerName,
+            Rule::DuplicateDefaultServer => Key::LintRuleDuplicateDefaultServer,
+            Rule::PortInUse => Key::LintRulePortInUse,
+            Rule::MissingCertificate => Key::LintRuleMissingCertificate,
+            Rule::MissingRoot => Key::LintRuleMissingRoot,
+            Rule::ProxyPassUri => Key::LintRuleProxyPassUri,
+            Rule::IfInLocation => Key::LintRuleIfInLocation,
+            Rule::AddHeaderInheritance => Key::LintRuleAddHeaderInheritance,
+        }
+    }
+}
+
+/// 问题所在的文件和行
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Location {
+    pub path: PathBuf,
+    pub line: usize,
+}
+
+impl std::fmt::Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.path.display(), self.line)
+    }
+}
+
+/// 一个问题；message 为英文说明
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Lint {
+    pub rule: Rule,
+    pub severity: Severity,
+    pub location: Location,
+    pub message: String,
+}
+
+/// 检查结果
+#[derive(Clone, Debug, Default)]
+pub struct LintReport {
+    /// 错误在前，其次按文件和行排列
+    pub lints: Vec<Lint>,
+    /// 读取或解析配置失败的原因
+    pub errors: Vec<String>,
+}
+
+impl LintReport {
+    pub fn count(&self, severity: Severity) -> usize {
+        self.lints.iter().filter(|lint| lint.severity == severity).count()
+    }
+}
+
+/// 检查 nginx 配置和管理的站点
+pub fn run(settings: &NginxSettings) -> LintReport {
+    let mut report = LintReport::default();
+    let files = match discover::main_conf_path(settings) {
+        Some(main) => nginx::load_tree(&main).unwrap_or_else(|e| {
+            report.errors.push(e.to_string());
+            Vec::new()
+        }),
+        None => Vec::new(),
+    };
+    let sites = site::load_sites().unwrap_or_else(|e| {
+        report.errors.push(e);
+        Vec::new()
+    });
+    let extra: Vec<ConfigFile> = sites.iter()
+        .filter(|site| site.enabled && !files.iter().any(|file| file.path == site::conf_path_of(site)))
+        .filter_map(|site| {
+            let config = Config::parse(&config::render(site)).ok()?;
+            Some(ConfigFile { path: site::conf_path_of(site), config })
+        })
+        .collect();
+
+    report.lints = rules::check(&files, &extra, &ports::bound_ports());
+    report.lints.sort_by(|a, b| {
+        (a.severity, &a.location.path, a.location.line).cmp(&(b.severity, &b.location.path, b.location.line))
+    });
+    report
+}
+
+// ==============================================================================
+// Messages
+// ==============================================================================
+
+/// 请求检查配置
+#[derive(Clone, Debug, Default)]
+pub struct LintRequest;
+
+impl crate::Message for LintRequest {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn message_type(&self) -> TypeId {
+        TypeId::of::<LintRequest>()
+    }
+    
+    fn clone_box(&self) -> Box<dyn crate::Message> {
+        Box::new(self.clone())
+    }
+}
+
+/// 检查完成
+#[derive(Clone, Debug)]
+pub struct LintFinished {
+    pub report: LintReport,
+}
+
+impl crate::Message for LintFinished {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn message_type(&self) -> TypeId {
+        TypeId::of::<LintFinished>()
+    }
+    
+    fn clone_box(&self) -> Box<dyn crate::Message> {
+        Box::new(self.clone())
+    }
+}
+
+// ==============================================================================
+// Module
+// ==============================================================================
+
+pub struct LintModule {
+    name: &'static str,
+    bus: Arc<RwLock<Option<Arc<MessageBus>>>>,
+}
+
+impl LintModule {
+    pub fn new() -> Self {
+        Self {
+            name: "lint",
+            bus: Arc::new(RwLock::new(None)),
+        }
+    }
+}
+
+impl Default for LintModule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Module for LintModule {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+    
+    async fn initialize(&mut self, bus: Arc<MessageBus>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        *self.bus.write().await = Some(bus.clone());
+        
+        let request_type = bus.register_message_type::<LintRequest>().await;
+        bus.register_message_type::<LintFinished>().await;
+        bus.subscribe(request_type, self.name().to_string()).await;
+        Ok(())
+    }
+    
+    async fn process_message(&self, envelope: MessageEnvelope) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if envelope.message_type == TypeId::of::<LintRequest>() {
+            let settings = Settings::load().nginx;
+            let report = tokio::task::spawn_blocking(move || run(&settings)).await?;
+            println!(
+                "[Lint] {} error(s), {} warning(s)",
+                report.count(Severity::Error),
+                report.count(Severity::Warning)
+            );
+            if let Some(bus) = &*self.bus.read().await {
+                bus.publish(LintFinished { report }).await?;
+            }
+        }
+        
+        Ok(())
+    }
+    
+    async fn shutdown(&mut self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        Ok(())
+    }
+}
+
+module_init!(LintModule, "lint");
+
+// ==============================================================================
+// CLI command
+// ==============================================================================
+
+/// easnginx lint [--strict]
+///
+/// 有错误时返回失败；--strict: 有警告时也返回失败
+fn cli_lint(args: &[String]) -> Result<(), String> {
+    let strict = args.iter().any(|arg| arg == "--strict");
+    let report = run(&Settings::load().nginx);
+    for error in &report.errors {
+        println!("error: {}", error);
+    }
+    for lint in &report.lints {
+        println!("{}: {}: [{}] {}", lint.location, lint.severity, lint.rule.id(), lint.message);
+    }
+
+    let errors = report.count(Severity::Error) + report.errors.len();
+    let warnings = report.count(Severity::Warning);
+    println!("{} error(s), {} warning(s)", errors, warnings);
+    if errors > 0 || (strict && warnings > 0) {
+        return Err(format!("{} error(s), {} warning(s)", errors, warnings));
+    }
+    Ok(())
+}
+
+cli_command!("lint", "Check the nginx configuration and managed sites for common mistakes", cli_lint);
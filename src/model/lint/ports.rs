@@ -0,0 +1,122 @@
+// MIT License
+// 
+// Copyright (c) 2026 Laffinty
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+
+
+// ==============================================================================
+// ports - 已被监听的端口
+// ==============================================================================
+// 读取 /proc/net/tcp 和 /proc/net/tcp6 中处于 LISTEN 状态的套接字，再通过
+// /proc/<pid>/fd 找到所属的进程（只能看到有权限访问的进程）。其它平台返回空列表。
+
+use std::collections::HashMap;
+
+/// 一个正在监听的端口
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BoundPort {
+    pub port: u16,
+    /// 进程名；没有权限查看时为 None
+    pub process: Option<String>,
+}
+
+/// 本机正在监听的 TCP 端口
+pub fn bound_ports() -> Vec<BoundPort> {
+    if !cfg!(target_os = "linux") {
+        return Vec::new();
+    }
+    let mut sockets = Vec::new();
+    for table in ["/proc/net/tcp", "/proc/net/tcp6"] {
+        if let Ok(text) = std::fs::read_to_string(table) {
+            sockets.extend(parse_listening(&text));
+        }
+    }
+    let owners = socket_owners();
+    let mut ports: Vec<BoundPort> = Vec::new();
+    for (port, inode) in sockets {
+        let process = owners.get(&inode).cloned();
+        match ports.iter_mut().find(|bound| bound.port == port) {
+            Some(bound) => {
+                if bound.process.is_none() {
+                    bound.process = process;
+                }
+            }
+            None => ports.push(BoundPort { port, process }),
+        }
+    }
+    ports
+}
+
+/// /proc/net/tcp 中 LISTEN（0A）状态的 (端口, inode)
+fn parse_listening(text: &str) -> Vec<(u16, u64)> {
+    text.lines()
+        .skip(1)
+        .filter_map(|line| {
+            // sl local_address rem_address st tx_queue:rx_queue tr:tm->when retrnsmt uid timeout inode
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if *fields.get(3)? != "0A" {
+                return None;
+            }
+            let port = u16::from_str_radix(fields.get(1)?.rsplit_once(':')?.1, 16).ok()?;
+            let inode = fields.get(9)?.parse().ok()?;
+            Some((port, inode))
+        })
+        .collect()
+}
+
+/// 套接字 inode -> 进程名
+fn socket_owners() -> HashMap<u64, String> {
+    let mut owners = HashMap::new();
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return owners;
+    };
+    for pid in entries.filter_map(|entry| entry.ok()?.file_name().to_str()?.parse::<u32>().ok()) {
+        let Ok(fds) = std::fs::read_dir(format!("/proc/{}/fd", pid)) else {
+            continue;
+        };
+        let Some(name) = std::fs::read_to_string(format!("/proc/{}/comm", pid)).ok().map(|comm| comm.trim().to_string()) else {
+            continue;
+        };
+        for fd in fds.filter_map(|fd| fd.ok()) {
+            let inode = std::fs::read_link(fd.path()).ok().and_then(|link| {
+                link.to_str()?.strip_prefix("socket:[")?.strip_suffix(']')?.parse::<u64>().ok()
+            });
+            if let Some(inode) = inode {
+                owners.insert(inode, name.clone());
+            }
+        }
+    }
+    owners
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_listening_sockets() {
+        let text = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode\n\
+   0: 00000000:0050 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 20931 1 0000000000000000 100 0 0 10 0\n\
+   1: 0100007F:1F90 0100007F:C350 01 00000000:00000000 00:00000000 00000000  1000        0 40213 1 0000000000000000 20 4 30 10 -1\n\
+   2: 00000000000000000000000000000000:01BB 00000000000000000000000000000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 20932 1 0000000000000000 100 0 0 10 0\n";
+        assert_eq!(parse_listening(text), [(80, 20931), (443, 20932)]);
+    }
+}
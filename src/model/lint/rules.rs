@@ -0,0 +1,434 @@
+// MIT License
+// 
+// Copyright (c) 2026 Laffinty
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+
+
+// ==============================================================================
+// rules - 检查规则
+// ==============================================================================
+// 先按 include 展开配置树（被 include 的指令挂在 include 所在的块下），
+// 再在展开后的树上逐条检查。stream、mail 中的 server 不是网站，不参与检查。
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use crate::model::nginx::{resolve_include, ConfigFile, Directive, Node};
+use crate::model::site::discover::parse_listen;
+use crate::model::site::Listen;
+use super::ports::BoundPort;
+use super::{Lint, Location, Rule, Severity};
+
+/// include 嵌套的最大层数（防止循环 include）
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+/// if 块中可以安全使用的指令
+const SAFE_IN_IF: [&str; 4] = ["return", "rewrite", "set", "break"];
+
+/// 展开 include 后的一条指令
+struct Entry<'a> {
+    directive: &'a Directive,
+    path: &'a Path,
+    parent: Option<usize>,
+}
+
+struct Tree<'a> {
+    entries: Vec<Entry<'a>>,
+    files: &'a [ConfigFile],
+    /// 相对 include 的基准目录（主配置文件所在目录）
+    prefix: PathBuf,
+}
+
+impl<'a> Tree<'a> {
+    /// files[0] 为主配置文件；extra 中的文件放在 http 块中
+    fn build(files: &'a [ConfigFile], extra: &'a [ConfigFile]) -> Self {
+        let prefix = files.first().and_then(|file| file.path.parent()).map(Path::to_path_buf).unwrap_or_default();
+        let mut tree = Self { entries: Vec::new(), files, prefix };
+        if let Some(main) = files.first() {
+            tree.add(&main.config.nodes, &main.path, None, 0);
+        }
+        let http = tree.entries.iter().position(|entry| entry.parent.is_none() && entry.directive.name() == "http");
+        for file in extra {
+            tree.add(&file.config.nodes, &file.path, http, 0);
+        }
+        tree
+    }
+
+    fn add(&mut self, nodes: &'a [Node], path: &'a Path, parent: Option<usize>, depth: usize) {
+        for node in nodes {
+            let Node::Directive(directive) = node else {
+                continue;
+            };
+            if directive.name() == "include" {
+                let Some(pattern) = directive.args().into_iter().next() else {
+                    continue;
+                };
+                if depth >= MAX_INCLUDE_DEPTH {
+                    continue;
+                }
+                for included in resolve_include(&pattern, &self.prefix) {
+                    if let Some(file) = self.files.iter().find(|file| file.path == included) {
+                        self.add(&file.config.nodes, &file.path, parent, depth + 1);
+                    }
+                }
+                continue;
+            }
+            let index = self.entries.len();
+            self.entries.push(Entry { directive, path, parent });
+            if let Some(block) = directive.block() {
+                self.add(&block.nodes, path, Some(index), depth);
+            }
+        }
+    }
+
+    fn name(&self, index: usize) -> String {
+        self.entries[index].directive.name()
+    }
+
+    fn location(&self, index: usize) -> Location {
+        let entry = &self.entries[index];
+        Location { path: entry.path.to_path_buf(), line: entry.directive.line }
+    }
+
+    fn children(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        (index + 1..self.entries.len()).filter(move |child| self.entries[*child].parent == Some(index))
+    }
+
+    /// 指定名称的子指令
+    fn children_named<'b>(&'b self, index: usize, name: &'b str) -> impl Iterator<Item = usize> + 'b {
+        self.children(index).filter(move |child| self.name(*child) == name)
+    }
+
+    fn ancestors(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        std::iter::successors(self.entries[index].parent, |parent| self.entries[*parent].parent)
+    }
+
+    /// http 上下文中的 server 块
+    fn servers(&self) -> Vec<usize> {
+        (0..self.entries.len())
+            .filter(|index| self.name(*index) == "server" && self.entries[*index].directive.block().is_some())
+            .filter(|index| self.ancestors(*index).all(|ancestor| !matches!(self.name(ancestor).as_str(), "stream" | "mail" | "upstream")))
+            .collect()
+    }
+
+    /// server 的 listen；没有 listen 时 nginx 监听 *:80
+    fn listens(&self, server: usize) -> Vec<(usize, Listen)> {
+        let listens: Vec<(usize, Listen)> = self.children_named(server, "listen")
+            .filter_map(|index| parse_listen(&self.entries[index].directive.args()).map(|listen| (index, listen)))
+            .collect();
+        if listens.is_empty() && self.children_named(server, "listen").next().is_none() {
+            let listen = Listen { address: None, port: 80, ssl: false, http2: false, default_server: false };
+            return vec![(server, listen)];
+        }
+        listens
+    }
+}
+
+/// 地址:端口，所有地址记为 *
+fn listen_key(listen: &Listen) -> String {
+    let address = match listen.address.as_deref() {
+        None | Some("0.0.0.0") => "*",
+        Some(address) => address,
+    };
+    format!("{}:{}", address, listen.port)
+}
+
+/// 不含变量的文件路径；相对路径基于 prefix
+fn literal_path(arg: &str, prefix: &Path) -> Option<PathBuf> {
+    if arg.contains('$') || arg.starts_with("data:") || arg.starts_with("engine:") {
+        return None;
+    }
+    let path = Path::new(arg);
+    Some(if path.is_absolute() { path.to_path_buf() } else { prefix.join(path) })
+}
+
+/// 检查展开后的配置；files[0] 为主配置文件，extra 为未被主配置加载的站点配置
+pub fn check(files: &[ConfigFile], extra: &[ConfigFile], bound: &[BoundPort]) -> Vec<Lint> {
+    let tree = Tree::build(files, extra);
+    let mut lints = Vec::new();
+    let servers = tree.servers();
+    check_server_names(&tree, &servers, &mut lints);
+    check_default_servers(&tree, &servers, &mut lints);
+    check_ports(&tree, &servers, bound, &mut lints);
+    check_certificates(&tree, &servers, &mut lints);
+    check_roots(&tree, &mut lints);
+    check_proxy_pass(&tree, &mut lints);
+    check_if(&tree, &mut lints);
+    check_add_header(&tree, &mut lints);
+    lints
+}
+
+fn lint(lints: &mut Vec<Lint>, rule: Rule, severity: Severity, location: Location, message: String) {
+    lints.push(Lint { rule, severity, location, message });
+}
+
+/// 同一地址端口上重复的 server_name：nginx 只使用第一个
+fn check_server_names(tree: &Tree, servers: &[usize], lints: &mut Vec<Lint>) {
+    let mut seen: HashMap<(String, String), usize> = HashMap::new();
+    for &server in servers {
+        let mut keys: Vec<String> = tree.listens(server).iter().map(|(_, listen)| listen_key(listen)).collect();
+        keys.dedup();
+        for index in tree.children_named(server, "server_name") {
+            for name in tree.entries[index].directive.args() {
+                let name = name.to_ascii_lowercase();
+                if name.is_empty() {
+                    continue;
+                }
+                for key in &keys {
+                    match seen.get(&(key.clone(), name.clone())) {
+                        Some(&first) if first != server => {
+                            let message = format!(
+                                "server_name \"{}\" on {} is already used by the server at {}; nginx ignores this one",
+                                name, key, tree.location(first),
+                            );
+                            lint(lints, Rule::DuplicateServerName, Severity::Warning, tree.location(index), message);
+                        }
+                        Some(_) => {}
+                        None => {
+                            seen.insert((key.clone(), name.clone()), server);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// 同一地址端口上有多个 default_server：nginx -t 失败
+fn check_default_servers(tree: &Tree, servers: &[usize], lints: &mut Vec<Lint>) {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    for &server in servers {
+        for (index, listen) in tree.listens(server).into_iter().filter(|(_, listen)| listen.default_server) {
+            let key = listen_key(&listen);
+            match seen.get(&key) {
+                Some(&first) => {
+                    let message = format!("{} already has a default server at {}", key, tree.location(first));
+                    lint(lints, Rule::DuplicateDefaultServer, Severity::Error, tree.location(index), message);
+                }
+                None => {
+                    seen.insert(key, index);
+                }
+            }
+        }
+    }
+}
+
+/// 被其它程序占用的端口：nginx 无法启动或重载
+fn check_ports(tree: &Tree, servers: &[usize], bound: &[BoundPort], lints: &mut Vec<Lint>) {
+    let mut reported = Vec::new();
+    for &server in servers {
+        for (index, listen) in tree.listens(server) {
+            if reported.contains(&listen.port) {
+                continue;
+            }
+            let owner = bound.iter()
+                .find(|bound| bound.port == listen.port)
+                .and_then(|bound| bound.process.as_deref())
+                .filter(|process| *process != "nginx");
+            if let Some(process) = owner {
+                reported.push(listen.port);
+                let message = format!("port {} is already in use by {}", listen.port, process);
+                lint(lints, Rule::PortInUse, Severity::Error, tree.location(index), message);
+            }
+        }
+    }
+}
+
+/// 使用 ssl 的 server 需要证书，证书文件必须存在
+fn check_certificates(tree: &Tree, servers: &[usize], lints: &mut Vec<Lint>) {
+    for &server in servers {
+        let ssl = tree.listens(server).iter().any(|(_, listen)| listen.ssl)
+            || tree.children_named(server, "ssl").any(|index| tree.entries[index].directive.args().first().is_some_and(|arg| arg == "on"));
+        if !ssl {
+            continue;
+        }
+        let has = |name: &str| std::iter::once(server).chain(tree.ancestors(server)).any(|scope| tree.children_named(scope, name).next().is_some());
+        if !has("ssl_certificate") || !has("ssl_certificate_key") {
+            let message = "this server accepts HTTPS but has no ssl_certificate and ssl_certificate_key".to_string();
+            lint(lints, Rule::MissingCertificate, Severity::Error, tree.location(server), message);
+        }
+    }
+    for index in 0..tree.entries.len() {
+        if !matches!(tree.name(index).as_str(), "ssl_certificate" | "ssl_certificate_key") {
+            continue;
+        }
+        let Some(path) = tree.entries[index].directive.args().first().and_then(|arg| literal_path(arg, &tree.prefix)) else {
+            continue;
+        };
+        if !path.is_file() {
+            let message = format!("{} does not exist", path.display());
+            lint(lints, Rule::MissingCertificate, Severity::Error, tree.location(index), message);
+        }
+    }
+}
+
+/// 不存在的网站根目录；相对路径基于 nginx 的前缀目录，无法确定，不检查
+fn check_roots(tree: &Tree, lints: &mut Vec<Lint>) {
+    for index in 0..tree.entries.len() {
+        if tree.name(index) != "root" {
+            continue;
+        }
+        let Some(root) = tree.entries[index].directive.args().into_iter().next() else {
+            continue;
+        };
+        if root.contains('$') || !Path::new(&root).is_absolute() || Path::new(&root).is_dir() {
+            continue;
+        }
+        let message = format!("document root {} does not exist", root);
+        lint(lints, Rule::MissingRoot, Severity::Warning, tree.location(index), message);
+    }
+}
+
+/// proxy_pass 的 URI 部分：http://host:port/uri -> /uri
+fn proxy_uri(target: &str) -> Option<&str> {
+    let rest = target.split_once("://")?.1;
+    if rest.starts_with("unix:") {
+        return rest.rsplit_once(':').map(|(_, uri)| uri).filter(|uri| uri.starts_with('/'));
+    }
+    rest.find('/').map(|i| &rest[i..])
+}
+
+/// proxy_pass 的 URI 会替换 location 匹配的部分，两者结尾的 '/' 不一致时路径会拼错
+fn check_proxy_pass(tree: &Tree, lints: &mut Vec<Lint>) {
+    for index in 0..tree.entries.len() {
+        if tree.name(index) != "proxy_pass" {
+            continue;
+        }
+        let Some(target) = tree.entries[index].directive.args().into_iter().next() else {
+            continue;
+        };
+        let Some(uri) = proxy_uri(&target).filter(|_| !target.contains('$')) else {
+            continue;
+        };
+        let Some(parent) = tree.entries[index].parent else {
+            continue;
+        };
+        let parent_name = tree.name(parent);
+        if parent_name == "if" || parent_name == "limit_except" {
+            let message = format!("proxy_pass cannot have a URI part ({}) inside \"{}\"", uri, parent_name);
+            lint(lints, Rule::ProxyPassUri, Severity::Error, tree.location(index), message);
+            continue;
+        }
+        if parent_name != "location" {
+            continue;
+        }
+        let args = tree.entries[parent].directive.args();
+        let (modifier, prefix) = match args.as_slice() {
+            [modifier, prefix, ..] if matches!(modifier.as_str(), "=" | "~" | "~*" | "^~") => (modifier.as_str(), prefix.as_str()),
+            [prefix, ..] => ("", prefix.as_str()),
+            [] => continue,
+        };
+        if modifier == "~" || modifier == "~*" || prefix.starts_with('@') {
+            let message = format!("proxy_pass cannot have a URI part ({}) in a regular expression or named location", uri);
+            lint(lints, Rule::ProxyPassUri, Severity::Error, tree.location(index), message);
+            continue;
+        }
+        if modifier == "=" || prefix.ends_with('/') == uri.ends_with('/') {
+            continue;
+        }
+        let request = if prefix.ends_with('/') { format!("{}page", prefix) } else { format!("{}/page", prefix) };
+        let passed = format!("{}{}", uri, &request[prefix.len()..]);
+        let message = format!(
+            "{} is passed upstream as {}; the location and the proxy_pass URI should both end with \"/\" or neither",
+            request, passed,
+        );
+        lint(lints, Rule::ProxyPassUri, Severity::Warning, tree.location(index), message);
+    }
+}
+
+/// location 中的 if 只适合 return 和 rewrite，其它指令的行为常常出人意料
+fn check_if(tree: &Tree, lints: &mut Vec<Lint>) {
+    for index in 0..tree.entries.len() {
+        if tree.name(index) != "if" || tree.entries[index].parent.is_none_or(|parent| tree.name(parent) != "location") {
+            continue;
+        }
+        if let Some(unsafe_child) = tree.children(index).find(|child| !SAFE_IN_IF.contains(&tree.name(*child).as_str())) {
+            let message = format!(
+                "\"{}\" inside if in a location may not work as expected; only return and rewrite are safe there",
+                tree.name(unsafe_child),
+            );
+            lint(lints, Rule::IfInLocation, Severity::Warning, tree.location(index), message);
+        }
+    }
+}
+
+/// 块中只要有一个 add_header，就不再继承上一级的 add_header
+fn check_add_header(tree: &Tree, lints: &mut Vec<Lint>) {
+    let headers = |scope: usize| -> Vec<(usize, String)> {
+        tree.children_named(scope, "add_header")
+            .filter_map(|index| tree.entries[index].directive.args().into_iter().next().map(|name| (index, name)))
+            .collect()
+    };
+    for scope in 0..tree.entries.len() {
+        let own = headers(scope);
+        let Some((first, _)) = own.first() else {
+            continue;
+        };
+        let Some(inherited) = tree.ancestors(scope).map(headers).find(|headers| !headers.is_empty()) else {
+            continue;
+        };
+        let dropped: Vec<&str> = inherited.iter()
+            .filter(|(_, name)| !own.iter().any(|(_, own)| own.eq_ignore_ascii_case(name)))
+            .map(|(_, name)| name.as_str())
+            .collect();
+        if dropped.is_empty() {
+            continue;
+        }
+        let message = format!(
+            "add_header here stops {} from being inherited from {}; repeat them in this block if they are still needed",
+            dropped.join(", "), tree.location(inherited[0].0),
+        );
+        lint(lints, Rule::AddHeaderInheritance, Severity::Warning, tree.location(*first), message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_common_mistakes() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/corpus/nginx/lint.conf");
+        let file = crate::model::nginx::load_file(&path).unwrap();
+        let bound = [
+            BoundPort { port: 80, process: Some("nginx".into()) },
+            BoundPort { port: 8080, process: Some("node".into()) },
+        ];
+        let mut found: Vec<(Rule, Severity, usize)> = check(&[file], &[], &bound)
+            .into_iter()
+            .map(|lint| (lint.rule, lint.severity, lint.location.line))
+            .collect();
+        found.sort_by_key(|(_, _, line)| *line);
+        assert_eq!(found, [
+            (Rule::MissingRoot, Severity::Warning, 11),
+            (Rule::ProxyPassUri, Severity::Warning, 14),
+            (Rule::ProxyPassUri, Severity::Warning, 18),
+            (Rule::ProxyPassUri, Severity::Error, 22),
+            (Rule::AddHeaderInheritance, Severity::Warning, 30),
+            (Rule::IfInLocation, Severity::Warning, 31),
+            (Rule::MissingCertificate, Severity::Error, 40),
+            (Rule::DuplicateDefaultServer, Severity::Error, 41),
+            (Rule::DuplicateServerName, Severity::Warning, 43),
+            (Rule::PortInUse, Severity::Error, 50),
+            (Rule::MissingCertificate, Severity::Error, 52),
+            (Rule::MissingCertificate, Severity::Error, 53),
+        ]);
+    }
+}
@@ -1,2 +1,28 @@
 pub mod ui;
-pub mod l18n;
\ No newline at end of file
+pub mod l18n;
+pub mod service;
+pub mod settings;
+pub mod site;
+pub mod nginx;
+pub mod controller;
+pub mod monitor;
+pub mod takeover;
+pub mod backup;
+pub mod transaction;
+pub mod lint;
+
+use std::path::PathBuf;
+
+/// 用户配置目录（翻译覆盖、设置等）
+///
+/// Windows: %APPDATA%\easyNginx，其它平台: $XDG_CONFIG_HOME/easyNginx 或 ~/.config/easyNginx
+pub fn config_dir() -> PathBuf {
+    #[cfg(windows)]
+    let base = std::env::var_os("APPDATA").map(PathBuf::from);
+    #[cfg(not(windows))]
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")));
+    
+    base.unwrap_or_else(|| PathBuf::from(".")).join("easyNginx")
+}
@@ -0,0 +1,507 @@
+// MIT License
+// 
+// Copyright (c) 2026 Laffinty
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+
+// ==============================================================================
+// Monitor Module - 监视 nginx 运行状态
+// ==============================================================================
+// 每隔 POLL_INTERVAL 读取 master 进程、worker 进程和启动时间；
+// 从 controller 和 site 模块的消息中得知重载、配置测试的结果。
+// 状态变化时广播 NginxStatusChanged；NginxStatusRequest 可随时获取当前状态。
+//
+// 状态：正在重载 > 出错（配置测试未通过或上次操作失败）> 运行中 > 已停止
+// 其它程序（如 systemctl reload）重载 nginx 时，master 不变而出现一组新的
+// worker，也记为一次重载。出错后轮询到 nginx 已用新的进程运行时清除错误。
+
+use async_trait::async_trait;
+use std::any::{Any, TypeId};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::sync::RwLock;
+use crate::{MessageEnvelope, MessageBus, Module, module_init};
+use crate::model::controller::{locate_binary, NginxCommand, NginxCommandRequest, NginxCommandResponse, NginxController, SystemNginx};
+use crate::model::settings::NginxSettings;
+use crate::model::site::{ReloadOutcome, SiteChangeResponse};
+
+pub mod process;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NginxState {
+    #[default]
+    Stopped,
+    Running,
+    Reloading,
+    Error,
+}
+
+/// nginx 的当前状态
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NginxSnapshot {
+    pub state: NginxState,
+    pub master_pid: Option<u32>,
+    /// master 的子进程
+    pub workers: Vec<u32>,
+    /// master 的启动时间
+    pub started_at: Option<SystemTime>,
+    /// 如 "nginx/1.24.0"
+    pub version: Option<String>,
+    pub last_reload: Option<SystemTime>,
+    /// 最近一次 nginx -t 的结果，None 表示尚未测试
+    pub config_ok: Option<bool>,
+    /// 最近一次操作失败的原因
+    pub error: Option<String>,
+}
+
+impl NginxSnapshot {
+    pub fn uptime(&self, now: SystemTime) -> Option<Duration> {
+        now.duration_since(self.started_at?).ok()
+    }
+}
+
+/// 一次轮询读到的进程信息
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Probe {
+    pub master_pid: Option<u32>,
+    pub workers: Vec<u32>,
+    pub started_at: Option<SystemTime>,
+}
+
+/// 请求广播当前状态
+#[derive(Clone, Debug, Default)]
+pub struct NginxStatusRequest;
+
+impl crate::Message for NginxStatusRequest {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn message_type(&self) -> TypeId {
+        TypeId::of::<NginxStatusRequest>()
+    }
+    
+    fn clone_box(&self) -> Box<dyn crate::Message> {
+        Box::new(self.clone())
+    }
+}
+
+/// nginx 状态（变化时及收到 NginxStatusRequest 时广播）
+#[derive(Clone, Debug)]
+pub struct NginxStatusChanged {
+    pub snapshot: NginxSnapshot,
+}
+
+impl crate::Message for NginxStatusChanged {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn message_type(&self) -> TypeId {
+        TypeId::of::<NginxStatusChanged>()
+    }
+    
+    fn clone_box(&self) -> Box<dyn crate::Message> {
+        Box::new(self.clone())
+    }
+}
+
+// ==============================================================================
+// Tracker
+// ==============================================================================
+
+/// 由轮询结果和操作结果得出状态
+#[derive(Clone, Debug, Default)]
+pub struct Tracker {
+    snapshot: NginxSnapshot,
+    /// 已收到重载请求、尚未收到结果
+    reloading: bool,
+    /// 记录 error 时的 master 和 worker；之后轮询到新的进程说明 nginx 已重新启动或重载
+    error_processes: Option<(Option<u32>, Vec<u32>)>,
+}
+
+impl Tracker {
+    pub fn snapshot(&self) -> &NginxSnapshot {
+        &self.snapshot
+    }
+    
+    fn set_error(&mut self, error: Option<String>) {
+        self.error_processes = error.as_ref().map(|_| (self.snapshot.master_pid, self.snapshot.workers.clone()));
+        self.snapshot.error = error;
+    }
+    
+    fn update_state(&mut self) {
+        let snapshot = &mut self.snapshot;
+        snapshot.state = if self.reloading {
+            NginxState::Reloading
+        } else if snapshot.config_ok == Some(false) || snapshot.error.is_some() {
+            NginxState::Error
+        } else if snapshot.master_pid.is_some() {
+            NginxState::Running
+        } else {
+            NginxState::Stopped
+        };
+    }
+    
+    pub fn observe(&mut self, probe: Probe, now: SystemTime) {
+        let snapshot = &mut self.snapshot;
+        // master 不变而出现至少与原来一样多的新 worker：被其它程序重载
+        let new_workers = probe.workers.iter().filter(|pid| !snapshot.workers.contains(pid)).count();
+        if probe.master_pid.is_some()
+            && probe.master_pid == snapshot.master_pid
+            && !snapshot.workers.is_empty()
+            && new_workers >= snapshot.workers.len()
+        {
+            snapshot.last_reload = Some(now);
+        }
+        if probe.master_pid.is_none() {
+            snapshot.version = None;
+        }
+        // 失败之后 nginx 已正常运行（master 在且 worker 换了一组）：清除上次的错误
+        if let Some((master, workers)) = &self.error_processes {
+            if probe.master_pid.is_some()
+                && !probe.workers.is_empty()
+                && (probe.master_pid != *master || probe.workers.iter().all(|pid| !workers.contains(pid)))
+            {
+                snapshot.error = None;
+                self.error_processes = None;
+            }
+        }
+        snapshot.master_pid = probe.master_pid;
+        snapshot.workers = probe.workers;
+        snapshot.started_at = probe.started_at;
+        self.update_state();
+    }
+    
+    pub fn set_version(&mut self, version: Option<String>) {
+        self.snapshot.version = version;
+    }
+    
+    pub fn command_started(&mut self, command: NginxCommand) {
+        if command == NginxCommand::Reload {
+            self.reloading = true;
+            self.update_state();
+        }
+    }
+    
+    pub fn command_finished(&mut self, response: &NginxCommandResponse, now: SystemTime) {
+        if let Some(report) = &response.report {
+            self.snapshot.config_ok = Some(report.success);
+        }
+        self.set_error(response.error.clone());
+        if response.command == NginxCommand::Reload {
+            self.reloading = false;
+            if response.is_ok() {
+                self.snapshot.last_reload = Some(now);
+            }
+        }
+        self.update_state();
+    }
+    
+    /// 保存站点的事务结果
+    pub fn site_reloaded(&mut self, outcome: &ReloadOutcome, now: SystemTime) {
+        match outcome {
+            ReloadOutcome::Reloaded => {
+                self.snapshot.config_ok = Some(true);
+                self.set_error(None);
+                self.snapshot.last_reload = Some(now);
+            }
+            // 没有写入修改或没有重载，nginx 使用的配置不变
+            ReloadOutcome::TestFailed(_) | ReloadOutcome::NotRunning => {}
+            ReloadOutcome::RolledBack(error) => {
+                self.set_error(Some(error.clone()));
+                self.snapshot.last_reload = Some(now);
+            }
+            ReloadOutcome::ReloadFailed(error) => self.set_error(Some(error.clone())),
+        }
+        self.update_state();
+    }
+    
+    pub fn tested(&mut self, ok: bool) {
+        self.snapshot.config_ok = Some(ok);
+        self.update_state();
+    }
+}
+
+// ==============================================================================
+// Module
+// ==============================================================================
+
+/// 创建时使用的设置和 SystemNginx
+type CachedNginx = Option<(NginxSettings, Arc<SystemNginx>)>;
+
+/// 模块和轮询任务共用的状态
+#[derive(Clone)]
+struct Shared {
+    bus: Arc<RwLock<Option<Arc<MessageBus>>>>,
+    tracker: Arc<RwLock<Tracker>>,
+    /// 按设置创建的 SystemNginx，设置不变时复用（保留找到的 pidfile）
+    nginx: Arc<std::sync::Mutex<CachedNginx>>,
+}
+
+impl Shared {
+    fn nginx(&self) -> Arc<SystemNginx> {
+        let settings = crate::model::settings::Settings::load().nginx;
+        let mut cached = self.nginx.lock().unwrap_or_else(|e| e.into_inner());
+        match &*cached {
+            Some((cached_settings, nginx)) if *cached_settings == settings => nginx.clone(),
+            _ => {
+                let nginx = Arc::new(SystemNginx::from_settings(&settings));
+                *cached = Some((settings, nginx.clone()));
+                nginx
+            }
+        }
+    }
+    
+    async fn publish(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let snapshot = self.tracker.read().await.snapshot().clone();
+        if let Some(bus) = &*self.bus.read().await {
+            bus.publish(NginxStatusChanged { snapshot }).await?;
+        }
+        Ok(())
+    }
+    
+    /// 读取进程信息；状态变化时广播
+    async fn poll(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let nginx = self.nginx();
+        let need_version = self.tracker.read().await.snapshot().version.is_none();
+        let (probe, version) = tokio::task::spawn_blocking(move || {
+            let master_pid = nginx.status().pid;
+            let probe = Probe {
+                master_pid,
+                workers: master_pid.map(process::child_pids).unwrap_or_default(),
+                started_at: master_pid.and_then(process::start_time),
+            };
+            let version = if need_version && master_pid.is_some() {
+                nginx.build_info().ok().map(|info| info.version).filter(|version| !version.is_empty())
+            } else {
+                None
+            };
+            (probe, version)
+        }).await?;
+        
+        let changed = {
+            let mut tracker = self.tracker.write().await;
+            let before = tracker.snapshot().clone();
+            tracker.observe(probe, SystemTime::now());
+            if version.is_some() {
+                tracker.set_version(version);
+            }
+            *tracker.snapshot() != before
+        };
+        if changed {
+            self.publish().await?;
+        }
+        Ok(())
+    }
+    
+    /// 启动时测试一次配置（找不到 nginx 时跳过）
+    async fn initial_test(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let nginx = self.nginx();
+        if locate_binary(&nginx.binary().to_string_lossy()).is_none() {
+            return Ok(());
+        }
+        let report = tokio::task::spawn_blocking(move || nginx.test()).await?;
+        self.tracker.write().await.tested(report.success);
+        self.publish().await
+    }
+}
+
+pub struct MonitorModule {
+    name: &'static str,
+    shared: Shared,
+    poll_task: Arc<RwLock<Option<tokio::task::JoinHandle<()>>>>,
+}
+
+impl MonitorModule {
+    pub fn new() -> Self {
+        Self {
+            name: "monitor",
+            shared: Shared {
+                bus: Arc::new(RwLock::new(None)),
+                tracker: Arc::new(RwLock::new(Tracker::default())),
+                nginx: Arc::new(std::sync::Mutex::new(None)),
+            },
+            poll_task: Arc::new(RwLock::new(None)),
+        }
+    }
+}
+
+impl Default for MonitorModule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Module for MonitorModule {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+    
+    async fn initialize(&mut self, bus: Arc<MessageBus>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        *self.shared.bus.write().await = Some(bus.clone());
+        
+        let status_request_type = bus.register_message_type::<NginxStatusRequest>().await;
+        let command_type = bus.register_message_type::<NginxCommandRequest>().await;
+        let command_response_type = bus.register_message_type::<NginxCommandResponse>().await;
+        let site_response_type = bus.register_message_type::<SiteChangeResponse>().await;
+        bus.register_message_type::<NginxStatusChanged>().await;
+        
+        bus.subscribe(status_request_type, self.name().to_string()).await;
+        bus.subscribe(command_type, self.name().to_string()).await;
+        bus.subscribe(command_response_type, self.name().to_string()).await;
+        bus.subscribe(site_response_type, self.name().to_string()).await;
+        
+        let shared = self.shared.clone();
+        let handle = tokio::spawn(async move {
+            if let Err(e) = shared.initial_test().await {
+                eprintln!("[Monitor] Initial configuration test failed: {}", e);
+            }
+            let mut ticker = tokio::time::interval(POLL_INTERVAL);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = shared.poll().await {
+                    eprintln!("[Monitor] Poll failed: {}", e);
+                }
+            }
+        });
+        *self.poll_task.write().await = Some(handle);
+        Ok(())
+    }
+    
+    async fn process_message(&self, envelope: MessageEnvelope) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let now = SystemTime::now();
+        if envelope.message_type == TypeId::of::<NginxStatusRequest>() {
+            self.shared.publish().await?;
+        } else if envelope.message_type == TypeId::of::<NginxCommandRequest>() {
+            if let Some(request) = envelope.payload.as_any().downcast_ref::<NginxCommandRequest>() {
+                self.shared.tracker.write().await.command_started(request.command);
+                self.shared.publish().await?;
+            }
+        } else if envelope.message_type == TypeId::of::<NginxCommandResponse>() {
+            if let Some(response) = envelope.payload.as_any().downcast_ref::<NginxCommandResponse>() {
+                self.shared.tracker.write().await.command_finished(response, now);
+                // poll 只在进程变化时广播，这里先广播操作结果
+                self.shared.publish().await?;
+                self.shared.poll().await?;
+            }
+        } else if envelope.message_type == TypeId::of::<SiteChangeResponse>() {
+            if let Some(outcome) = envelope.payload.as_any().downcast_ref::<SiteChangeResponse>().and_then(|response| response.reload.as_ref()) {
+                self.shared.tracker.write().await.site_reloaded(outcome, now);
+                self.shared.publish().await?;
+                self.shared.poll().await?;
+            }
+        }
+        
+        Ok(())
+    }
+    
+    async fn shutdown(&mut self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if let Some(handle) = self.poll_task.write().await.take() {
+            handle.abort();
+        }
+        Ok(())
+    }
+}
+
+module_init!(MonitorModule, "monitor");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn probe(master: u32, workers: &[u32]) -> Probe {
+        Probe { master_pid: Some(master), workers: workers.to_vec(), started_at: None }
+    }
+
+    #[test]
+    fn tracks_state_and_reloads() {
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let later = start + Duration::from_secs(60);
+        let mut tracker = Tracker::default();
+        assert_eq!(tracker.snapshot().state, NginxState::Stopped);
+
+        tracker.observe(probe(100, &[101, 102]), start);
+        assert_eq!(tracker.snapshot().state, NginxState::Running);
+        assert_eq!(tracker.snapshot().last_reload, None);
+
+        // 一个 worker 异常退出后被重新创建，不算重载
+        tracker.observe(probe(100, &[102, 103]), start);
+        assert_eq!(tracker.snapshot().last_reload, None);
+        // 外部重载：新旧 worker 短暂共存
+        tracker.observe(probe(100, &[102, 103, 104, 105]), later);
+        assert_eq!(tracker.snapshot().last_reload, Some(later));
+
+        tracker.command_started(NginxCommand::Reload);
+        assert_eq!(tracker.snapshot().state, NginxState::Reloading);
+        let mut response = NginxCommandResponse {
+            id: 1,
+            requester: "ui".into(),
+            command: NginxCommand::Reload,
+            error: Some("nginx -t failed".into()),
+            report: Some(crate::model::controller::parse_test_output(false, "")),
+        };
+        tracker.command_finished(&response, later);
+        assert_eq!(tracker.snapshot().state, NginxState::Error);
+        assert_eq!(tracker.snapshot().config_ok, Some(false));
+
+        response.error = None;
+        response.report = Some(crate::model::controller::parse_test_output(true, ""));
+        tracker.command_finished(&response, later);
+        assert_eq!(tracker.snapshot().state, NginxState::Running);
+
+        tracker.observe(Probe::default(), later);
+        assert_eq!(tracker.snapshot().state, NginxState::Stopped);
+    }
+
+    #[test]
+    fn clears_error_once_nginx_runs_again() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let mut tracker = Tracker::default();
+        let response = NginxCommandResponse {
+            id: 1,
+            requester: "ui".into(),
+            command: NginxCommand::Start,
+            error: Some("bind() to 0.0.0.0:80 failed".into()),
+            report: None,
+        };
+        tracker.command_finished(&response, now);
+        assert_eq!(tracker.snapshot().state, NginxState::Error);
+        tracker.observe(Probe::default(), now);
+        assert_eq!(tracker.snapshot().state, NginxState::Error);
+        // 之后由其它程序启动成功
+        tracker.observe(probe(100, &[101]), now);
+        assert_eq!(tracker.snapshot().state, NginxState::Running);
+        assert_eq!(tracker.snapshot().error, None);
+
+        // 运行中重载失败：同一组 worker 仍在，错误保留
+        tracker.site_reloaded(&ReloadOutcome::ReloadFailed("no new workers".into()), now);
+        tracker.observe(probe(100, &[101]), now);
+        assert_eq!(tracker.snapshot().state, NginxState::Error);
+        // 换了一组 worker（重载成功）后清除
+        tracker.observe(probe(100, &[101, 102]), now);
+        assert_eq!(tracker.snapshot().state, NginxState::Error);
+        tracker.observe(probe(100, &[102]), now);
+        assert_eq!(tracker.snapshot().state, NginxState::Running);
+    }
+}
@@ -0,0 +1,231 @@
+// MIT License
+// 
+// Copyright (c) 2026 Laffinty
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+
+// ==============================================================================
+// nginx includes - 展开 include 指令
+// ==============================================================================
+// include 的参数可以是相对路径（相对于主配置文件所在目录，与 nginx 的
+// conf prefix 一致）和通配符（*、?、[...]，可出现在任意一级目录中）。
+// 通配符的匹配结果按文件名排序，不匹配以 '.' 开头的文件，与 glob(3) 相同。
+
+use std::path::{Component, Path, PathBuf};
+use super::parser::{Config, ParseError};
+
+/// 读取或解析某个文件失败
+#[derive(Debug)]
+pub struct LoadError {
+    pub path: PathBuf,
+    pub message: String,
+}
+
+impl std::fmt::Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path.display(), self.message)
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+/// 一个已解析的配置文件
+#[derive(Clone, Debug)]
+pub struct ConfigFile {
+    pub path: PathBuf,
+    pub config: Config,
+}
+
+/// 读取并解析单个文件
+pub fn load_file(path: &Path) -> Result<ConfigFile, LoadError> {
+    let text = std::fs::read_to_string(path).map_err(|e| LoadError {
+        path: path.to_path_buf(),
+        message: e.to_string(),
+    })?;
+    let config = Config::parse(&text).map_err(|e: ParseError| LoadError {
+        path: path.to_path_buf(),
+        message: e.to_string(),
+    })?;
+    Ok(ConfigFile { path: path.to_path_buf(), config })
+}
+
+/// 读取主配置文件及其 include 的全部文件（按 nginx 读取的顺序，每个文件只读一次）
+pub fn load_tree(main: &Path) -> Result<Vec<ConfigFile>, LoadError> {
+    let prefix = main.parent().map(Path::to_path_buf).unwrap_or_default();
+    let mut files = Vec::new();
+    load_recursive(main, &prefix, &mut files)?;
+    Ok(files)
+}
+
+fn load_recursive(path: &Path, prefix: &Path, files: &mut Vec<ConfigFile>) -> Result<(), LoadError> {
+    if files.iter().any(|file| file.path == path) {
+        return Ok(());
+    }
+    let file = load_file(path)?;
+    let mut includes = Vec::new();
+    file.config.walk(&mut |directive| {
+        if directive.name() == "include" {
+            if let Some(pattern) = directive.args().first() {
+                includes.push(pattern.clone());
+            }
+        }
+    });
+    files.push(file);
+
+    for pattern in includes {
+        let paths = resolve_include(&pattern, prefix);
+        if paths.is_empty() && !has_wildcard(&pattern) {
+            return Err(LoadError {
+                path: path.to_path_buf(),
+                message: format!("included file '{}' not found", pattern),
+            });
+        }
+        for included in paths {
+            load_recursive(&included, prefix, files)?;
+        }
+    }
+    Ok(())
+}
+
+/// 是否包含通配符
+pub(crate) fn has_wildcard(text: &str) -> bool {
+    text.contains(['*', '?', '['])
+}
+
+/// include 参数 -> 存在的文件（相对路径基于 prefix）
+pub fn resolve_include(pattern: &str, prefix: &Path) -> Vec<PathBuf> {
+    let pattern = Path::new(pattern);
+    let pattern = if pattern.is_absolute() { pattern.to_path_buf() } else { prefix.join(pattern) };
+    if !has_wildcard(&pattern.to_string_lossy()) {
+        return if pattern.is_file() { vec![pattern] } else { Vec::new() };
+    }
+
+    let mut candidates = vec![PathBuf::new()];
+    for component in pattern.components() {
+        let part = component.as_os_str().to_string_lossy();
+        if !matches!(component, Component::Normal(_)) || !has_wildcard(&part) {
+            for candidate in &mut candidates {
+                candidate.push(component.as_os_str());
+            }
+            continue;
+        }
+        let mut next = Vec::new();
+        for dir in &candidates {
+            let Ok(entries) = std::fs::read_dir(dir) else {
+                continue;
+            };
+            let mut names: Vec<String> = entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                .filter(|name| glob_match(&part, name) && (part.starts_with('.') || !name.starts_with('.')))
+                .collect();
+            names.sort();
+            next.extend(names.into_iter().map(|name| dir.join(name)));
+        }
+        candidates = next;
+    }
+    candidates.retain(|path| path.is_file());
+    candidates
+}
+
+/// 匹配一级路径：* 任意字符，? 单个字符，[abc] [a-z] [!x] 字符集合
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    glob_match_from(&pattern, &name)
+}
+
+fn glob_match_from(pattern: &[char], name: &[char]) -> bool {
+    match pattern.first() {
+        None => name.is_empty(),
+        Some('*') => (0..=name.len()).any(|skip| glob_match_from(&pattern[1..], &name[skip..])),
+        Some('?') => !name.is_empty() && glob_match_from(&pattern[1..], &name[1..]),
+        Some('[') => match (name.first(), pattern.iter().position(|c| *c == ']').filter(|end| *end > 1)) {
+            (Some(c), Some(end)) => {
+                let set = &pattern[1..end];
+                let (negate, set) = match set.first() {
+                    Some('!' | '^') => (true, &set[1..]),
+                    _ => (false, set),
+                };
+                let mut matched = false;
+                let mut i = 0;
+                while i < set.len() {
+                    if i + 2 < set.len() && set[i + 1] == '-' {
+                        matched |= set[i] <= *c && *c <= set[i + 2];
+                        i += 3;
+                    } else {
+                        matched |= set[i] == *c;
+                        i += 1;
+                    }
+                }
+                matched != negate && glob_match_from(&pattern[end + 1..], &name[1..])
+            }
+            // 没有配对的 ']'：按普通字符匹配
+            (Some(c), None) => *c == '[' && glob_match_from(&pattern[1..], &name[1..]),
+            (None, _) => false,
+        },
+        Some(p) => name.first() == Some(p) && glob_match_from(&pattern[1..], &name[1..]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glob_patterns() {
+        assert!(glob_match("*.conf", "site.conf"));
+        assert!(!glob_match("*.conf", "site.conf.bak"));
+        assert!(glob_match("site-?.conf", "site-a.conf"));
+        assert!(glob_match("[a-c]*", "blog"));
+        assert!(!glob_match("[!a-c]*", "blog"));
+        assert!(glob_match("*", ""));
+    }
+
+    #[test]
+    fn includes_resolve_relative_to_the_main_file() {
+        let dir = std::env::temp_dir().join(format!("easynginx-include-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        for name in ["sites-enabled/b.conf", "sites-enabled/a.conf", "sites-enabled/.hidden.conf", "conf.d/x.txt"] {
+            let path = dir.join(name);
+            std::fs::create_dir_all(path.parent().expect("parent")).expect("create dir");
+            std::fs::write(&path, "server { listen 80; }\n").expect("write");
+        }
+        std::fs::write(dir.join("nginx.conf"), "http {\n    include sites-*/*.conf;\n    include conf.d/*.conf;\n}\n").expect("write");
+
+        let files = load_tree(&dir.join("nginx.conf")).expect("loads");
+        let names: Vec<String> = files.iter()
+            .map(|file| file.path.strip_prefix(&dir).expect("inside dir").display().to_string())
+            .collect();
+        assert_eq!(names, ["nginx.conf", "sites-enabled/a.conf", "sites-enabled/b.conf"]);
+
+        std::fs::write(dir.join("nginx.conf"), "include missing.conf;\n").expect("write");
+        assert!(load_tree(&dir.join("nginx.conf")).unwrap_err().message.contains("missing.conf"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn corpus_main_file_loads_mime_types() {
+        let main = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/corpus/nginx/nginx.conf");
+        let files = load_tree(&main).expect("corpus loads");
+        assert_eq!(files.len(), 2);
+        assert!(files[1].path.ends_with("mime.types"));
+    }
+}
@@ -0,0 +1,666 @@
+// MIT License
+// 
+// Copyright (c) 2026 Laffinty
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+
+// ==============================================================================
+// nginx config parser - 无损的语法树
+// ==============================================================================
+// 解析 nginx 配置语法：指令、块、引号字符串、变量（$var、${var}）和注释。
+// 语法树保留原文中的每一个字节 - 空白、注释、引号和转义都原样保存，
+// Display 输出与输入逐字节相同。easyNginx 修改用户手写的文件时，只改动
+// 被修改的指令，文件其它部分保持不变。
+//
+// 词法规则与 nginx 的 ngx_conf_read_token 一致：
+//   - '#' 只在词首开始注释，词中间的 '#' 是普通字符
+//   - 引号只在词首开始引号字符串；引号后可以紧跟 ')'（用于 if 条件）
+//   - '\' 转义下一个字符，${name} 中的 '{' 不会开始块
+//   - *_by_lua_block 的内容是 Lua 代码，整体保存为原文
+
+use std::fmt;
+
+/// 解析错误（行号、列号从 1 开始）
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// 一个配置文件
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Config {
+    pub nodes: Vec<Node>,
+}
+
+/// 块中的一项
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Node {
+    /// 空白和换行
+    Whitespace(String),
+    /// 注释，包含开头的 '#'，不包含行尾换行
+    Comment(String),
+    Directive(Directive),
+}
+
+/// 一个词：指令名或参数
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Word {
+    /// 原文，包括引号和转义
+    pub raw: String,
+}
+
+/// 指令的一个参数及其前面的空白、注释
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Arg {
+    pub gap: String,
+    pub word: Word,
+}
+
+/// 指令结尾
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Body {
+    /// ';'
+    Semicolon,
+    /// '{' ... '}'
+    Block(Block),
+    /// 不解析的块（如 *_by_lua_block），保存括号之间的原文
+    Raw(String),
+}
+
+/// 一个指令
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Directive {
+    pub name: Word,
+    pub args: Vec<Arg>,
+    /// 最后一个参数与 ';' 或 '{' 之间的空白、注释
+    pub gap: String,
+    pub body: Body,
+    /// 指令名所在的行
+    pub line: usize,
+}
+
+/// '{' 与 '}' 之间的内容
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Block {
+    pub nodes: Vec<Node>,
+}
+
+// ==============================================================================
+// Words
+// ==============================================================================
+
+impl Word {
+    /// 由值构造词；值中有特殊字符时加双引号
+    pub fn new(value: &str) -> Self {
+        let needs_quotes = value.is_empty()
+            || value.starts_with(['"', '\'', '#'])
+            || value.contains(|c: char| c.is_whitespace() || matches!(c, ';' | '{' | '}' | '\\'));
+        let raw = if needs_quotes {
+            let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+            format!("\"{}\"", escaped)
+        } else {
+            value.to_string()
+        };
+        Self { raw }
+    }
+
+    /// 是否带引号
+    pub fn is_quoted(&self) -> bool {
+        self.raw.starts_with(['"', '\''])
+    }
+
+    /// nginx 看到的值：去掉引号，处理 \" \' \\ \t \r \n 转义
+    pub fn value(&self) -> String {
+        let text = if self.is_quoted() {
+            // 引号后可能跟着 ')'（if 条件）
+            let quote = self.raw.chars().next().unwrap_or('"');
+            let close = self.raw.rfind(quote).filter(|i| *i > 0).unwrap_or(self.raw.len());
+            format!("{}{}", &self.raw[1..close], &self.raw[(close + 1).min(self.raw.len())..])
+        } else {
+            self.raw.clone()
+        };
+        unescape(&text)
+    }
+}
+
+fn unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.peek() {
+                Some('"' | '\'' | '\\') => {
+                    out.extend(chars.next());
+                    continue;
+                }
+                Some('t') => { chars.next(); out.push('\t'); continue; }
+                Some('r') => { chars.next(); out.push('\r'); continue; }
+                Some('n') => { chars.next(); out.push('\n'); continue; }
+                _ => {}
+            }
+        }
+        out.push(c);
+    }
+    out
+}
+
+// ==============================================================================
+// Tree helpers
+// ==============================================================================
+
+impl Config {
+    pub fn parse(source: &str) -> Result<Self, ParseError> {
+        let mut parser = Parser::new(source);
+        let nodes = parser.parse_nodes(false)?;
+        Ok(Self { nodes })
+    }
+
+    /// 顶层指令
+    pub fn directives(&self) -> impl Iterator<Item = &Directive> {
+        directives(&self.nodes)
+    }
+
+    pub fn directives_mut(&mut self) -> impl Iterator<Item = &mut Directive> {
+        directives_mut(&mut self.nodes)
+    }
+
+    /// 按文件中的顺序访问所有指令（包括块内的）
+    pub fn walk<'a>(&'a self, visit: &mut dyn FnMut(&'a Directive)) {
+        walk(&self.nodes, visit);
+    }
+}
+
+impl Block {
+    pub fn directives(&self) -> impl Iterator<Item = &Directive> {
+        directives(&self.nodes)
+    }
+
+    pub fn directives_mut(&mut self) -> impl Iterator<Item = &mut Directive> {
+        directives_mut(&mut self.nodes)
+    }
+
+    /// 第一个指定名称的子指令
+    pub fn find(&self, name: &str) -> Option<&Directive> {
+        self.directives().find(|directive| directive.name() == name)
+    }
+
+    /// 在块的末尾（'}' 所在行之前）追加指令，使用 indent 缩进
+    pub fn push(&mut self, directive: Directive, indent: &str) {
+        // 块结尾的空白属于 '}' 所在行的缩进，新指令插在它之前
+        let closing = match self.nodes.last() {
+            Some(Node::Whitespace(text)) => {
+                let text = text.clone();
+                self.nodes.pop();
+                text
+            }
+            _ => String::new(),
+        };
+        let (before, after) = match closing.rfind('\n') {
+            Some(i) => (closing[..i].to_string(), closing[i..].to_string()),
+            None => (closing, "\n".to_string()),
+        };
+        self.nodes.push(Node::Whitespace(format!("{}\n{}", before, indent)));
+        self.nodes.push(Node::Directive(directive));
+        self.nodes.push(Node::Whitespace(after));
+    }
+}
+
+impl Directive {
+    /// 新指令：参数之间用一个空格分隔，以 ';' 结尾
+    pub fn new(name: &str, args: &[&str]) -> Self {
+        Self {
+            name: Word::new(name),
+            args: args.iter().map(|arg| Arg { gap: " ".into(), word: Word::new(arg) }).collect(),
+            gap: String::new(),
+            body: Body::Semicolon,
+            line: 0,
+        }
+    }
+
+    pub fn name(&self) -> String {
+        self.name.value()
+    }
+
+    /// 参数的值
+    pub fn args(&self) -> Vec<String> {
+        self.args.iter().map(|arg| arg.word.value()).collect()
+    }
+
+    /// 替换参数；保留原有参数前的空白，新增的参数用一个空格分隔
+    pub fn set_args(&mut self, values: &[&str]) {
+        let gaps: Vec<String> = self.args.iter().map(|arg| arg.gap.clone()).collect();
+        self.args = values.iter().enumerate().map(|(i, value)| Arg {
+            gap: gaps.get(i).cloned().unwrap_or_else(|| " ".into()),
+            word: Word::new(value),
+        }).collect();
+    }
+
+    pub fn block(&self) -> Option<&Block> {
+        match &self.body {
+            Body::Block(block) => Some(block),
+            _ => None,
+        }
+    }
+
+    pub fn block_mut(&mut self) -> Option<&mut Block> {
+        match &mut self.body {
+            Body::Block(block) => Some(block),
+            _ => None,
+        }
+    }
+}
+
+fn directives(nodes: &[Node]) -> impl Iterator<Item = &Directive> {
+    nodes.iter().filter_map(|node| match node {
+        Node::Directive(directive) => Some(directive),
+        _ => None,
+    })
+}
+
+fn directives_mut(nodes: &mut [Node]) -> impl Iterator<Item = &mut Directive> {
+    nodes.iter_mut().filter_map(|node| match node {
+        Node::Directive(directive) => Some(directive),
+        _ => None,
+    })
+}
+
+fn walk<'a>(nodes: &'a [Node], visit: &mut dyn FnMut(&'a Directive)) {
+    for directive in directives(nodes) {
+        visit(directive);
+        if let Some(block) = directive.block() {
+            walk(&block.nodes, visit);
+        }
+    }
+}
+
+// ==============================================================================
+// Output
+// ==============================================================================
+
+impl fmt::Display for Config {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.nodes.iter().try_for_each(|node| node.fmt(f))
+    }
+}
+
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Node::Whitespace(text) | Node::Comment(text) => f.write_str(text),
+            Node::Directive(directive) => directive.fmt(f),
+        }
+    }
+}
+
+impl fmt::Display for Directive {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name.raw)?;
+        for arg in &self.args {
+            f.write_str(&arg.gap)?;
+            f.write_str(&arg.word.raw)?;
+        }
+        f.write_str(&self.gap)?;
+        match &self.body {
+            Body::Semicolon => f.write_str(";"),
+            Body::Block(block) => write!(f, "{{{}}}", block),
+            Body::Raw(text) => write!(f, "{{{}}}", text),
+        }
+    }
+}
+
+impl fmt::Display for Block {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.nodes.iter().try_for_each(|node| node.fmt(f))
+    }
+}
+
+// ==============================================================================
+// Parser
+// ==============================================================================
+
+/// 内容不按 nginx 语法解析的块
+fn is_raw_block(name: &str) -> bool {
+    name.ends_with("_by_lua_block")
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    pos: usize,
+    line: usize,
+    line_start: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(source: &'a str) -> Self {
+        Self { source, pos: 0, line: 1, line_start: 0 }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.source[self.pos..].chars().next()
+    }
+
+    fn peek_second(&self) -> Option<char> {
+        let mut chars = self.source[self.pos..].chars();
+        chars.next();
+        chars.next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        if c == '\n' {
+            self.line += 1;
+            self.line_start = self.pos;
+        }
+        Some(c)
+    }
+
+    fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError {
+            line: self.line,
+            column: self.source[self.line_start..self.pos].chars().count() + 1,
+            message: message.into(),
+        }
+    }
+
+    fn unexpected(&self) -> ParseError {
+        match self.peek() {
+            Some(c) => self.error(format!("unexpected '{}'", c)),
+            None => self.error("unexpected end of file"),
+        }
+    }
+
+    fn take_while(&mut self, keep: impl Fn(char) -> bool) -> &'a str {
+        let start = self.pos;
+        while self.peek().is_some_and(&keep) {
+            self.bump();
+        }
+        &self.source[start..self.pos]
+    }
+
+    /// 块内容：直到文件结尾（顶层）或 '}'（块内，不消耗 '}'）
+    fn parse_nodes(&mut self, in_block: bool) -> Result<Vec<Node>, ParseError> {
+        let mut nodes = Vec::new();
+        loop {
+            match self.peek() {
+                None if in_block => return Err(self.error("unexpected end of file, expecting '}'")),
+                None => return Ok(nodes),
+                Some('}') if in_block => return Ok(nodes),
+                Some('}' | ';' | '{') => return Err(self.unexpected()),
+                Some(c) if c.is_whitespace() => {
+                    let text = self.take_while(char::is_whitespace);
+                    nodes.push(Node::Whitespace(text.to_string()));
+                }
+                Some('#') => {
+                    let text = self.take_while(|c| c != '\n');
+                    nodes.push(Node::Comment(text.to_string()));
+                }
+                Some(_) => nodes.push(Node::Directive(self.parse_directive()?)),
+            }
+        }
+    }
+
+    /// 空白和注释
+    fn parse_gap(&mut self) -> &'a str {
+        let start = self.pos;
+        loop {
+            match self.peek() {
+                Some(c) if c.is_whitespace() => {
+                    self.bump();
+                }
+                Some('#') => {
+                    self.take_while(|c| c != '\n');
+                }
+                _ => return &self.source[start..self.pos],
+            }
+        }
+    }
+
+    fn parse_directive(&mut self) -> Result<Directive, ParseError> {
+        let line = self.line;
+        let name = self.parse_word()?;
+        let mut args = Vec::new();
+        loop {
+            let gap = self.parse_gap().to_string();
+            match self.peek() {
+                Some(';') => {
+                    self.bump();
+                    return Ok(Directive { name, args, gap, body: Body::Semicolon, line });
+                }
+                Some('{') => {
+                    self.bump();
+                    let body = if is_raw_block(&name.value()) {
+                        Body::Raw(self.parse_raw_block()?)
+                    } else {
+                        Body::Block(Block { nodes: self.parse_nodes(true)? })
+                    };
+                    // parse_nodes 在 '}' 处返回
+                    self.bump();
+                    return Ok(Directive { name, args, gap, body, line });
+                }
+                None => return Err(self.error("unexpected end of file, expecting ';' or '}'")),
+                Some('}') => return Err(self.unexpected()),
+                Some(_) if gap.is_empty() => return Err(self.unexpected()),
+                Some(_) => {
+                    let word = self.parse_word()?;
+                    args.push(Arg { gap, word });
+                }
+            }
+        }
+    }
+
+    fn parse_word(&mut self) -> Result<Word, ParseError> {
+        let start = self.pos;
+        match self.peek() {
+            Some(quote @ ('"' | '\'')) => {
+                self.bump();
+                loop {
+                    match self.bump() {
+                        None => return Err(self.error("unterminated quoted string")),
+                        Some('\\') => {
+                            self.bump();
+                        }
+                        Some(c) if c == quote => break,
+                        Some(_) => {}
+                    }
+                }
+                if self.peek() == Some(')') {
+                    self.bump();
+                }
+                // 引号字符串后必须是分隔符
+                match self.peek() {
+                    None | Some(';' | '{' | '}') => {}
+                    Some(c) if c.is_whitespace() => {}
+                    Some(_) => return Err(self.unexpected()),
+                }
+            }
+            _ => loop {
+                match self.peek() {
+                    None | Some(';' | '{' | '}') => break,
+                    Some(c) if c.is_whitespace() => break,
+                    Some('\\') => {
+                        self.bump();
+                        self.bump();
+                    }
+                    Some('$') if self.peek_second() == Some('{') => {
+                        self.bump();
+                        self.bump();
+                        self.take_while(|c| c != '}' && c != '\n');
+                        if self.bump() != Some('}') {
+                            return Err(self.error("unterminated variable name"));
+                        }
+                    }
+                    Some(_) => {
+                        self.bump();
+                    }
+                }
+            },
+        }
+        Ok(Word { raw: self.source[start..self.pos].to_string() })
+    }
+
+    /// 不解析的块：配对括号，跳过字符串和 Lua 注释（--），不消耗最后的 '}'
+    fn parse_raw_block(&mut self) -> Result<String, ParseError> {
+        let start = self.pos;
+        let mut depth = 0usize;
+        loop {
+            match self.peek() {
+                None => return Err(self.error("unexpected end of file, expecting '}'")),
+                Some('}') if depth == 0 => return Ok(self.source[start..self.pos].to_string()),
+                Some('}') => {
+                    depth -= 1;
+                    self.bump();
+                }
+                Some('{') => {
+                    depth += 1;
+                    self.bump();
+                }
+                Some(quote @ ('"' | '\'')) => {
+                    self.bump();
+                    loop {
+                        match self.bump() {
+                            None => return Err(self.error("unterminated quoted string")),
+                            Some('\\') => {
+                                self.bump();
+                            }
+                            Some(c) if c == quote => break,
+                            Some(_) => {}
+                        }
+                    }
+                }
+                Some('-') if self.peek_second() == Some('-') => {
+                    self.take_while(|c| c != '\n');
+                }
+                Some(_) => {
+                    self.bump();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn corpus_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/corpus/nginx")
+    }
+
+    fn corpus(name: &str) -> Config {
+        let text = std::fs::read_to_string(corpus_dir().join(name)).expect("corpus file");
+        Config::parse(&text).expect("corpus file parses")
+    }
+
+    fn find_all<'a>(config: &'a Config, name: &str) -> Vec<&'a Directive> {
+        let mut found = Vec::new();
+        config.walk(&mut |directive| {
+            if directive.name() == name {
+                found.push(directive);
+            }
+        });
+        found
+    }
+
+    #[test]
+    fn corpus_round_trips_byte_for_byte() {
+        let mut count = 0;
+        for entry in std::fs::read_dir(corpus_dir()).expect("corpus directory") {
+            let path = entry.expect("corpus entry").path();
+            let text = std::fs::read_to_string(&path).expect("corpus file");
+            let config = Config::parse(&text).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+            assert_eq!(config.to_string(), text, "{} does not round-trip", path.display());
+            count += 1;
+        }
+        assert!(count >= 8);
+    }
+
+    #[test]
+    fn corpus_structure() {
+        let config = corpus("wordpress.conf");
+        let servers: Vec<&Directive> = config.directives().filter(|d| d.name() == "server").collect();
+        assert_eq!(servers.len(), 2);
+        let server_name = servers[0].block().and_then(|block| block.find("server_name")).expect("server_name");
+        assert_eq!(server_name.args(), ["blog.example.org", "www.blog.example.org"]);
+        assert_eq!(find_all(&config, "ssl_ciphers")[0].args()[0], "ECDHE-ECDSA-AES128-GCM-SHA256:ECDHE-RSA-AES128-GCM-SHA256:ECDHE-ECDSA-AES256-GCM-SHA384");
+        assert_eq!(find_all(&config, "location").len(), 6);
+
+        let config = corpus("reverse-proxy.conf");
+        let map = config.directives().next().expect("map");
+        assert_eq!(map.args(), ["$http_upgrade", "$connection_upgrade"]);
+        assert_eq!(map.block().expect("map block").directives().nth(1).expect("'' close").name(), "");
+
+        let config = corpus("openresty-lua.conf");
+        let lua = find_all(&config, "content_by_lua_block");
+        assert!(matches!(&lua[0].body, Body::Raw(code) if code.contains("punct = \"}\"")));
+        assert_eq!(find_all(&config, "proxy_pass")[0].args(), ["http://127.0.0.1:9000"]);
+    }
+
+    #[test]
+    fn edge_case_values() {
+        let config = corpus("edge-cases.conf");
+        let value = |name: &str, index: usize| find_all(&config, name).iter().map(|d| d.args()).nth(index).expect(name);
+        assert_eq!(value("server_name", 0), ["quoted name.example", "single.example"]);
+        assert_eq!(value("set", 0), ["$path_prefix", "/srv/${host}_data"]);
+        // 与 nginx 相同：只有 \" \' \\ 去掉反斜杠，\; 保持原样（但不结束指令）
+        assert_eq!(value("set", 1), ["$escaped", "\\;semicolon\\ and\\ space"]);
+        assert_eq!(value("set", 2), ["$tricky", "say \"hi\"\n"]);
+        assert_eq!(value("if", 0), ["($http_user_agent", "~", "MSIE [5-6]\\.)"]);
+        assert_eq!(value("log_format", 0), ["compact", "$remote_addr \"$request\"", "$status"]);
+        assert_eq!(value("return", 1), ["200", "a#b;c{d}"]);
+        assert_eq!(value("worker_rlimit_nofile", 0), ["65535"]);
+        assert!(find_all(&config, "empty_block")[0].block().is_some_and(|block| block.nodes.is_empty()));
+    }
+
+    #[test]
+    fn errors_report_position() {
+        let error = Config::parse("http {\n    server {\n        listen 80;\n    }\n").unwrap_err();
+        assert_eq!((error.line, error.message.as_str()), (5, "unexpected end of file, expecting '}'"));
+        let error = Config::parse("events {}\n}\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        let error = Config::parse("return 200 \"unterminated;\n").unwrap_err();
+        assert_eq!(error.message, "unterminated quoted string");
+        let error = Config::parse("listen 80").unwrap_err();
+        assert_eq!(error.message, "unexpected end of file, expecting ';' or '}'");
+        assert!(Config::parse("add_header X \"a\"b;").is_err());
+    }
+
+    #[test]
+    fn edits_leave_the_rest_untouched() {
+        let text = "server {\n    # keep me\n    listen  80;\n    server_name a.example;\n}\n";
+        let mut config = Config::parse(text).expect("parses");
+        let server = config.directives_mut().next().expect("server");
+        let block = server.block_mut().expect("block");
+        block.directives_mut().next().expect("listen").set_args(&["8080", "default server"]);
+        block.push(Directive::new("root", &["/srv/www"]), "    ");
+        assert_eq!(config.to_string(), "server {\n    # keep me\n    listen  8080 \"default server\";\n    server_name a.example;\n    root /srv/www;\n}\n");
+    }
+}
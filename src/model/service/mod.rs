@@ -0,0 +1,503 @@
+// MIT License
+//
+// Copyright (c) 2026 Laffinty
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// ==============================================================================
+// Service Module - 后台守护进程 / systemd 集成
+// ==============================================================================
+// 在 `--daemon` 模式下：
+// - 写入 pidfile（`--pidfile <path>` 或默认路径）
+// - 通过 NOTIFY_SOCKET 发送 sd_notify READY=1 / STOPPING=1
+// - 若设置了 WATCHDOG_USEC，按一半间隔发送 WATCHDOG=1
+//
+// 任何模式下都处理 StartupOnBootRequest（"开机启动" 菜单项）：
+// 生成 systemd unit 并通过 systemctl enable/disable。
+
+use async_trait::async_trait;
+use std::any::{Any, TypeId};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use crate::{MessageEnvelope, MessageBus, Module, RunMode, module_init, cli_command};
+
+/// systemd unit 名称
+pub const UNIT_NAME: &str = "easynginx.service";
+
+/// 开机启动请求 - 由 UI 的 "开机启动" 菜单项发布
+#[derive(Clone, Debug)]
+pub struct StartupOnBootRequest {
+    pub enable: bool,
+}
+
+impl StartupOnBootRequest {
+    pub fn new(enable: bool) -> Self {
+        Self { enable }
+    }
+}
+
+impl crate::Message for StartupOnBootRequest {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn message_type(&self) -> TypeId {
+        TypeId::of::<StartupOnBootRequest>()
+    }
+
+    fn clone_box(&self) -> Box<dyn crate::Message> {
+        Box::new(self.clone())
+    }
+}
+
+/// 开机启动结果 - 返回实际生效的状态
+#[derive(Clone, Debug)]
+pub struct StartupOnBootResponse {
+    pub enabled: bool,
+    pub error: Option<String>,
+}
+
+impl crate::Message for StartupOnBootResponse {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn message_type(&self) -> TypeId {
+        TypeId::of::<StartupOnBootResponse>()
+    }
+
+    fn clone_box(&self) -> Box<dyn crate::Message> {
+        Box::new(self.clone())
+    }
+}
+
+// ==============================================================================
+// sd_notify
+// ==============================================================================
+
+/// 向 systemd 发送状态通知
+///
+/// 未设置 NOTIFY_SOCKET（不是由 systemd 启动）时返回 Ok(false)
+pub fn sd_notify(state: &str) -> std::io::Result<bool> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::net::UnixDatagram;
+
+        let socket_path = match std::env::var_os("NOTIFY_SOCKET") {
+            Some(p) if !p.is_empty() => p,
+            _ => return Ok(false),
+        };
+        let socket = UnixDatagram::unbound()?;
+        let socket_path = socket_path.to_string_lossy().into_owned();
+
+        // '@' 开头表示 Linux 抽象命名空间 socket
+        if let Some(_abstract_name) = socket_path.strip_prefix('@') {
+            #[cfg(target_os = "linux")]
+            {
+                use std::os::linux::net::SocketAddrExt;
+                let addr = std::os::unix::net::SocketAddr::from_abstract_name(_abstract_name.as_bytes())?;
+                socket.send_to_addr(state.as_bytes(), &addr)?;
+                return Ok(true);
+            }
+            #[cfg(not(target_os = "linux"))]
+            return Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "abstract sockets require Linux"));
+        }
+
+        socket.send_to(state.as_bytes(), &socket_path)?;
+        Ok(true)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = state;
+        Ok(false)
+    }
+}
+
+/// 从 WATCHDOG_USEC 计算心跳间隔（超时时间的一半）
+fn watchdog_interval() -> Option<std::time::Duration> {
+    let usec = std::env::var("WATCHDOG_USEC").ok();
+    let pid = std::env::var("WATCHDOG_PID").ok();
+    parse_watchdog(usec.as_deref(), pid.as_deref(), std::process::id())
+}
+
+fn parse_watchdog(usec: Option<&str>, pid: Option<&str>, own_pid: u32) -> Option<std::time::Duration> {
+    let usec: u64 = usec?.parse().ok()?;
+    // WATCHDOG_PID 存在时只有指定进程需要发送心跳
+    if let Some(pid) = pid {
+        if pid.parse::<u32>().ok() != Some(own_pid) {
+            return None;
+        }
+    }
+    if usec == 0 {
+        return None;
+    }
+    Some(std::time::Duration::from_micros(usec / 2))
+}
+
+// ==============================================================================
+// pidfile / systemd unit
+// ==============================================================================
+
+/// 当前进程是否以 root 运行（读取 /proc/self/status 中的有效 UID）
+fn is_root() -> bool {
+    std::fs::read_to_string("/proc/self/status")
+        .ok()
+        .and_then(|status| {
+            status.lines()
+                .find_map(|line| line.strip_prefix("Uid:"))
+                .and_then(|uids| uids.split_whitespace().nth(1).map(|euid| euid == "0"))
+        })
+        .unwrap_or(false)
+}
+
+/// 默认 pidfile 路径：root 使用 /run，普通用户使用 XDG_RUNTIME_DIR
+pub fn default_pidfile() -> PathBuf {
+    if cfg!(unix) && is_root() {
+        return PathBuf::from("/run/easynginx.pid");
+    }
+    std::env::var_os("XDG_RUNTIME_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(std::env::temp_dir)
+        .join("easynginx.pid")
+}
+
+/// 当前进程使用的 pidfile 路径
+pub fn pidfile_path() -> PathBuf {
+    crate::arg_value("--pidfile")
+        .map(PathBuf::from)
+        .unwrap_or_else(default_pidfile)
+}
+
+fn write_pidfile(path: &Path) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, format!("{}\n", std::process::id()))
+}
+
+/// unit 文件安装位置：root 安装为系统服务，否则为用户服务
+pub fn unit_path() -> PathBuf {
+    if is_root() {
+        return PathBuf::from("/etc/systemd/system").join(UNIT_NAME);
+    }
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")))
+        .unwrap_or_else(|| PathBuf::from("."));
+    config_home.join("systemd/user").join(UNIT_NAME)
+}
+
+/// 生成 systemd unit 文件内容
+pub fn generate_unit() -> String {
+    let exe = std::env::current_exe()
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|_| "/usr/local/bin/easnginx".to_string());
+    let wanted_by = if is_root() { "multi-user.target" } else { "default.target" };
+    unit_file(&exe, &default_pidfile(), wanted_by)
+}
+
+fn unit_file(exe: &str, pidfile: &Path, wanted_by: &str) -> String {
+    format!(
+        "[Unit]\n\
+         Description=easyNginx - Nginx management service\n\
+         After=network-online.target\n\
+         Wants=network-online.target\n\
+         \n\
+         [Service]\n\
+         Type=notify\n\
+         NotifyAccess=main\n\
+         ExecStart={exe} --daemon --pidfile {pid_arg}\n\
+         PIDFile={pid}\n\
+         WatchdogSec=30\n\
+         Restart=on-failure\n\
+         RestartSec=5\n\
+         \n\
+         [Install]\n\
+         WantedBy={wanted_by}\n",
+        exe = exec_arg(exe),
+        pid_arg = exec_arg(&pidfile.display().to_string()),
+        pid = pidfile.display().to_string().replace('%', "%%"),
+        wanted_by = wanted_by,
+    )
+}
+
+/// ExecStart 中的单个参数：含空白、引号或反斜杠时按 systemd 规则加双引号并转义，
+/// % 和 $ 会被 systemd 展开，需要加倍
+fn exec_arg(arg: &str) -> String {
+    let escaped = arg.replace('%', "%%").replace('$', "$$");
+    if !escaped.chars().any(|c| c.is_whitespace() || matches!(c, '"' | '\'' | '\\' | ';')) {
+        return escaped;
+    }
+    let mut quoted = String::with_capacity(escaped.len() + 2);
+    quoted.push('"');
+    for c in escaped.chars() {
+        match c {
+            '"' | '\\' => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn systemctl(args: &[&str]) -> Result<(), String> {
+    let mut command = std::process::Command::new("systemctl");
+    if !is_root() {
+        command.arg("--user");
+    }
+    let output = command.args(args)
+        .output()
+        .map_err(|e| format!("failed to run systemctl: {}", e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+/// 安装 unit 文件并启用开机启动
+pub fn install_service() -> Result<PathBuf, String> {
+    if !cfg!(target_os = "linux") {
+        return Err("startup on boot is only supported with systemd on Linux".to_string());
+    }
+    let path = unit_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("{}: {}", parent.display(), e))?;
+    }
+    std::fs::write(&path, generate_unit()).map_err(|e| format!("{}: {}", path.display(), e))?;
+    systemctl(&["daemon-reload"])?;
+    systemctl(&["enable", UNIT_NAME])?;
+    Ok(path)
+}
+
+/// 禁用开机启动（保留 unit 文件）
+pub fn uninstall_service() -> Result<(), String> {
+    if !cfg!(target_os = "linux") {
+        return Err("startup on boot is only supported with systemd on Linux".to_string());
+    }
+    systemctl(&["disable", UNIT_NAME])
+}
+
+/// 开机启动是否已启用
+pub fn is_startup_enabled() -> bool {
+    if !cfg!(target_os = "linux") || !unit_path().exists() {
+        return false;
+    }
+    systemctl(&["is-enabled", "--quiet", UNIT_NAME]).is_ok()
+}
+
+// ==============================================================================
+// CLI commands
+// ==============================================================================
+
+fn cli_systemd_unit(_args: &[String]) -> Result<(), String> {
+    print!("{}", generate_unit());
+    Ok(())
+}
+
+fn cli_install_service(_args: &[String]) -> Result<(), String> {
+    let path = install_service()?;
+    println!("Installed and enabled {}", path.display());
+    Ok(())
+}
+
+fn cli_uninstall_service(_args: &[String]) -> Result<(), String> {
+    uninstall_service()?;
+    println!("Disabled {}", UNIT_NAME);
+    Ok(())
+}
+
+cli_command!("systemd-unit", "Print the systemd unit for running easyNginx as a service", cli_systemd_unit);
+cli_command!("install-service", "Install the systemd unit and enable startup on boot", cli_install_service);
+cli_command!("uninstall-service", "Disable startup on boot", cli_uninstall_service);
+
+// ==============================================================================
+// Module
+// ==============================================================================
+
+pub struct ServiceModule {
+    name: &'static str,
+    bus: Arc<RwLock<Option<Arc<MessageBus>>>>,
+    /// daemon 模式下写入的 pidfile
+    pidfile: Arc<RwLock<Option<PathBuf>>>,
+    watchdog_task: Arc<RwLock<Option<tokio::task::JoinHandle<()>>>>,
+}
+
+impl ServiceModule {
+    pub fn new() -> Self {
+        Self {
+            name: "service",
+            bus: Arc::new(RwLock::new(None)),
+            pidfile: Arc::new(RwLock::new(None)),
+            watchdog_task: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    async fn start_daemon(&self) {
+        let path = pidfile_path();
+        match write_pidfile(&path) {
+            Ok(()) => {
+                println!("[Service] Wrote pidfile {}", path.display());
+                *self.pidfile.write().await = Some(path);
+            }
+            Err(e) => eprintln!("[Service] Failed to write pidfile {}: {}", path.display(), e),
+        }
+
+        if let Some(interval) = watchdog_interval() {
+            println!("[Service] Watchdog enabled, ping every {:?}", interval);
+            let handle = tokio::spawn(async move {
+                let mut ticker = tokio::time::interval(interval);
+                loop {
+                    ticker.tick().await;
+                    if let Err(e) = sd_notify("WATCHDOG=1") {
+                        eprintln!("[Service] Watchdog ping failed: {}", e);
+                    }
+                }
+            });
+            *self.watchdog_task.write().await = Some(handle);
+        }
+    }
+
+    async fn handle_startup_on_boot(&self, request: &StartupOnBootRequest) {
+        let result = if request.enable {
+            install_service().map(|path| println!("[Service] Installed {}", path.display()))
+        } else {
+            uninstall_service()
+        };
+
+        let response = match result {
+            Ok(()) => StartupOnBootResponse { enabled: request.enable, error: None },
+            Err(e) => {
+                eprintln!("[Service] Startup on boot failed: {}", e);
+                StartupOnBootResponse { enabled: is_startup_enabled(), error: Some(e) }
+            }
+        };
+
+        if let Some(bus) = &*self.bus.read().await {
+            let _ = bus.publish(response).await;
+        }
+    }
+}
+
+impl Default for ServiceModule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Module for ServiceModule {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    async fn initialize(&mut self, bus: Arc<MessageBus>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        *self.bus.write().await = Some(bus.clone());
+
+        let request_type = bus.register_message_type::<StartupOnBootRequest>().await;
+        bus.register_message_type::<StartupOnBootResponse>().await;
+        bus.subscribe(request_type, self.name().to_string()).await;
+        // SystemMessage 由 main 在模块注册后注册，这里只需订阅
+        bus.subscribe(TypeId::of::<crate::SystemMessage>(), self.name().to_string()).await;
+
+        if crate::run_mode() == RunMode::Daemon {
+            self.start_daemon().await;
+        }
+
+        Ok(())
+    }
+
+    async fn process_message(&self, envelope: MessageEnvelope) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if envelope.message_type == TypeId::of::<crate::SystemMessage>() {
+            if let Some(msg) = envelope.payload.as_any().downcast_ref::<crate::SystemMessage>() {
+                // 框架发出初始化完成消息后才报告就绪
+                if msg.source == "main" && crate::run_mode() == RunMode::Daemon {
+                    if let Ok(true) = sd_notify(&format!("READY=1\nMAINPID={}", std::process::id())) {
+                        println!("[Service] Notified systemd: READY");
+                    }
+                }
+            }
+        } else if envelope.message_type == TypeId::of::<StartupOnBootRequest>() {
+            if let Some(msg) = envelope.payload.as_any().downcast_ref::<StartupOnBootRequest>() {
+                self.handle_startup_on_boot(msg).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn shutdown(&mut self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if crate::run_mode() == RunMode::Daemon {
+            let _ = sd_notify("STOPPING=1");
+        }
+        if let Some(handle) = self.watchdog_task.write().await.take() {
+            handle.abort();
+        }
+        if let Some(path) = self.pidfile.write().await.take() {
+            if let Err(e) = std::fs::remove_file(&path) {
+                eprintln!("[Service] Failed to remove pidfile {}: {}", path.display(), e);
+            }
+        }
+        Ok(())
+    }
+}
+
+module_init!(ServiceModule, "service");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn watchdog_interval_is_half_the_timeout() {
+        assert_eq!(parse_watchdog(Some("30000000"), None, 42), Some(Duration::from_secs(15)));
+        assert_eq!(parse_watchdog(Some("30000000"), Some("42"), 42), Some(Duration::from_secs(15)));
+        // 心跳属于其它进程、未启用或值非法
+        assert_eq!(parse_watchdog(Some("30000000"), Some("7"), 42), None);
+        assert_eq!(parse_watchdog(None, Some("42"), 42), None);
+        assert_eq!(parse_watchdog(Some("0"), None, 42), None);
+        assert_eq!(parse_watchdog(Some("soon"), None, 42), None);
+    }
+
+    #[test]
+    fn generates_notify_unit() {
+        let unit = unit_file("/opt/easnginx", Path::new("/run/easynginx.pid"), "multi-user.target");
+        assert!(unit.contains("\nType=notify\n"));
+        assert!(unit.contains("\nExecStart=/opt/easnginx --daemon --pidfile /run/easynginx.pid\n"));
+        assert!(unit.contains("\nPIDFile=/run/easynginx.pid\n"));
+        assert!(unit.contains("\nWatchdogSec=30\n"));
+        assert!(unit.ends_with("[Install]\nWantedBy=multi-user.target\n"));
+
+        // 含空格、引号和说明符的路径
+        let unit = unit_file("/opt/easy nginx/easnginx", Path::new("/run/100%/a\"b\\c.pid"), "default.target");
+        assert!(unit.contains("\nExecStart=\"/opt/easy nginx/easnginx\" --daemon --pidfile \"/run/100%%/a\\\"b\\\\c.pid\"\n"), "{}", unit);
+        assert!(unit.contains("\nPIDFile=/run/100%%/a\"b\\c.pid\n"), "{}", unit);
+        assert_eq!(exec_arg("/opt/$HOME"), "/opt/$$HOME");
+
+        let exe = std::env::current_exe().unwrap().display().to_string();
+        assert!(generate_unit().contains(&format!("ExecStart={} --daemon", exe)));
+    }
+}
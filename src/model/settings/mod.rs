@@ -0,0 +1,169 @@
+// MIT License
+//
+// Copyright (c) 2026 Laffinty
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// ==============================================================================
+// Settings - 用户设置持久化
+// ==============================================================================
+// 设置保存在 <config_dir>/settings.toml。这里不是消息模块：启动时各模块
+// 需要同步读取设置（如初始语言），因此提供简单的 load / update 函数。
+// 未知字段会被忽略，缺失字段使用默认值，旧版本的设置文件可以直接读取。
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// 用户设置
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// 用户选择的界面语言（BCP-47 标签），None 表示跟随系统
+    pub language: Option<String>,
+    /// nginx 程序及配置
+    pub nginx: NginxSettings,
+    /// 站点列表的列布局和排序
+    pub site_list: SiteListSettings,
+}
+
+/// [site_list] 设置
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SiteListSettings {
+    /// 按显示顺序排列的列；为空时使用默认布局，未列出的列追加在末尾
+    pub columns: Vec<ColumnSettings>,
+    /// 排序列的 id，None 表示按添加顺序
+    pub sort_column: Option<String>,
+    pub sort_descending: bool,
+}
+
+/// 一列的宽度和可见性
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ColumnSettings {
+    pub id: String,
+    pub width: f32,
+    pub visible: bool,
+}
+
+/// [nginx] 设置
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NginxSettings {
+    /// nginx 可执行文件，默认从 PATH 中查找
+    pub binary: String,
+    /// 主配置文件（传给 nginx -c），None 表示使用 nginx 编译时的默认路径
+    pub conf_path: Option<String>,
+    /// 前缀目录（传给 nginx -p），None 表示使用 nginx 编译时的默认路径
+    pub prefix: Option<String>,
+    /// 重载后检查的本地地址（如 http://127.0.0.1/），None 表示只检查进程
+    pub probe_url: Option<String>,
+}
+
+impl Default for NginxSettings {
+    fn default() -> Self {
+        Self {
+            binary: "nginx".into(),
+            conf_path: None,
+            prefix: None,
+            probe_url: None,
+        }
+    }
+}
+
+impl Settings {
+    /// 设置文件路径
+    pub fn path() -> PathBuf {
+        crate::model::config_dir().join("settings.toml")
+    }
+
+    /// 读取设置；文件不存在或无法解析时返回默认设置
+    pub fn load() -> Self {
+        Self::load_from(&Self::path()).unwrap_or_else(|e| {
+            eprintln!("[Settings] Ignoring invalid settings: {}", e);
+            Self::default()
+        })
+    }
+
+    /// 读取设置；文件不存在时返回默认设置，无法读取或解析时返回错误
+    fn load_from(path: &Path) -> std::io::Result<Self> {
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(std::io::Error::new(e.kind(), format!("{}: {}", path.display(), e))),
+        };
+        toml::from_str(&text).map_err(|e| {
+            std::io::Error::new(std::io::ErrorKind::InvalidData, format!("{}: {}", path.display(), e.message()))
+        })
+    }
+
+    /// 写入设置（先写临时文件再重命名，避免写一半的文件）
+    pub fn save(&self) -> std::io::Result<()> {
+        self.save_to(&Self::path())
+    }
+
+    fn save_to(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let text = toml::to_string_pretty(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        let tmp = path.with_extension("toml.tmp");
+        std::fs::write(&tmp, text)?;
+        std::fs::rename(&tmp, path)
+    }
+
+    /// 读取 - 修改 - 保存
+    ///
+    /// 设置文件存在但无法解析时返回错误而不保存，以免用默认值覆盖用户的设置
+    pub fn update(change: impl FnOnce(&mut Settings)) -> std::io::Result<()> {
+        Self::update_at(&Self::path(), change)
+    }
+
+    fn update_at(path: &Path, change: impl FnOnce(&mut Settings)) -> std::io::Result<()> {
+        let mut settings = Self::load_from(path)?;
+        change(&mut settings);
+        settings.save_to(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn update_keeps_unparsable_settings() {
+        let dir = std::env::temp_dir().join(format!("easynginx-settings-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = dir.join("settings.toml");
+
+        // 文件不存在时从默认值开始
+        Settings::update_at(&path, |settings| settings.nginx.probe_url = Some("http://127.0.0.1/".into())).unwrap();
+        Settings::update_at(&path, |settings| settings.language = Some("de".into())).unwrap();
+        let saved = Settings::load_from(&path).unwrap();
+        assert_eq!(saved.language.as_deref(), Some("de"));
+        assert_eq!(saved.nginx.probe_url.as_deref(), Some("http://127.0.0.1/"));
+
+        // 无法解析的文件原样保留
+        let broken = "language = \"de\"\n[nginx\nbinary = \"/opt/nginx/sbin/nginx\"\n";
+        std::fs::write(&path, broken).unwrap();
+        assert!(Settings::update_at(&path, |settings| settings.language = Some("fr".into())).is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), broken);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
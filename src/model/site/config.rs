@@ -0,0 +1,295 @@
+// MIT License
+// 
+// Copyright (c) 2026 Laffinty
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+
+// ==============================================================================
+// Site config - 由站点生成 nginx server 块
+// ==============================================================================
+// 每个站点生成一个独立的 <name>.conf，由 nginx.conf 中的 include 引入。
+// 文件头部标记为 easyNginx 生成，手动修改会在下次保存站点时被覆盖。
+//
+// 配置用 nginx::parser 的语法树构造后输出，站点中的值由 Word::new 按需加引号，
+// 输出只取决于站点字段，相同的站点总是生成相同的文件（见 tests/corpus/site）。
+
+use crate::model::nginx::{Arg, Block, Body, Config, Directive, Node, Word};
+use super::model::{Site, SiteKind};
+
+const INDENT: &str = "    ";
+
+/// 按缩进层级逐行生成块的内容
+struct BlockWriter {
+    depth: usize,
+    nodes: Vec<Node>,
+}
+
+impl BlockWriter {
+    fn new(depth: usize) -> Self {
+        Self { depth, nodes: Vec::new() }
+    }
+
+    fn line(&mut self, node: Node) {
+        // 顶层的第一行前面没有换行
+        let newline = if self.depth == 0 && self.nodes.is_empty() { "" } else { "\n" };
+        self.nodes.push(Node::Whitespace(format!("{}{}", newline, INDENT.repeat(self.depth))));
+        self.nodes.push(node);
+    }
+
+    fn blank(&mut self) {
+        self.nodes.push(Node::Whitespace("\n".into()));
+    }
+
+    fn comment(&mut self, text: &str) {
+        self.line(Node::Comment(format!("# {}", text)));
+    }
+
+    /// 参数为值，必要时加引号
+    fn directive(&mut self, name: &str, args: &[&str]) {
+        self.line(Node::Directive(Directive::new(name, args)));
+    }
+
+    /// 参数原样输出（用于正则表达式等包含 '\' 的固定写法）
+    fn directive_raw(&mut self, name: &str, args: &[&str]) {
+        self.line(Node::Directive(raw_directive(name, args, Body::Semicolon)));
+    }
+
+    /// 块指令；参数原样输出
+    fn block(&mut self, name: &str, args: &[&str], build: impl FnOnce(&mut BlockWriter)) {
+        let mut inner = BlockWriter::new(self.depth + 1);
+        build(&mut inner);
+        let block = inner.finish_block();
+        self.line(Node::Directive(raw_directive(name, args, Body::Block(block))));
+    }
+
+    fn finish_block(mut self) -> Block {
+        self.nodes.push(Node::Whitespace(format!("\n{}", INDENT.repeat(self.depth.saturating_sub(1)))));
+        Block { nodes: self.nodes }
+    }
+
+    fn finish_config(mut self) -> Config {
+        self.nodes.push(Node::Whitespace("\n".into()));
+        Config { nodes: self.nodes }
+    }
+}
+
+fn raw_directive(name: &str, args: &[&str], body: Body) -> Directive {
+    let gap = if matches!(body, Body::Semicolon) { "" } else { " " };
+    Directive {
+        name: Word::new(name),
+        args: args.iter().map(|arg| Arg { gap: " ".into(), word: Word { raw: arg.to_string() } }).collect(),
+        gap: gap.into(),
+        body,
+        line: 0,
+    }
+}
+
+/// 生成站点的配置文件内容
+pub fn render(site: &Site) -> String {
+    let mut out = BlockWriter::new(0);
+    out.comment(&format!("Managed by easyNginx - site '{}'. Changes made here will be overwritten.", site.name));
+
+    if site.enable_https && site.enable_http_redirect {
+        out.block("server", &[], |server| {
+            write_listens(server, site, 80, false);
+            write_server_name(server, site);
+            server.blank();
+            let target = if site.port == 443 {
+                "https://$host$request_uri".to_string()
+            } else {
+                format!("https://$host:{}$request_uri", site.port)
+            };
+            server.directive("return", &["301", &target]);
+        });
+        out.blank();
+    }
+
+    out.block("server", &[], |server| {
+        write_listens(server, site, site.port, site.enable_https);
+        write_server_name(server, site);
+        server.blank();
+
+        if site.enable_https {
+            server.directive("ssl_certificate", &[&site.certificate]);
+            server.directive("ssl_certificate_key", &[&site.certificate_key]);
+            server.directive("ssl_protocols", &["TLSv1.2", "TLSv1.3"]);
+            server.directive("ssl_session_cache", &["shared:SSL:10m"]);
+            server.directive("ssl_session_timeout", &["1d"]);
+            server.blank();
+        }
+
+        write_kind(server, &site.kind);
+    });
+
+    out.finish_config().to_string()
+}
+
+/// listen 指令；启用 IPv6 时再监听 [::]
+fn write_listens(server: &mut BlockWriter, site: &Site, port: u16, ssl: bool) {
+    let port = port.to_string();
+    // 使用 listen 的 http2 参数而不是 1.25.1 起的 `http2 on;`，兼容旧版本 nginx
+    let mut flags: Vec<&str> = Vec::new();
+    if ssl {
+        flags.push("ssl");
+        if site.http2 {
+            flags.push("http2");
+        }
+    }
+    let ipv6 = format!("[::]:{}", port);
+    let addresses: &[&str] = if site.ipv6 { &[&port, &ipv6] } else { &[&port] };
+    for address in addresses {
+        let args: Vec<&str> = std::iter::once(*address).chain(flags.iter().copied()).collect();
+        server.directive("listen", &args);
+    }
+}
+
+fn write_server_name(server: &mut BlockWriter, site: &Site) {
+    let names: Vec<&str> = site.server_names().collect();
+    if !names.is_empty() {
+        server.directive("server_name", &names);
+    }
+}
+
+fn write_kind(server: &mut BlockWriter, kind: &SiteKind) {
+    match kind {
+        SiteKind::Static { root } => {
+            server.directive("root", &[root]);
+            server.directive("index", &["index.html", "index.htm"]);
+            server.blank();
+            server.block("location", &["/"], |location| {
+                location.directive("try_files", &["$uri", "$uri/", "=404"]);
+            });
+        }
+        SiteKind::Php { root, fpm_socket, index_files } => {
+            let index: Vec<&str> = index_files.iter().map(String::as_str).collect();
+            server.directive("root", &[root]);
+            server.directive("index", &index);
+            server.blank();
+            server.block("location", &["/"], |location| {
+                location.directive("try_files", &["$uri", "$uri/", "/index.php?$query_string"]);
+            });
+            server.blank();
+            server.block("location", &["~", r"\.php$"], |location| {
+                // 不存在的 .php 文件直接返回 404，不交给 PHP-FPM
+                location.directive("try_files", &["$uri", "=404"]);
+                location.directive_raw("fastcgi_split_path_info", &[r"^(.+\.php)(/.+)$"]);
+                location.directive("fastcgi_pass", &[fpm_socket]);
+                location.directive("fastcgi_index", &["index.php"]);
+                location.directive("include", &["fastcgi_params"]);
+                location.directive("fastcgi_param", &["SCRIPT_FILENAME", "$document_root$fastcgi_script_name"]);
+                location.directive("fastcgi_param", &["PATH_INFO", "$fastcgi_path_info"]);
+            });
+            server.blank();
+            server.block("location", &["~", r"/\.ht"], |location| {
+                location.directive("deny", &["all"]);
+            });
+        }
+        SiteKind::Proxy { upstream } => {
+            server.block("location", &["/"], |location| {
+                location.directive("proxy_pass", &[upstream]);
+                location.directive("proxy_http_version", &["1.1"]);
+                location.directive("proxy_set_header", &["Host", "$host"]);
+                location.directive("proxy_set_header", &["X-Real-IP", "$remote_addr"]);
+                location.directive("proxy_set_header", &["X-Forwarded-For", "$proxy_add_x_forwarded_for"]);
+                location.directive("proxy_set_header", &["X-Forwarded-Proto", "$scheme"]);
+                location.directive("proxy_set_header", &["X-Forwarded-Host", "$host"]);
+            });
+        }
+        SiteKind::Custom => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::nginx::ConfigFile;
+    use crate::model::site::discover::sites_from_files;
+    use crate::model::site::SiteType;
+    use std::path::{Path, PathBuf};
+
+    fn sites() -> Vec<Site> {
+        let mut static_http = Site::new("static-http", SiteType::Static);
+        static_http.domain = "static.example.com".into();
+
+        let mut static_https = Site::new("static-https-ipv6", SiteType::Static);
+        static_https.domain = "docs.example.com www.docs.example.com".into();
+        static_https.port = 443;
+        static_https.ipv6 = true;
+        static_https.enable_https = true;
+        static_https.certificate = "/etc/ssl/certs/docs.pem".into();
+        static_https.certificate_key = "/etc/ssl/private/docs.key".into();
+        static_https.kind = SiteKind::Static { root: "/srv/My Docs".into() };
+
+        let mut php = Site::new("php-redirect-http2", SiteType::Php);
+        php.domain = "blog.example.org".into();
+        php.port = 443;
+        php.ipv6 = true;
+        php.http2 = true;
+        php.enable_https = true;
+        php.enable_http_redirect = true;
+        php.certificate = "/etc/letsencrypt/live/blog.example.org/fullchain.pem".into();
+        php.certificate_key = "/etc/letsencrypt/live/blog.example.org/privkey.pem".into();
+
+        let mut proxy = Site::new("proxy-custom-port", SiteType::Proxy);
+        proxy.domain = "app.example.com".into();
+        proxy.port = 8443;
+        proxy.enable_https = true;
+        proxy.enable_http_redirect = true;
+        proxy.certificate = "/etc/ssl/certs/app.pem".into();
+        proxy.certificate_key = "/etc/ssl/private/app.key".into();
+        proxy.kind = SiteKind::Proxy { upstream: "http://127.0.0.1:3000".into() };
+
+        vec![static_http, static_https, php, proxy]
+    }
+
+    /// 与 tests/corpus/site/<name>.conf 比较；设置 UPDATE_GOLDEN=1 时改为写入
+    #[test]
+    fn matches_golden_files() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/corpus/site");
+        for site in sites() {
+            let path = dir.join(format!("{}.conf", site.name));
+            let rendered = render(&site);
+            assert_eq!(rendered, render(&site), "{} is not deterministic", site.name);
+            if std::env::var_os("UPDATE_GOLDEN").is_some() {
+                std::fs::write(&path, &rendered).unwrap();
+                continue;
+            }
+            let expected = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+            assert_eq!(rendered, expected, "{} differs from {}", site.name, path.display());
+        }
+    }
+
+    /// 生成的配置能被解析，并被 discover 识别为同样的站点
+    #[test]
+    fn discovers_rendered_sites() {
+        for site in sites() {
+            let config = Config::parse(&render(&site)).unwrap();
+            let file = ConfigFile { path: PathBuf::from(format!("/etc/nginx/sites-enabled/{}.conf", site.name)), config };
+            let found = sites_from_files(&[file], Path::new("/nonexistent"), &[]);
+            assert_eq!(found.len(), 1, "{}", site.name);
+            let found = &found[0];
+            assert_eq!(found.name, site.name);
+            assert_eq!(found.kind, site.kind);
+            assert_eq!(found.domain, site.domain);
+            assert_eq!((found.port, found.enable_https, found.enable_http_redirect), (site.port, site.enable_https, site.enable_http_redirect));
+            assert_eq!((found.ipv6, found.http2), (site.ipv6, site.http2));
+        }
+    }
+}
@@ -0,0 +1,406 @@
+// MIT License
+// 
+// Copyright (c) 2026 Laffinty
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+
+// ==============================================================================
+// discover - 从 nginx 配置中发现站点
+// ==============================================================================
+// 读取主配置文件及其 include 的文件（sites-enabled、conf.d 等），把每个 http
+// server 块转换为一个只读的 Site：
+//   名称      配置文件名（同一文件中有多个 server 块时加 -2、-3 …）
+//   端口      listen 指令（包括 ssl、http2、default_server 标志）
+//   类型      fastcgi_pass -> PHP，proxy_pass -> 代理，root -> 静态，否则为 Custom
+// 只把 HTTP 重定向到 HTTPS 的 server 块合并到对应的 HTTPS 站点（enable_http_redirect）。
+// easyNginx 生成的配置（sites-available）和接管时导入的文件不在这里读取，它们由 sites.toml 管理。
+
+use std::path::{Path, PathBuf};
+use crate::model::nginx::{self, ConfigFile, Directive};
+use crate::model::settings::NginxSettings;
+use super::model::{Listen, Site, SiteKind, SiteOrigin};
+
+/// 未指定 conf_path 时依次查找的主配置文件
+const DEFAULT_CONF_PATHS: [&str; 4] = [
+    "/etc/nginx/nginx.conf",
+    "/usr/local/nginx/conf/nginx.conf",
+    "/usr/local/etc/nginx/nginx.conf",
+    "/opt/homebrew/etc/nginx/nginx.conf",
+];
+
+/// 发现结果
+#[derive(Clone, Debug, Default)]
+pub struct Discovery {
+    pub sites: Vec<Site>,
+    /// 读取或解析配置失败的原因
+    pub errors: Vec<String>,
+}
+
+/// 主配置文件路径；未配置且默认位置都不存在时返回 None
+pub fn main_conf_path(settings: &NginxSettings) -> Option<PathBuf> {
+    match &settings.conf_path {
+        Some(path) => Some(PathBuf::from(path)),
+        None => DEFAULT_CONF_PATHS.iter().map(PathBuf::from).find(|path| path.is_file()),
+    }
+}
+
+/// 读取 nginx 配置并发现站点；taken 为已被管理站点使用的名称，imported 为已导入的文件
+pub fn discover(settings: &NginxSettings, taken: &[String], imported: &[PathBuf]) -> Discovery {
+    let Some(main) = main_conf_path(settings) else {
+        return Discovery::default();
+    };
+    match nginx::load_tree(&main) {
+        Ok(mut files) => Discovery {
+            sites: {
+                files.retain(|file| !imported.contains(&file.path));
+                sites_from_files(&files, &super::available_dir(), taken)
+            },
+            errors: Vec::new(),
+        },
+        Err(e) => Discovery {
+            sites: Vec::new(),
+            errors: vec![e.to_string()],
+        },
+    }
+}
+
+/// 从已解析的文件中提取站点；跳过 managed_dir 下的文件
+pub fn sites_from_files(files: &[ConfigFile], managed_dir: &Path, taken: &[String]) -> Vec<Site> {
+    let mut sites: Vec<Site> = Vec::new();
+    let mut redirects: Vec<Site> = Vec::new();
+
+    for file in files.iter().filter(|file| !file.path.starts_with(managed_dir)) {
+        let mut servers = Vec::new();
+        for directive in file.config.directives() {
+            collect_servers(directive, &mut servers);
+        }
+        let stem = file_stem(&file.path);
+        for server in servers {
+            let (site, is_redirect) = site_from_server(&stem, &file.path, server);
+            if is_redirect {
+                redirects.push(site);
+            } else {
+                sites.push(site);
+            }
+        }
+    }
+
+    // 把重定向块合并到使用相同域名的 HTTPS 站点；找不到时作为 Custom 站点显示
+    for redirect in redirects {
+        let target = sites.iter_mut().find(|site| {
+            site.enable_https && site.server_names().any(|name| redirect.server_names().any(|other| other == name))
+        });
+        match target {
+            Some(site) => {
+                site.enable_http_redirect = true;
+                if let (SiteOrigin::Discovered { listens, .. }, SiteOrigin::Discovered { listens: extra, .. }) = (&mut site.origin, redirect.origin) {
+                    listens.extend(extra);
+                }
+            }
+            None => sites.push(redirect),
+        }
+    }
+
+    // 合并后再命名，被合并的重定向块不占用名称
+    let mut names: Vec<String> = taken.to_vec();
+    for site in &mut sites {
+        site.name = unique_name(&site.name, &names);
+        names.push(site.name.clone());
+    }
+    sites
+}
+
+/// http 上下文中的 server 块（包括 http 块里的）；stream、mail 中的 server 不是网站
+fn collect_servers<'a>(directive: &'a Directive, servers: &mut Vec<&'a Directive>) {
+    match directive.name().as_str() {
+        "server" if directive.block().is_some() => servers.push(directive),
+        "http" => {
+            if let Some(block) = directive.block() {
+                for child in block.directives() {
+                    collect_servers(child, servers);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn file_stem(path: &Path) -> String {
+    let stem = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("conf") => path.file_stem(),
+        _ => path.file_name(),
+    };
+    stem.map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_else(|| "site".into())
+}
+
+fn unique_name(stem: &str, taken: &[String]) -> String {
+    if !taken.iter().any(|name| name == stem) {
+        return stem.to_string();
+    }
+    (2..)
+        .map(|n| format!("{}-{}", stem, n))
+        .find(|name| !taken.contains(name))
+        .unwrap_or_default()
+}
+
+/// 把一个 server 块转换为站点；第二个值表示它是否只把 HTTP 重定向到 HTTPS
+fn site_from_server(name: &str, path: &Path, server: &Directive) -> (Site, bool) {
+    let block = server.block().expect("server block");
+    let first_arg = |name: &str| block.find(name).and_then(|directive| directive.args().into_iter().next());
+
+    let mut listens: Vec<Listen> = block.directives()
+        .filter(|directive| directive.name() == "listen")
+        .filter_map(|directive| parse_listen(&directive.args()))
+        .collect();
+    if listens.is_empty() {
+        listens.push(Listen { address: None, port: 80, ssl: false, http2: false, default_server: false });
+    }
+    // 旧版本的 `ssl on;` 和 1.25 起的 `http2 on;` 作用于所有 listen
+    let enabled = |name: &str| first_arg(name).is_some_and(|value| value == "on");
+    let (ssl_on, http2_on) = (enabled("ssl"), enabled("http2"));
+    for listen in &mut listens {
+        listen.ssl |= ssl_on;
+        listen.http2 |= http2_on;
+    }
+
+    let enable_https = listens.iter().any(|listen| listen.ssl);
+    let port = listens.iter()
+        .find(|listen| listen.ssl == enable_https)
+        .map(|listen| listen.port)
+        .unwrap_or(80);
+    let domain = block.directives()
+        .filter(|directive| directive.name() == "server_name")
+        .flat_map(|directive| directive.args())
+        .filter(|name| !name.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let redirect_here = block.directives().any(redirects_to_https);
+    let is_redirect = !enable_https && redirect_here;
+    // 同时监听 HTTP 和 HTTPS、用 if 判断协议后重定向的写法
+    let redirect_in_if = enable_https && block.directives()
+        .filter(|directive| directive.name() == "if")
+        .filter_map(Directive::block)
+        .any(|inner| inner.directives().any(redirects_to_https));
+
+    let kind = if is_redirect { SiteKind::Custom } else { infer_kind(server) };
+    let site = Site {
+        name: name.to_string(),
+        port,
+        domain,
+        enable_https,
+        enable_http_redirect: redirect_in_if,
+        ipv6: listens.iter().any(|listen| listen.address.as_deref().is_some_and(|address| address.starts_with('['))),
+        http2: listens.iter().any(|listen| listen.http2),
+        certificate: first_arg("ssl_certificate").unwrap_or_default(),
+        certificate_key: first_arg("ssl_certificate_key").unwrap_or_default(),
+        enabled: true,
+        imported_from: None,
+        kind,
+        origin: SiteOrigin::Discovered {
+            path: path.to_path_buf(),
+            line: server.line,
+            listens,
+        },
+    };
+    (site, is_redirect)
+}
+
+/// server 块是否只把 HTTP 重定向到 HTTPS（发现时会合并到对应的站点）
+pub(super) fn is_redirect_server(server: &Directive) -> bool {
+    site_from_server("", Path::new(""), server).1
+}
+
+/// `return 301 https://…` 或 `rewrite ^ https://… permanent`
+fn redirects_to_https(directive: &Directive) -> bool {
+    let args = directive.args();
+    match (directive.name().as_str(), args.as_slice()) {
+        ("return", [code, url]) => matches!(code.as_str(), "301" | "302" | "307" | "308") && url.starts_with("https://"),
+        ("rewrite", [_, url, flag]) => url.starts_with("https://") && matches!(flag.as_str(), "permanent" | "redirect"),
+        _ => false,
+    }
+}
+
+/// 按 fastcgi_pass > proxy_pass > root 的顺序推断类型（包括 location 中的指令）
+fn infer_kind(server: &Directive) -> SiteKind {
+    let mut fastcgi_pass = None;
+    let mut proxy_pass = None;
+    let mut root = None;
+    let mut index_files = None;
+    walk_block(server, &mut |directive| {
+        let slot = match directive.name().as_str() {
+            "fastcgi_pass" => &mut fastcgi_pass,
+            "proxy_pass" => &mut proxy_pass,
+            "root" => &mut root,
+            "index" => {
+                if index_files.is_none() {
+                    index_files = Some(directive.args());
+                }
+                return;
+            }
+            _ => return,
+        };
+        if slot.is_none() {
+            *slot = directive.args().into_iter().next();
+        }
+    });
+    // server 级的 root 优先于 location 中的
+    if let Some(server_root) = server.block().and_then(|block| block.find("root")).and_then(|d| d.args().into_iter().next()) {
+        root = Some(server_root);
+    }
+
+    if let Some(fpm_socket) = fastcgi_pass {
+        SiteKind::Php {
+            root: root.unwrap_or_default(),
+            fpm_socket,
+            index_files: index_files.unwrap_or_default(),
+        }
+    } else if let Some(upstream) = proxy_pass {
+        SiteKind::Proxy { upstream }
+    } else if let Some(root) = root {
+        SiteKind::Static { root }
+    } else {
+        SiteKind::Custom
+    }
+}
+
+fn walk_block<'a>(directive: &'a Directive, visit: &mut dyn FnMut(&'a Directive)) {
+    if let Some(block) = directive.block() {
+        for child in block.directives() {
+            visit(child);
+            walk_block(child, visit);
+        }
+    }
+}
+
+/// 解析 listen 的参数；unix 套接字返回 None
+pub fn parse_listen(args: &[String]) -> Option<Listen> {
+    let (first, flags) = args.split_first()?;
+    if first.starts_with("unix:") {
+        return None;
+    }
+    let (address, port) = if let Some(rest) = first.strip_prefix('[') {
+        // [::]:80 或 [::1]
+        let (host, rest) = rest.split_once(']')?;
+        let port = match rest.strip_prefix(':') {
+            Some(port) => port.parse().ok()?,
+            None => 80,
+        };
+        (Some(format!("[{}]", host)), port)
+    } else if let Ok(port) = first.parse::<u16>() {
+        (None, port)
+    } else if let Some((host, port)) = first.rsplit_once(':') {
+        (Some(host.to_string()), port.parse().ok()?)
+    } else {
+        // 只有地址时端口为 80
+        (Some(first.clone()), 80)
+    };
+    let has = |flag: &str| flags.iter().any(|arg| arg == flag);
+    Some(Listen {
+        address: address.filter(|address| address != "*"),
+        port,
+        ssl: has("ssl"),
+        http2: has("http2"),
+        default_server: has("default_server") || has("default"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::site::SiteType;
+
+    fn corpus(name: &str) -> Vec<Site> {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/corpus/nginx").join(name);
+        let file = nginx::load_file(&path).unwrap();
+        sites_from_files(&[file], Path::new("/nonexistent"), &[])
+    }
+
+    fn listens(site: &Site) -> &[Listen] {
+        match &site.origin {
+            SiteOrigin::Discovered { listens, .. } => listens,
+            SiteOrigin::Managed => panic!("{} is not discovered", site.name),
+        }
+    }
+
+    #[test]
+    fn merges_https_redirect_into_php_site() {
+        let sites = corpus("wordpress.conf");
+        assert_eq!(sites.len(), 1);
+        let site = &sites[0];
+        assert_eq!(site.name, "wordpress");
+        assert_eq!(site.domain, "blog.example.org www.blog.example.org");
+        assert_eq!((site.port, site.enable_https, site.enable_http_redirect), (443, true, true));
+        assert_eq!(site.certificate, "/etc/letsencrypt/live/blog.example.org/fullchain.pem");
+        assert_eq!(site.kind, SiteKind::Php {
+            root: "/var/www/wordpress".into(),
+            fpm_socket: "php".into(),
+            index_files: vec!["index.php".into()],
+        });
+        assert!(listens(site)[0].http2);
+        assert_eq!(site.listen_ports(), vec![443, 80]);
+        assert_eq!(site.port_label(), "443/80(redirect)");
+    }
+
+    #[test]
+    fn infers_proxy_and_static_sites() {
+        let proxy = corpus("reverse-proxy.conf");
+        assert_eq!(proxy.len(), 1);
+        assert_eq!(proxy[0].kind, SiteKind::Proxy { upstream: "http://app_backend".into() });
+        assert_eq!((proxy[0].port, proxy[0].enable_https), (443, true));
+
+        let debian = corpus("debian-default");
+        assert_eq!(debian.len(), 1);
+        assert_eq!(debian[0].name, "debian-default");
+        assert_eq!(debian[0].kind, SiteKind::Static { root: "/var/www/html".into() });
+        assert!(listens(&debian[0]).iter().all(|listen| listen.port == 80 && listen.default_server));
+        assert_eq!(debian[0].port_label(), "80");
+
+        // server 块在 http 块中，root 只出现在 location 里
+        let main = corpus("nginx.conf");
+        assert_eq!(main.len(), 1);
+        assert_eq!(main[0].kind, SiteKind::Static { root: "html".into() });
+    }
+
+    #[test]
+    fn unclassified_blocks_are_custom_with_unique_names() {
+        let config = nginx::Config::parse(
+            "server { listen 80 default_server; return 444; }\n\
+             server { listen 8080; server_name old.example; return 301 https://new.example$request_uri; }\n",
+        ).unwrap();
+        let file = ConfigFile { path: PathBuf::from("/etc/nginx/conf.d/misc.conf"), config };
+        let sites = sites_from_files(&[file], Path::new("/nonexistent"), &["misc".to_string()]);
+        let names: Vec<&str> = sites.iter().map(|site| site.name.as_str()).collect();
+        assert_eq!(names, ["misc-2", "misc-3"]);
+        assert!(sites.iter().all(|site| site.site_type() == SiteType::Custom && !site.is_managed()));
+    }
+
+    #[test]
+    fn parses_listen_forms() {
+        let parse = |text: &str| parse_listen(&text.split(' ').map(String::from).collect::<Vec<_>>());
+        let listen = parse("[::]:443 ssl http2").unwrap();
+        assert_eq!((listen.address.as_deref(), listen.port, listen.ssl, listen.http2), (Some("[::]"), 443, true, true));
+        let listen = parse("127.0.0.1:8081").unwrap();
+        assert_eq!((listen.address.as_deref(), listen.port), (Some("127.0.0.1"), 8081));
+        assert_eq!(parse("*:80 default").unwrap().address, None);
+        assert_eq!(parse("localhost").unwrap().port, 80);
+        assert!(parse("unix:/run/nginx.sock").is_none());
+    }
+}
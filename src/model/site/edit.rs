@@ -0,0 +1,233 @@
+// MIT License
+// 
+// Copyright (c) 2026 Laffinty
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// ==============================================================================
+// edit - 在用户原来的文件中修改导入的站点
+// ==============================================================================
+// 接管时导入的站点（imported_from）使用用户手写的文件，文件里可能还有 upstream
+// 块、额外的 location、expires 规则等站点模型之外的内容。保存这类站点时不重新
+// 生成文件，而是在无损语法树上只修改模型对应的指令：listen 的端口、server_name、
+// 证书路径、root、index、fastcgi_pass 和 proxy_pass，其它内容逐字节保留。
+// HTTPS、重定向、IPv6、HTTP/2 和类型的改动涉及整个块的结构，只能手动修改文件。
+
+use crate::model::nginx::{Config, Directive, Word};
+use super::discover;
+use super::model::{Site, SiteKind};
+
+/// 把 old 到 site 的修改写入文件内容 source
+pub fn update_imported(source: &str, old: &Site, site: &Site) -> Result<String, String> {
+    if old.site_type() != site.site_type()
+        || (old.enable_https, old.enable_http_redirect, old.ipv6, old.http2) != (site.enable_https, site.enable_http_redirect, site.ipv6, site.http2)
+    {
+        return Err("HTTPS, redirect, IPv6, HTTP/2 and the site type of an imported site can only be changed in its file".into());
+    }
+    let mut config = Config::parse(source).map_err(|e| e.to_string())?;
+    let mut servers = Vec::new();
+    for directive in config.directives_mut() {
+        collect_servers(directive, &mut servers);
+    }
+    // 站点所在的 server 块和合并进来的重定向块
+    let (main, mut redirects): (Vec<_>, Vec<_>) = servers.into_iter().partition(|server| !discover::is_redirect_server(server));
+    let Ok([main]) = <[&mut Directive; 1]>::try_from(main) else {
+        return Err("the file no longer contains exactly one site".into());
+    };
+
+    if old.port != site.port {
+        let mut changed = 0;
+        for listen in server_directives(main, "listen") {
+            let args = listen.args();
+            if discover::parse_listen(&args).is_some_and(|parsed| parsed.port == old.port) {
+                set_first_arg(listen, &with_port(&args[0], site.port));
+                changed += 1;
+            }
+        }
+        if changed == 0 {
+            return Err(format!("no listen directive uses port {}", old.port));
+        }
+    }
+    if old.domain != site.domain {
+        let names: Vec<&str> = site.server_names().collect();
+        set_server_names(main, &names)?;
+        for redirect in &mut redirects {
+            set_server_names(redirect, &names)?;
+        }
+    }
+    if old.certificate != site.certificate {
+        replace_server_arg(main, "ssl_certificate", &site.certificate)?;
+    }
+    if old.certificate_key != site.certificate_key {
+        replace_server_arg(main, "ssl_certificate_key", &site.certificate_key)?;
+    }
+
+    match (&old.kind, &site.kind) {
+        (SiteKind::Static { root: old_root }, SiteKind::Static { root }) => replace_all(main, "root", old_root, root)?,
+        (SiteKind::Php { root: old_root, fpm_socket: old_socket, index_files: old_index }, SiteKind::Php { root, fpm_socket, index_files }) => {
+            replace_all(main, "root", old_root, root)?;
+            replace_all(main, "fastcgi_pass", old_socket, fpm_socket)?;
+            if old_index != index_files {
+                // 与发现时一样，站点的 index 为第一个 index 指令
+                let mut first = None;
+                walk_mut(main, &mut |directive| {
+                    if first.is_none() && directive.name() == "index" {
+                        let values: Vec<&str> = index_files.iter().map(String::as_str).collect();
+                        directive.set_args(&values);
+                        first = Some(());
+                    }
+                });
+                first.ok_or("the file has no index directive")?;
+            }
+        }
+        (SiteKind::Proxy { upstream: old_upstream }, SiteKind::Proxy { upstream }) => replace_all(main, "proxy_pass", old_upstream, upstream)?,
+        _ => {}
+    }
+    Ok(config.to_string())
+}
+
+/// http 上下文中的 server 块（与 discover 相同）
+fn collect_servers<'a>(directive: &'a mut Directive, servers: &mut Vec<&'a mut Directive>) {
+    match directive.name().as_str() {
+        "server" if directive.block().is_some() => servers.push(directive),
+        "http" => {
+            if let Some(block) = directive.block_mut() {
+                for child in block.directives_mut() {
+                    collect_servers(child, servers);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// server 块中直接出现的指定指令
+fn server_directives<'a>(server: &'a mut Directive, name: &'a str) -> impl Iterator<Item = &'a mut Directive> {
+    server.block_mut().into_iter().flat_map(|block| block.directives_mut()).filter(move |directive| directive.name() == name)
+}
+
+fn walk_mut(directive: &mut Directive, visit: &mut dyn FnMut(&mut Directive)) {
+    if let Some(block) = directive.block_mut() {
+        for child in block.directives_mut() {
+            visit(child);
+            walk_mut(child, visit);
+        }
+    }
+}
+
+/// 只替换第一个参数，其它参数保持原文
+fn set_first_arg(directive: &mut Directive, value: &str) {
+    if let Some(arg) = directive.args.first_mut() {
+        arg.word = Word::new(value);
+    }
+}
+
+/// listen 的第一个参数改用新端口，保留地址
+fn with_port(address: &str, port: u16) -> String {
+    if address.parse::<u16>().is_ok() {
+        port.to_string()
+    } else if let Some((host, _)) = address.rsplit_once(']') {
+        format!("{}]:{}", host, port)
+    } else if let Some((host, _)) = address.rsplit_once(':') {
+        format!("{}:{}", host, port)
+    } else {
+        format!("{}:{}", address, port)
+    }
+}
+
+fn set_server_names(server: &mut Directive, names: &[&str]) -> Result<(), String> {
+    let mut directives: Vec<&mut Directive> = server_directives(server, "server_name").collect();
+    match directives.as_mut_slice() {
+        [directive] => {
+            directive.set_args(names);
+            Ok(())
+        }
+        [] => Err("the server block has no server_name directive".into()),
+        _ => Err("the server block has several server_name directives".into()),
+    }
+}
+
+fn replace_server_arg(server: &mut Directive, name: &str, value: &str) -> Result<(), String> {
+    let directive = server_directives(server, name).next().ok_or_else(|| format!("the file has no {} directive", name))?;
+    set_first_arg(directive, value);
+    Ok(())
+}
+
+/// 把所有值为 old 的指令（包括 location 中的）改为 value
+fn replace_all(server: &mut Directive, name: &str, old: &str, value: &str) -> Result<(), String> {
+    if old == value {
+        return Ok(());
+    }
+    let mut changed = 0;
+    walk_mut(server, &mut |directive| {
+        if directive.name() == name && directive.args().first().is_some_and(|arg| arg == old) {
+            set_first_arg(directive, value);
+            changed += 1;
+        }
+    });
+    if changed == 0 {
+        return Err(format!("no {} directive is set to {}", name, old));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::nginx::ConfigFile;
+    use std::path::{Path, PathBuf};
+
+    const SOURCE: &str = "# docs\nserver {\n    listen 8080;   # public\n    listen [::]:8080;\n    server_name docs.example.com;\n    root \"/srv/docs\";\n    location /assets { expires 7d; }\n}\n";
+
+    fn imported(source: &str) -> Site {
+        let file = ConfigFile { path: PathBuf::from("/etc/nginx/conf.d/docs.conf"), config: Config::parse(source).unwrap() };
+        discover::sites_from_files(&[file], Path::new("/nonexistent"), &[]).remove(0)
+    }
+
+    #[test]
+    fn changes_only_model_directives() {
+        let old = imported(SOURCE);
+        let mut site = old.clone();
+        site.port = 9000;
+        site.kind = SiteKind::Static { root: "/srv/My Docs".into() };
+        let updated = update_imported(SOURCE, &old, &site).unwrap();
+        assert_eq!(updated, SOURCE
+            .replace("listen 8080;", "listen 9000;")
+            .replace("[::]:8080", "[::]:9000")
+            .replace("root \"/srv/docs\"", "root \"/srv/My Docs\""));
+        assert_eq!(imported(&updated).port, 9000);
+
+        assert_eq!(with_port("127.0.0.1:80", 81), "127.0.0.1:81");
+        assert_eq!(with_port("localhost", 81), "localhost:81");
+    }
+
+    #[test]
+    fn refuses_changes_it_cannot_make() {
+        let old = imported(SOURCE);
+        let mut site = old.clone();
+        site.enable_https = true;
+        assert!(update_imported(SOURCE, &old, &site).is_err());
+
+        // 文件已被手动修改，找不到原来的值
+        let mut site = old.clone();
+        site.kind = SiteKind::Static { root: "/srv/new".into() };
+        let edited = SOURCE.replace("/srv/docs", "/srv/elsewhere");
+        assert!(update_imported(&edited, &old, &site).is_err());
+    }
+}
@@ -0,0 +1,729 @@
+// MIT License
+// 
+// Copyright (c) 2026 Laffinty
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+
+// ==============================================================================
+// Site Module - 站点管理
+// ==============================================================================
+// 站点列表保存在 <config_dir>/sites.toml，每个站点的 nginx 配置生成到
+// <config_dir>/nginx/sites-available/<name>.conf，已启用的站点列在
+// <config_dir>/nginx/sites-enabled.conf 中（在 nginx.conf 的 http 块里 include 它）。
+// 其它模块通过总线修改站点：
+//   SiteChangeRequest -> 校验 -> 一次事务写入配置和列表 -> SiteChangeResponse
+// 请求重载时事务先用 nginx -t 测试修改后的配置，重载后确认 nginx 仍在服务，
+// 否则恢复原来的文件（见 transaction）。每次写入文件之前自动备份（见 backup）。
+// 站点列表变化后广播 SitesUpdated；SiteListRequest 可随时获取当前列表。
+// 列表中还包括从 nginx 配置中发现的只读站点（见 discover），
+// 启动时及收到 SiteRefreshRequest 时重新读取。
+// 接管 nginx 时导入的站点（imported_from）仍使用原来的文件，由 nginx.conf 原有的
+// include 加载，不列入 sites-enabled.conf；保存时只修改文件中对应的指令（见 edit）。
+// 停用站点只是把它从 sites-enabled.conf 中去掉，配置文件保留；删除站点时可以先把
+// 配置文件和站点设置归档到 <config_dir>/archive/<name>-<时间>/；导入的站点删除的是
+// 用户原来的文件，因此总是先归档。
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::any::{Any, TypeId};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use crate::{MessageEnvelope, MessageBus, Module, module_init};
+use crate::model::transaction::Transaction;
+
+pub mod model;
+pub mod config;
+
+pub mod discover;
+pub mod edit;
+
+pub use model::{Listen, Site, SiteConflict, SiteKind, SiteOrigin, SiteType, SiteField, ValidationError, parse_port};
+pub use crate::model::transaction::ReloadOutcome;
+
+/// 对站点的修改
+#[derive(Clone, Debug)]
+pub enum SiteChange {
+    /// 保存站点；original_name 为修改前的名称（新建时为 None），改名时删除旧配置
+    Save {
+        original_name: Option<String>,
+        site: Box<Site>,
+    },
+    /// 启用或停用站点（写入或移出 sites-enabled.conf）
+    SetEnabled {
+        name: String,
+        enabled: bool,
+    },
+    /// 删除站点；archive 为 true 时先归档配置文件和站点设置（导入的站点总是归档）
+    Delete {
+        name: String,
+        archive: bool,
+    },
+}
+
+#[derive(Clone, Debug)]
+pub struct SiteChangeRequest {
+    /// 请求方生成的编号，原样返回在响应中
+    pub id: u64,
+    pub change: SiteChange,
+    pub requester: String,
+    /// 测试修改后的配置，写入后重载 nginx
+    pub reload: bool,
+}
+
+/// 生成进程内唯一的请求编号
+pub fn next_request_id() -> u64 {
+    static NEXT_ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(1);
+    NEXT_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+}
+
+impl SiteChangeRequest {
+    pub fn new(id: u64, change: SiteChange, requester: &str) -> Self {
+        Self {
+            id,
+            change,
+            requester: requester.to_string(),
+            reload: false,
+        }
+    }
+    
+    pub fn with_reload(mut self) -> Self {
+        self.reload = true;
+        self
+    }
+}
+
+impl crate::Message for SiteChangeRequest {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn message_type(&self) -> TypeId {
+        TypeId::of::<SiteChangeRequest>()
+    }
+    
+    fn clone_box(&self) -> Box<dyn crate::Message> {
+        Box::new(self.clone())
+    }
+}
+
+/// 修改结果
+#[derive(Clone, Debug)]
+pub struct SiteChangeResponse {
+    pub id: u64,
+    pub requester: String,
+    /// 校验未通过的字段
+    pub validation_errors: Vec<ValidationError>,
+    /// 写入配置或保存列表失败时的错误
+    pub error: Option<String>,
+    /// 请求了重载时的结果；TestFailed 和 RolledBack 表示修改没有生效
+    pub reload: Option<ReloadOutcome>,
+}
+
+impl SiteChangeResponse {
+    pub fn is_ok(&self) -> bool {
+        self.validation_errors.is_empty() && self.error.is_none()
+    }
+}
+
+impl crate::Message for SiteChangeResponse {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn message_type(&self) -> TypeId {
+        TypeId::of::<SiteChangeResponse>()
+    }
+    
+    fn clone_box(&self) -> Box<dyn crate::Message> {
+        Box::new(self.clone())
+    }
+}
+
+/// 请求广播当前站点列表
+#[derive(Clone, Debug, Default)]
+pub struct SiteListRequest;
+
+impl crate::Message for SiteListRequest {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn message_type(&self) -> TypeId {
+        TypeId::of::<SiteListRequest>()
+    }
+    
+    fn clone_box(&self) -> Box<dyn crate::Message> {
+        Box::new(self.clone())
+    }
+}
+
+/// 重新读取站点列表和 nginx 配置中的站点，完成后广播 SitesUpdated
+#[derive(Clone, Debug, Default)]
+pub struct SiteRefreshRequest;
+
+impl crate::Message for SiteRefreshRequest {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn message_type(&self) -> TypeId {
+        TypeId::of::<SiteRefreshRequest>()
+    }
+    
+    fn clone_box(&self) -> Box<dyn crate::Message> {
+        Box::new(self.clone())
+    }
+}
+
+/// 站点列表（变化时及收到 SiteListRequest 时广播）
+#[derive(Clone, Debug)]
+pub struct SitesUpdated {
+    /// 管理的站点在前，发现的站点在后
+    pub sites: Vec<Site>,
+    /// 读取站点列表或 nginx 配置失败的原因
+    pub errors: Vec<String>,
+}
+
+impl crate::Message for SitesUpdated {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn message_type(&self) -> TypeId {
+        TypeId::of::<SitesUpdated>()
+    }
+    
+    fn clone_box(&self) -> Box<dyn crate::Message> {
+        Box::new(self.clone())
+    }
+}
+
+// ==============================================================================
+// Storage
+// ==============================================================================
+
+#[derive(Default, Serialize, Deserialize)]
+struct SiteStore {
+    #[serde(default)]
+    sites: Vec<Site>,
+}
+
+/// 站点列表文件
+pub fn store_path() -> PathBuf {
+    crate::model::config_dir().join("sites.toml")
+}
+
+/// 生成的站点配置所在目录
+pub fn available_dir() -> PathBuf {
+    crate::model::config_dir().join("nginx").join("sites-available")
+}
+
+/// 列出已启用站点的 include 文件
+pub fn enabled_include_path() -> PathBuf {
+    crate::model::config_dir().join("nginx").join("sites-enabled.conf")
+}
+
+/// 站点配置文件路径
+pub fn site_conf_path(name: &str) -> PathBuf {
+    available_dir().join(format!("{}.conf", name))
+}
+
+/// 删除站点时归档的目录
+pub fn archive_dir() -> PathBuf {
+    crate::model::config_dir().join("archive")
+}
+
+/// 站点实际使用的配置文件（导入的站点为原来的文件）
+pub fn conf_path_of(site: &Site) -> PathBuf {
+    site.imported_from.clone().unwrap_or_else(|| site_conf_path(&site.name))
+}
+
+pub(crate) fn enabled_include(sites: &[Site]) -> String {
+    let mut text = String::from("# Managed by easyNginx - include this file from the http block of nginx.conf.\n");
+    for site in sites.iter().filter(|site| site.enabled && site.imported_from.is_none()) {
+        text.push_str(&format!("include \"{}\";\n", site_conf_path(&site.name).display()));
+    }
+    text
+}
+
+/// 读取站点列表；文件不存在时为空列表
+///
+/// 文件无法读取或解析时返回错误，调用方不能再保存列表，以免覆盖用户的文件。
+pub(crate) fn load_sites() -> Result<Vec<Site>, String> {
+    let path = store_path();
+    let text = match std::fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("{}: {}", path.display(), e)),
+    };
+    parse_sites(&text).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn parse_sites(text: &str) -> Result<Vec<Site>, String> {
+    toml::from_str::<SiteStore>(text)
+        .map(|store| store.sites)
+        .map_err(|e| e.message().to_string())
+}
+
+/// 把站点的配置文件和设置加入事务，写入 archive_dir() 下的新目录
+fn archive_site(site: &Site, transaction: &mut Transaction) -> Result<PathBuf, String> {
+    let stamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let dir = archive_dir().join(format!("{}-{}", site.name, stamp));
+    let path = conf_path_of(site);
+    // 配置文件不存在时（例如从未写入过）按当前设置生成
+    let conf = std::fs::read(&path).unwrap_or_else(|_| config::render(site).into_bytes());
+    let file_name = path.file_name().map(|name| name.to_os_string()).unwrap_or_else(|| format!("{}.conf", site.name).into());
+    transaction.write(dir.join(file_name), conf);
+    let text = sites_toml(std::slice::from_ref(site)).map_err(|e| e.to_string())?;
+    transaction.write(dir.join("sites.toml"), text);
+    Ok(dir)
+}
+
+/// 修改失败的原因：校验错误或其它错误
+type ChangeError = (Vec<ValidationError>, Option<String>);
+
+/// 校验修改并把要写入的文件加入事务；返回自动备份的说明和修改后的列表，
+/// 修改不改变任何内容时返回 None
+fn stage_change(change: &SiteChange, sites: &[Site], discovered: &[Site], transaction: &mut Transaction) -> Result<Option<(String, Vec<Site>)>, ChangeError> {
+    let mut updated = sites.to_vec();
+    let label = match change {
+        SiteChange::Save { original_name, site } => {
+            if discovered.iter().any(|s| Some(&s.name) == original_name.as_ref()) {
+                return Err((Vec::new(), Some(format!("{} is not managed by easyNginx", site.name))));
+            }
+            let others: Vec<Site> = sites.iter()
+                .filter(|s| Some(&s.name) != original_name.as_ref())
+                .chain(discovered.iter())
+                .cloned()
+                .collect();
+            let other_names: Vec<String> = others.iter().map(|s| s.name.clone()).collect();
+            let mut errors = site.validate(&other_names);
+            if site.enabled && site.conflicts(&others).iter().any(SiteConflict::is_blocking) {
+                errors.push(ValidationError { field: SiteField::Domain, problem: crate::model::l18n::Key::ValidationDomainConflict });
+            }
+            if !errors.is_empty() {
+                return Err((errors, None));
+            }
+            let label = format!("Before saving site {}", site.name);
+            let index = original_name.as_ref().and_then(|name| sites.iter().position(|s| &s.name == name));
+            let mut site = (**site).clone();
+            // 导入的站点改名后仍写回原来的文件
+            site.imported_from = index.and_then(|index| sites[index].imported_from.clone());
+            let path = conf_path_of(&site);
+            // 导入的站点只修改原来文件中对应的指令，文件中的其它内容保持不变
+            let content = match index.map(|index| &sites[index]).filter(|old| old.imported_from.is_some()) {
+                Some(old) => std::fs::read_to_string(&path)
+                    .map_err(|e| e.to_string())
+                    .and_then(|source| edit::update_imported(&source, old, &site))
+                    .map_err(|e| (Vec::new(), Some(format!("{}: {}", path.display(), e))))?,
+                None => config::render(&site),
+            };
+            transaction.write(path.clone(), content);
+            
+            match index {
+                Some(index) => {
+                    let old = std::mem::replace(&mut updated[index], site);
+                    let old_path = conf_path_of(&old);
+                    if old_path != path {
+                        transaction.remove(old_path);
+                    }
+                }
+                None => updated.push(site),
+            }
+            label
+        }
+        SiteChange::SetEnabled { name, enabled } => {
+            if discovered.iter().any(|s| &s.name == name) {
+                return Err((Vec::new(), Some(format!("{} is not managed by easyNginx", name))));
+            }
+            let Some(index) = sites.iter().position(|s| &s.name == name) else {
+                return Err((Vec::new(), Some(format!("{} does not exist", name))));
+            };
+            if sites[index].enabled == *enabled {
+                return Ok(None);
+            }
+            // 导入的站点由 nginx.conf 原有的 include 加载，不能通过 sites-enabled.conf 停用
+            if sites[index].imported_from.is_some() {
+                return Err((Vec::new(), Some(format!("{} is loaded by an include in nginx.conf and cannot be disabled here", name))));
+            }
+            if *enabled {
+                let others: Vec<Site> = sites.iter()
+                    .filter(|s| &s.name != name)
+                    .chain(discovered.iter())
+                    .cloned()
+                    .collect();
+                if sites[index].conflicts(&others).iter().any(SiteConflict::is_blocking) {
+                    return Err((vec![ValidationError { field: SiteField::Domain, problem: crate::model::l18n::Key::ValidationDomainConflict }], None));
+                }
+            }
+            let action = if *enabled { "enabling" } else { "disabling" };
+            updated[index].enabled = *enabled;
+            format!("Before {} site {}", action, name)
+        }
+        SiteChange::Delete { name, archive } => {
+            if discovered.iter().any(|s| &s.name == name) {
+                return Err((Vec::new(), Some(format!("{} is not managed by easyNginx", name))));
+            }
+            let Some(index) = sites.iter().position(|s| &s.name == name) else {
+                return Ok(None);
+            };
+            let site = updated.remove(index);
+            // 导入的站点使用用户原来的文件，删除前总是归档
+            if *archive || site.imported_from.is_some() {
+                let dir = archive_site(&site, transaction).map_err(|e| (Vec::new(), Some(e)))?;
+                println!("[Site] Archiving {} to {}", name, dir.display());
+            }
+            transaction.remove(conf_path_of(&site));
+            format!("Before deleting site {}", name)
+        }
+    };
+    transaction.write(enabled_include_path(), enabled_include(&updated));
+    let text = sites_toml(&updated).map_err(|e| (Vec::new(), Some(format!("{}: {}", store_path().display(), e))))?;
+    transaction.write(store_path(), text);
+    Ok(Some((label, updated)))
+}
+
+pub(crate) fn sites_toml(sites: &[Site]) -> std::io::Result<String> {
+    let store = SiteStore { sites: sites.to_vec() };
+    toml::to_string_pretty(&store).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+
+// ==============================================================================
+// Module
+// ==============================================================================
+
+pub struct SiteModule {
+    name: &'static str,
+    bus: Arc<RwLock<Option<Arc<MessageBus>>>>,
+    sites: Arc<RwLock<Vec<Site>>>,
+    /// sites.toml 无法读取时的错误；此时拒绝修改，避免覆盖用户的文件
+    load_error: Arc<RwLock<Option<String>>>,
+    /// 从 nginx 配置中发现的站点
+    discovered: Arc<RwLock<discover::Discovery>>,
+}
+
+impl SiteModule {
+    pub fn new() -> Self {
+        Self {
+            name: "site",
+            bus: Arc::new(RwLock::new(None)),
+            sites: Arc::new(RwLock::new(Vec::new())),
+            load_error: Arc::new(RwLock::new(None)),
+            discovered: Arc::new(RwLock::new(discover::Discovery::default())),
+        }
+    }
+    
+    /// 重新读取站点列表
+    async fn reload_sites(&self) {
+        let (sites, error) = match load_sites() {
+            Ok(sites) => (sites, None),
+            Err(e) => {
+                eprintln!("[Site] Failed to read the site list, changes are disabled: {}", e);
+                (Vec::new(), Some(e))
+            }
+        };
+        *self.sites.write().await = sites;
+        *self.load_error.write().await = error;
+    }
+    
+    /// 重新读取 nginx 配置
+    async fn refresh_discovered(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let sites = self.sites.read().await;
+        let taken: Vec<String> = sites.iter().map(|site| site.name.clone()).collect();
+        let imported: Vec<PathBuf> = sites.iter().filter_map(|site| site.imported_from.clone()).collect();
+        drop(sites);
+        let settings = crate::model::settings::Settings::load().nginx;
+        let discovery = tokio::task::spawn_blocking(move || discover::discover(&settings, &taken, &imported)).await?;
+        for error in &discovery.errors {
+            eprintln!("[Site] Failed to read nginx configuration: {}", error);
+        }
+        *self.discovered.write().await = discovery;
+        Ok(())
+    }
+    
+    /// 应用修改；返回校验错误或 IO 错误，请求重载时返回提交的结果
+    async fn apply(&self, change: &SiteChange, reload: bool) -> Result<Option<ReloadOutcome>, ChangeError> {
+        if let Some(error) = &*self.load_error.read().await {
+            return Err((Vec::new(), Some(format!("The site list cannot be changed until it is fixed: {}", error))));
+        }
+        let discovered = self.discovered.read().await.sites.clone();
+        let mut sites = self.sites.write().await;
+        let mut transaction = Transaction::new();
+        let Some((label, updated)) = stage_change(change, &sites, &discovered, &mut transaction)? else {
+            return Ok(None);
+        };
+        crate::model::backup::auto_snapshot(&label).map_err(|e| (Vec::new(), Some(e)))?;
+        
+        let outcome = if reload {
+            let settings = crate::model::settings::Settings::load().nginx;
+            let outcome = tokio::task::spawn_blocking(move || transaction.commit(&settings))
+                .await
+                .map_err(|e| (Vec::new(), Some(e.to_string())))?
+                .map_err(|e| (Vec::new(), Some(e)))?;
+            println!("[Site] Reload after change: {:?}", outcome);
+            Some(outcome)
+        } else {
+            transaction.apply().map_err(|e| (Vec::new(), Some(e)))?;
+            None
+        };
+        if outcome.as_ref().is_none_or(ReloadOutcome::is_applied) {
+            *sites = updated;
+        }
+        Ok(outcome)
+    }
+    
+    async fn publish_sites(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut sites = self.sites.read().await.clone();
+        let discovered = self.discovered.read().await;
+        sites.extend(discovered.sites.iter().cloned());
+        let mut errors = discovered.errors.clone();
+        drop(discovered);
+        errors.extend(self.load_error.read().await.clone());
+        if let Some(bus) = &*self.bus.read().await {
+            bus.publish(SitesUpdated { sites, errors }).await?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for SiteModule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Module for SiteModule {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+    
+    async fn initialize(&mut self, bus: Arc<MessageBus>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        *self.bus.write().await = Some(bus.clone());
+        self.reload_sites().await;
+        self.refresh_discovered().await?;
+        
+        let change_request_type = bus.register_message_type::<SiteChangeRequest>().await;
+        let list_request_type = bus.register_message_type::<SiteListRequest>().await;
+        let refresh_request_type = bus.register_message_type::<SiteRefreshRequest>().await;
+        bus.register_message_type::<SiteChangeResponse>().await;
+        bus.register_message_type::<SitesUpdated>().await;
+        
+        bus.subscribe(change_request_type, self.name().to_string()).await;
+        bus.subscribe(list_request_type, self.name().to_string()).await;
+        bus.subscribe(refresh_request_type, self.name().to_string()).await;
+        
+        println!(
+            "[Site] Loaded {} site(s), found {} in the nginx configuration",
+            self.sites.read().await.len(),
+            self.discovered.read().await.sites.len()
+        );
+        Ok(())
+    }
+    
+    async fn process_message(&self, envelope: MessageEnvelope) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if envelope.message_type == TypeId::of::<SiteChangeRequest>() {
+            if let Some(msg) = envelope.payload.as_any().downcast_ref::<SiteChangeRequest>() {
+                let (validation_errors, error, reload) = match self.apply(&msg.change, msg.reload).await {
+                    Ok(reload) => (Vec::new(), None, reload),
+                    Err((validation_errors, error)) => (validation_errors, error, None),
+                };
+                if let Some(error) = &error {
+                    eprintln!("[Site] Failed to apply change: {}", error);
+                }
+                let changed = validation_errors.is_empty() && error.is_none()
+                    && reload.as_ref().is_none_or(ReloadOutcome::is_applied);
+                let response = SiteChangeResponse {
+                    id: msg.id,
+                    requester: msg.requester.clone(),
+                    validation_errors,
+                    error,
+                    reload,
+                };
+                
+                if let Some(bus) = &*self.bus.read().await {
+                    bus.publish(response).await?;
+                }
+                if changed {
+                    self.publish_sites().await?;
+                }
+            }
+        } else if envelope.message_type == TypeId::of::<SiteListRequest>() {
+            self.publish_sites().await?;
+        } else if envelope.message_type == TypeId::of::<SiteRefreshRequest>() {
+            // 站点列表可能已被接管或撤销接管修改
+            self.reload_sites().await;
+            self.refresh_discovered().await?;
+            self.publish_sites().await?;
+        }
+        
+        Ok(())
+    }
+    
+    async fn shutdown(&mut self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        Ok(())
+    }
+}
+
+module_init!(SiteModule, "site");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_site_list_without_examples() {
+        assert!(parse_sites("").unwrap().is_empty());
+        let text = sites_toml(&[Site::new("blog", SiteType::Static)]).unwrap();
+        let sites = parse_sites(&text).unwrap();
+        assert_eq!(sites.len(), 1);
+        assert_eq!(sites[0].name, "blog");
+        // 无法解析的文件报错，不退回任何默认站点
+        assert!(parse_sites("[[sites]]\nname = ").is_err());
+    }
+
+    fn stage(change: SiteChange, sites: &[Site]) -> Result<(Transaction, Vec<Site>), ChangeError> {
+        let mut transaction = Transaction::new();
+        let (_, updated) = stage_change(&change, sites, &[], &mut transaction)?.expect("change should not be a no-op");
+        Ok((transaction, updated))
+    }
+
+    fn written(transaction: &Transaction, path: &std::path::Path) -> Option<String> {
+        transaction.writes().get(path).cloned().flatten().map(|data| String::from_utf8(data).unwrap())
+    }
+
+    fn sites() -> Vec<Site> {
+        let mut blog = Site::new("blog", SiteType::Static);
+        blog.domain = "blog.example.com".into();
+        let mut shop = Site::new("shop", SiteType::Static);
+        shop.domain = "shop.example.com".into();
+        shop.enabled = false;
+        vec![blog, shop]
+    }
+
+    #[test]
+    fn set_enabled_rewrites_include() {
+        let include = |name: &str| format!("include \"{}\";", site_conf_path(name).display());
+        // 停用的站点不列入 sites-enabled.conf
+        let text = enabled_include(&sites());
+        assert!(text.contains(&include("blog")) && !text.contains(&include("shop")));
+
+        let (transaction, updated) = stage(SiteChange::SetEnabled { name: "shop".into(), enabled: true }, &sites()).unwrap();
+        assert!(updated.iter().all(|site| site.enabled));
+        let text = written(&transaction, &enabled_include_path()).unwrap();
+        assert!(text.contains(&include("blog")) && text.contains(&include("shop")));
+        // 配置文件本身不变
+        assert!(!transaction.writes().contains_key(&site_conf_path("shop")));
+
+        let (transaction, _) = stage(SiteChange::SetEnabled { name: "blog".into(), enabled: false }, &sites()).unwrap();
+        assert_eq!(written(&transaction, &enabled_include_path()).unwrap().lines().count(), 1);
+        assert!(stage_change(&SiteChange::SetEnabled { name: "shop".into(), enabled: false }, &sites(), &[], &mut Transaction::new()).unwrap().is_none());
+
+        let mut imported = sites();
+        imported[0].imported_from = Some(PathBuf::from("/etc/nginx/conf.d/blog.conf"));
+        assert!(stage(SiteChange::SetEnabled { name: "blog".into(), enabled: false }, &imported).is_err());
+    }
+
+    #[test]
+    fn archives_site_files() {
+        let mut site = sites().remove(0);
+        site.imported_from = Some(std::env::temp_dir().join(format!("easynginx-missing-{}", std::process::id())).join("blog.conf"));
+        let site = &site;
+        let mut transaction = Transaction::new();
+        let dir = archive_site(site, &mut transaction).unwrap();
+        assert!(dir.starts_with(archive_dir()) && dir.file_name().unwrap().to_string_lossy().starts_with("blog-"));
+        let files: Vec<&PathBuf> = transaction.writes().keys().collect();
+        assert_eq!(files, [&dir.join("blog.conf"), &dir.join("sites.toml")]);
+        // 配置文件不存在时按当前设置生成
+        assert_eq!(written(&transaction, &dir.join("blog.conf")).unwrap(), config::render(site));
+        let archived = parse_sites(&written(&transaction, &dir.join("sites.toml")).unwrap()).unwrap();
+        assert_eq!(archived.len(), 1);
+        assert_eq!(archived[0].name, "blog");
+    }
+
+    #[test]
+    fn delete_removes_or_archives_site() {
+        let (transaction, updated) = stage(SiteChange::Delete { name: "blog".into(), archive: false }, &sites()).unwrap();
+        assert_eq!(updated.len(), 1);
+        assert_eq!(transaction.writes().get(&site_conf_path("blog")), Some(&None));
+        assert!(!transaction.writes().keys().any(|path| path.starts_with(archive_dir())));
+        assert!(!written(&transaction, &enabled_include_path()).unwrap().contains("blog"));
+
+        let (transaction, _) = stage(SiteChange::Delete { name: "blog".into(), archive: true }, &sites()).unwrap();
+        assert_eq!(transaction.writes().keys().filter(|path| path.starts_with(archive_dir())).count(), 2);
+        assert_eq!(transaction.writes().get(&site_conf_path("blog")), Some(&None));
+
+        // 导入的站点即使不要求归档，也先归档用户原来的文件
+        let dir = std::env::temp_dir().join(format!("easynginx-site-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let original = dir.join("blog.conf");
+        std::fs::write(&original, "server { listen 80; }\n").unwrap();
+        let mut imported = sites();
+        imported[0].imported_from = Some(original.clone());
+        let (transaction, _) = stage(SiteChange::Delete { name: "blog".into(), archive: false }, &imported).unwrap();
+        assert_eq!(transaction.writes().get(&original), Some(&None));
+        let archived = transaction.writes().keys()
+            .find(|path| path.starts_with(archive_dir()) && path.file_name().is_some_and(|name| name == "blog.conf"))
+            .unwrap();
+        assert_eq!(written(&transaction, archived).unwrap(), "server { listen 80; }\n");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn saving_imported_site_keeps_its_file() {
+        let corpus = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/corpus/nginx/wordpress.conf");
+        let source = std::fs::read_to_string(&corpus).unwrap();
+        let dir = std::env::temp_dir().join(format!("easynginx-imported-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("wordpress.conf");
+        std::fs::write(&path, &source).unwrap();
+
+        let file = crate::model::nginx::load_file(&path).unwrap();
+        let plan = crate::model::takeover::plan_import(&[file], &dir.join("nginx.conf"), std::path::Path::new("/nonexistent"), &[]);
+        let sites = plan.sites;
+        assert_eq!(sites.len(), 1);
+        let save = |site: &Site| stage(SiteChange::Save { original_name: Some("wordpress".into()), site: Box::new(site.clone()) }, &sites);
+
+        // 不修改直接保存：文件逐字节不变
+        let (transaction, _) = save(&sites[0]).unwrap();
+        assert_eq!(written(&transaction, &path).unwrap(), source);
+
+        // 只改动模型对应的指令，upstream、location 等其它内容保留
+        let mut site = sites[0].clone();
+        site.domain = "news.example.org".into();
+        if let SiteKind::Php { fpm_socket, .. } = &mut site.kind {
+            *fpm_socket = "unix:/run/php/php8.3-fpm.sock".into();
+        }
+        let (transaction, _) = save(&site).unwrap();
+        let expected = source
+            .replace("server_name blog.example.org www.blog.example.org;", "server_name news.example.org;")
+            .replace("fastcgi_pass php;", "fastcgi_pass unix:/run/php/php8.3-fpm.sock;");
+        assert_eq!(written(&transaction, &path).unwrap(), expected);
+
+        site.http2 = false;
+        assert!(save(&site).is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
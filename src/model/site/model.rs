@@ -0,0 +1,533 @@
+// MIT License
+// 
+// Copyright (c) 2026 Laffinty
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+
+// ==============================================================================
+// Site model - 站点数据与校验
+// ==============================================================================
+// Site 是 easyNginx 管理的一个 server 块。公共字段（名称、端口、域名、HTTPS）
+// 之外，不同类型的站点有各自的字段，由 SiteKind 表示。
+//
+// 校验结果使用翻译键描述问题，界面可以直接显示在对应字段下方；
+// SiteModule 保存前也会做同样的校验。
+//
+// 从 nginx 配置中发现的站点（见 discover）也用 Site 表示，origin 记录其来源；
+// 无法归类为静态、PHP 或代理的 server 块类型为 Custom。
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use crate::model::l18n::Key;
+
+/// 站点类型（用于显示和筛选）
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SiteType {
+    Static,
+    Php,
+    Proxy,
+    /// 从配置中发现、无法归类的 server 块
+    Custom,
+}
+
+impl SiteType {
+    pub const ALL: [SiteType; 4] = [SiteType::Static, SiteType::Php, SiteType::Proxy, SiteType::Custom];
+    /// 可以在编辑器和向导中创建的类型
+    pub const EDITABLE: [SiteType; 3] = [SiteType::Static, SiteType::Php, SiteType::Proxy];
+
+    pub fn translation_key(&self) -> Key {
+        match self {
+            SiteType::Static => Key::SiteTypeStatic,
+            SiteType::Php => Key::SiteTypePhp,
+            SiteType::Proxy => Key::SiteTypeProxy,
+            SiteType::Custom => Key::SiteTypeCustom,
+        }
+    }
+}
+
+/// 各类型站点特有的字段
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum SiteKind {
+    Static {
+        root: String,
+    },
+    Php {
+        root: String,
+        /// PHP-FPM 地址：unix:/run/php/php-fpm.sock 或 127.0.0.1:9000
+        fpm_socket: String,
+        index_files: Vec<String>,
+    },
+    Proxy {
+        upstream: String,
+    },
+    Custom,
+}
+
+impl SiteKind {
+    /// 指定类型的默认字段
+    pub fn default_for(site_type: SiteType) -> Self {
+        match site_type {
+            SiteType::Static => SiteKind::Static {
+                root: "/var/www/html".into(),
+            },
+            SiteType::Php => SiteKind::Php {
+                root: "/var/www/html".into(),
+                fpm_socket: "unix:/run/php/php-fpm.sock".into(),
+                index_files: vec!["index.php".into(), "index.html".into()],
+            },
+            SiteType::Proxy => SiteKind::Proxy {
+                upstream: "http://127.0.0.1:3000".into(),
+            },
+            SiteType::Custom => SiteKind::Custom,
+        }
+    }
+
+    pub fn site_type(&self) -> SiteType {
+        match self {
+            SiteKind::Static { .. } => SiteType::Static,
+            SiteKind::Php { .. } => SiteType::Php,
+            SiteKind::Proxy { .. } => SiteType::Proxy,
+            SiteKind::Custom => SiteType::Custom,
+        }
+    }
+}
+
+/// 一个站点
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Site {
+    pub name: String,
+    pub port: u16,
+    /// server_name，多个域名以空格分隔
+    pub domain: String,
+    pub enable_https: bool,
+    /// 在 80 端口把 HTTP 请求重定向到 HTTPS（仅在启用 HTTPS 时有效）
+    pub enable_http_redirect: bool,
+    /// 同时监听 IPv6（[::]）
+    #[serde(default)]
+    pub ipv6: bool,
+    /// 启用 HTTP/2（仅在启用 HTTPS 时有效）
+    #[serde(default)]
+    pub http2: bool,
+    #[serde(default)]
+    pub certificate: String,
+    #[serde(default)]
+    pub certificate_key: String,
+    /// 是否写入 sites-enabled.conf（由 nginx 加载）
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+    /// 接管 nginx 时导入的站点所在的配置文件（见 takeover）；修改后的配置写回这个文件，
+    /// 修改之前文件保持原样
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub imported_from: Option<PathBuf>,
+    #[serde(flatten)]
+    pub kind: SiteKind,
+    /// 站点来源（不保存）
+    #[serde(skip)]
+    pub origin: SiteOrigin,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+/// 站点来源
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum SiteOrigin {
+    /// 由 easyNginx 生成和管理
+    #[default]
+    Managed,
+    /// 从 nginx 配置中读取，只读
+    Discovered {
+        path: PathBuf,
+        line: usize,
+        listens: Vec<Listen>,
+    },
+}
+
+/// 一条 listen 指令
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Listen {
+    /// 地址（如 127.0.0.1、[::]），None 表示所有地址
+    pub address: Option<String>,
+    pub port: u16,
+    pub ssl: bool,
+    pub http2: bool,
+    pub default_server: bool,
+}
+
+impl Site {
+    pub fn new(name: &str, site_type: SiteType) -> Self {
+        Self {
+            name: name.to_string(),
+            port: 80,
+            domain: String::new(),
+            enable_https: false,
+            enable_http_redirect: false,
+            ipv6: false,
+            http2: false,
+            certificate: String::new(),
+            certificate_key: String::new(),
+            enabled: true,
+            imported_from: None,
+            kind: SiteKind::default_for(site_type),
+            origin: SiteOrigin::Managed,
+        }
+    }
+
+    pub fn is_managed(&self) -> bool {
+        self.origin == SiteOrigin::Managed
+    }
+
+    pub fn site_type(&self) -> SiteType {
+        self.kind.site_type()
+    }
+
+    /// 监听的端口（启用重定向时包括 80）
+    pub fn listen_ports(&self) -> Vec<u16> {
+        let mut ports = match &self.origin {
+            SiteOrigin::Discovered { listens, .. } if !listens.is_empty() => {
+                let mut ports: Vec<u16> = Vec::new();
+                for listen in listens {
+                    if !ports.contains(&listen.port) {
+                        ports.push(listen.port);
+                    }
+                }
+                ports
+            }
+            _ => vec![self.port],
+        };
+        if self.enable_https && self.enable_http_redirect && !ports.contains(&80) {
+            ports.push(80);
+        }
+        ports
+    }
+
+    /// 列表中显示的端口，如 "443/80(redirect)"
+    pub fn port_label(&self) -> String {
+        let ports = self.listen_ports();
+        let redirect = self.enable_https && self.enable_http_redirect;
+        ports.iter()
+            .map(|port| if redirect && *port == 80 { "80(redirect)".to_string() } else { port.to_string() })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// server_name 中的各个域名
+    pub fn server_names(&self) -> impl Iterator<Item = &str> {
+        self.domain.split_whitespace()
+    }
+
+    /// 与其它已启用站点的端口和 server_name 冲突
+    pub fn conflicts(&self, others: &[Site]) -> Vec<SiteConflict> {
+        let mut conflicts = Vec::new();
+        for other in others.iter().filter(|other| other.enabled && other.name != self.name) {
+            let other_ports = other.listen_ports();
+            for port in self.listen_ports().into_iter().filter(|port| other_ports.contains(port)) {
+                let shared: Vec<&str> = self.server_names()
+                    .filter(|name| other.server_names().any(|other_name| other_name.eq_ignore_ascii_case(name)))
+                    .collect();
+                if shared.is_empty() {
+                    conflicts.push(SiteConflict::Port { site: other.name.clone(), port });
+                }
+                for domain in shared {
+                    conflicts.push(SiteConflict::ServerName { site: other.name.clone(), port, domain: domain.to_string() });
+                }
+            }
+        }
+        conflicts
+    }
+
+    /// 校验全部字段；other_names 为其它站点的名称（用于检查重名）
+    pub fn validate(&self, other_names: &[String]) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+        let mut check = |field: SiteField, problem: Option<Key>| {
+            if let Some(problem) = problem {
+                errors.push(ValidationError { field, problem });
+            }
+        };
+
+        check(SiteField::Name, validate_name(&self.name, other_names));
+        check(SiteField::Port, (self.port == 0).then_some(Key::ValidationPortInvalid));
+        check(SiteField::Domain, validate_domains(&self.domain));
+        if self.enable_https {
+            check(SiteField::Certificate, validate_path(&self.certificate));
+            check(SiteField::CertificateKey, validate_path(&self.certificate_key));
+        }
+
+        match &self.kind {
+            SiteKind::Static { root } => check(SiteField::Root, validate_path(root)),
+            SiteKind::Php { root, fpm_socket, index_files } => {
+                check(SiteField::Root, validate_path(root));
+                check(SiteField::FpmSocket, validate_fpm_socket(fpm_socket));
+                check(SiteField::IndexFiles, index_files.is_empty().then_some(Key::ValidationIndexRequired));
+            }
+            SiteKind::Proxy { upstream } => check(SiteField::Upstream, validate_upstream(upstream)),
+            SiteKind::Custom => {}
+        }
+        errors
+    }
+}
+
+/// 与其它站点的冲突
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SiteConflict {
+    /// 监听同一端口，但 server_name 不同 - nginx 可以区分，仅作提示
+    Port { site: String, port: u16 },
+    /// 同一端口上的相同域名 - nginx 会忽略其中一个，不允许保存
+    ServerName { site: String, port: u16, domain: String },
+}
+
+impl SiteConflict {
+    pub fn is_blocking(&self) -> bool {
+        matches!(self, SiteConflict::ServerName { .. })
+    }
+}
+
+/// 可编辑的字段（用于把校验错误显示在对应输入框下方）
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SiteField {
+    Name,
+    Port,
+    Domain,
+    Certificate,
+    CertificateKey,
+    Root,
+    FpmSocket,
+    IndexFiles,
+    Upstream,
+}
+
+/// 一个字段的校验错误，problem 为描述问题的翻译键
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ValidationError {
+    pub field: SiteField,
+    pub problem: Key,
+}
+
+/// 端口文本 -> 端口号（1-65535）
+pub fn parse_port(text: &str) -> Option<u16> {
+    text.trim().parse::<u16>().ok().filter(|port| *port != 0)
+}
+
+fn validate_name(name: &str, other_names: &[String]) -> Option<Key> {
+    // 名称同时用作配置文件名
+    let valid_char = |c: char| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_');
+    if name.trim().is_empty() {
+        Some(Key::ValidationNameRequired)
+    } else if name.starts_with('.') || !name.chars().all(valid_char) {
+        Some(Key::ValidationNameInvalid)
+    } else if other_names.iter().any(|other| other == name) {
+        Some(Key::ValidationNameTaken)
+    } else {
+        None
+    }
+}
+
+fn validate_domains(domain: &str) -> Option<Key> {
+    // 允许通配符（*.example.com）和 nginx 的占位名 "_"
+    let valid_domain = |name: &str| {
+        name == "_" || (!name.starts_with('.') && !name.ends_with('.') && name.split('.').enumerate().all(|(i, label)| {
+            (i == 0 && label == "*")
+                || (!label.is_empty() && !label.starts_with('-') && !label.ends_with('-')
+                    && label.chars().all(|c| c.is_alphanumeric() || c == '-'))
+        }))
+    };
+    let names: Vec<&str> = domain.split_whitespace().collect();
+    (names.is_empty() || !names.iter().all(|name| valid_domain(name))).then_some(Key::ValidationDomainInvalid)
+}
+
+fn validate_path(path: &str) -> Option<Key> {
+    let path = path.trim();
+    if path.is_empty() {
+        Some(Key::ValidationPathRequired)
+    } else if !(path.starts_with('/') || std::path::Path::new(path).is_absolute()) {
+        Some(Key::ValidationPathAbsolute)
+    } else {
+        None
+    }
+}
+
+fn validate_fpm_socket(socket: &str) -> Option<Key> {
+    let socket = socket.trim();
+    // 不带端口的名称是 upstream 块的名称（如 `fastcgi_pass php;`）
+    let upstream_name = |name: &str| !name.is_empty()
+        && !name.starts_with(['.', '-'])
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'));
+    let valid = match socket.strip_prefix("unix:") {
+        Some(path) => path.starts_with('/'),
+        None => match socket.rsplit_once(':') {
+            Some((host, port)) => !host.is_empty() && parse_port(port).is_some(),
+            None => upstream_name(socket),
+        },
+    };
+    (!valid).then_some(Key::ValidationSocketInvalid)
+}
+
+fn validate_upstream(upstream: &str) -> Option<Key> {
+    let rest = upstream.trim()
+        .strip_prefix("http://")
+        .or_else(|| upstream.trim().strip_prefix("https://"));
+    let valid = rest.map(|rest| {
+        let host = rest.split('/').next().unwrap_or_default();
+        !host.is_empty() && !host.contains(char::is_whitespace)
+    }).unwrap_or(false);
+    (!valid).then_some(Key::ValidationUpstreamInvalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn site(name: &str, port: u16, domain: &str) -> Site {
+        let mut site = Site::new(name, SiteType::Static);
+        site.port = port;
+        site.domain = domain.into();
+        site
+    }
+
+    #[test]
+    fn conflicts_distinguish_ports_and_server_names() {
+        let mut redirecting = site("secure", 443, "a.example.com");
+        redirecting.enable_https = true;
+        redirecting.enable_http_redirect = true;
+        let mut disabled = site("old", 80, "b.example.com");
+        disabled.enabled = false;
+        let existing = vec![redirecting, disabled, site("other", 8080, "c.example.com")];
+
+        let new_site = site("new", 80, "b.example.com A.example.com");
+        assert_eq!(new_site.conflicts(&existing), vec![SiteConflict::ServerName {
+            site: "secure".into(),
+            port: 80,
+            domain: "A.example.com".into(),
+        }]);
+
+        let new_site = site("new", 8080, "d.example.com");
+        assert_eq!(new_site.conflicts(&existing), vec![SiteConflict::Port { site: "other".into(), port: 8080 }]);
+    }
+
+    #[test]
+    fn validates_names() {
+        let taken = vec!["blog".to_string()];
+        let cases = [
+            ("shop", None),
+            ("my-site_2.example", None),
+            ("", Some(Key::ValidationNameRequired)),
+            ("   ", Some(Key::ValidationNameRequired)),
+            (".hidden", Some(Key::ValidationNameInvalid)),
+            ("a/b", Some(Key::ValidationNameInvalid)),
+            ("with space", Some(Key::ValidationNameInvalid)),
+            ("blog", Some(Key::ValidationNameTaken)),
+        ];
+        for (name, expected) in cases {
+            assert_eq!(validate_name(name, &taken), expected, "{:?}", name);
+        }
+    }
+
+    #[test]
+    fn validates_domains() {
+        let cases = [
+            ("example.com", None),
+            ("example.com www.example.com", None),
+            ("*.example.com", None),
+            ("_", None),
+            ("localhost", None),
+            ("", Some(Key::ValidationDomainInvalid)),
+            (".example.com", Some(Key::ValidationDomainInvalid)),
+            ("example.com.", Some(Key::ValidationDomainInvalid)),
+            ("-bad.example.com", Some(Key::ValidationDomainInvalid)),
+            ("a..example.com", Some(Key::ValidationDomainInvalid)),
+            ("www.*.example.com", Some(Key::ValidationDomainInvalid)),
+            ("example.com bad/name", Some(Key::ValidationDomainInvalid)),
+        ];
+        for (domain, expected) in cases {
+            assert_eq!(validate_domains(domain), expected, "{:?}", domain);
+        }
+    }
+
+    #[test]
+    fn validates_paths() {
+        let cases = [
+            ("/var/www/html", None),
+            (" /srv/My Docs ", None),
+            ("", Some(Key::ValidationPathRequired)),
+            ("  ", Some(Key::ValidationPathRequired)),
+            ("var/www", Some(Key::ValidationPathAbsolute)),
+            ("./html", Some(Key::ValidationPathAbsolute)),
+        ];
+        for (path, expected) in cases {
+            assert_eq!(validate_path(path), expected, "{:?}", path);
+        }
+    }
+
+    #[test]
+    fn validates_fpm_sockets() {
+        let cases = [
+            ("unix:/run/php/php8.2-fpm.sock", None),
+            ("127.0.0.1:9000", None),
+            ("php-fpm:9000", None),
+            ("php", None),
+            ("php_backend", None),
+            ("", Some(Key::ValidationSocketInvalid)),
+            ("unix:run/php.sock", Some(Key::ValidationSocketInvalid)),
+            ("127.0.0.1:0", Some(Key::ValidationSocketInvalid)),
+            (":9000", Some(Key::ValidationSocketInvalid)),
+            ("-php", Some(Key::ValidationSocketInvalid)),
+            ("/run/php.sock", Some(Key::ValidationSocketInvalid)),
+        ];
+        for (socket, expected) in cases {
+            assert_eq!(validate_fpm_socket(socket), expected, "{:?}", socket);
+        }
+    }
+
+    #[test]
+    fn validates_upstreams() {
+        let cases = [
+            ("http://127.0.0.1:3000", None),
+            ("https://api.example.com/v1", None),
+            ("http://app_backend", None),
+            ("", Some(Key::ValidationUpstreamInvalid)),
+            ("127.0.0.1:3000", Some(Key::ValidationUpstreamInvalid)),
+            ("ftp://example.com", Some(Key::ValidationUpstreamInvalid)),
+            ("http://", Some(Key::ValidationUpstreamInvalid)),
+            ("http:///path", Some(Key::ValidationUpstreamInvalid)),
+        ];
+        for (upstream, expected) in cases {
+            assert_eq!(validate_upstream(upstream), expected, "{:?}", upstream);
+        }
+    }
+
+    #[test]
+    fn validate_reports_fields() {
+        let mut php = Site::new("blog", SiteType::Php);
+        php.domain = "blog.example.com".into();
+        php.port = 0;
+        php.enable_https = true;
+        php.certificate = "cert.pem".into();
+        php.kind = SiteKind::Php { root: "/var/www".into(), fpm_socket: "php".into(), index_files: Vec::new() };
+        let errors: Vec<(SiteField, Key)> = php.validate(&[]).into_iter().map(|error| (error.field, error.problem)).collect();
+        assert_eq!(errors, [
+            (SiteField::Port, Key::ValidationPortInvalid),
+            (SiteField::Certificate, Key::ValidationPathAbsolute),
+            (SiteField::CertificateKey, Key::ValidationPathRequired),
+            (SiteField::IndexFiles, Key::ValidationIndexRequired),
+        ]);
+    }
+}
@@ -0,0 +1,120 @@
+// MIT License
+// 
+// Copyright (c) 2026 Laffinty
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+
+// ==============================================================================
+// detect - 查找已安装的 nginx 和可以导入的站点
+// ==============================================================================
+// 在 PATH、/usr/sbin、/usr/local/nginx 等位置查找 nginx，由 nginx -V 得到
+// 前缀、主配置文件和编译的模块，由 pidfile 找到正在运行的 master。
+//
+// 只导入能够整体交给 easyNginx 的配置文件：文件中只有一个站点（包括合并进来的
+// HTTP 重定向块），且是静态、PHP 或代理站点。其它文件中的站点仍作为只读站点显示。
+
+use std::path::{Path, PathBuf};
+use crate::model::controller::{installed_binaries, BuildInfo, NginxController, SystemNginx};
+use crate::model::nginx::ConfigFile;
+use crate::model::site::{discover, Site, SiteOrigin, SiteType};
+
+/// 一个已安装的 nginx
+#[derive(Clone, Debug, PartialEq)]
+pub struct Installation {
+    pub binary: PathBuf,
+    pub build: BuildInfo,
+    pub prefix: PathBuf,
+    pub conf_path: PathBuf,
+    pub pid_path: PathBuf,
+    /// 正在运行的 master 进程
+    pub master_pid: Option<u32>,
+}
+
+impl Installation {
+    /// 读取 nginx -V 和 pidfile；不是可用的 nginx 时返回 None
+    pub fn probe(binary: &Path) -> Option<Self> {
+        let nginx = SystemNginx::new(binary.to_path_buf(), None, None);
+        let build = nginx.build_info().ok().filter(|build| !build.version.is_empty())?;
+        let (prefix, conf_path) = nginx.paths(&build);
+        Some(Self {
+            binary: binary.to_path_buf(),
+            prefix,
+            conf_path,
+            pid_path: nginx.pid_path().to_path_buf(),
+            master_pid: nginx.status().pid,
+            build,
+        })
+    }
+}
+
+/// 所有已安装的 nginx
+pub fn detect() -> Vec<Installation> {
+    installed_binaries().iter().filter_map(|binary| Installation::probe(binary)).collect()
+}
+
+/// 不导入某个文件的原因
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SkipReason {
+    /// 主配置文件
+    MainConfig,
+    /// 文件中有多个站点
+    SeveralSites(usize),
+    /// 无法归类的 server 块
+    Custom,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Skipped {
+    pub path: PathBuf,
+    pub reason: SkipReason,
+}
+
+/// 接管时导入的站点和跳过的文件
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ImportPlan {
+    /// 已设置 imported_from 的管理站点
+    pub sites: Vec<Site>,
+    pub skipped: Vec<Skipped>,
+}
+
+/// 按文件决定导入哪些站点；跳过 managed_dir 下的文件，taken 为已被使用的名称
+pub fn plan_import(files: &[ConfigFile], main: &Path, managed_dir: &Path, taken: &[String]) -> ImportPlan {
+    let mut plan = ImportPlan::default();
+    let mut names = taken.to_vec();
+    for file in files {
+        let mut found = discover::sites_from_files(std::slice::from_ref(file), managed_dir, &names);
+        let reason = match found.as_slice() {
+            [] => continue,
+            _ if file.path == main => SkipReason::MainConfig,
+            [site] if site.site_type() == SiteType::Custom => SkipReason::Custom,
+            [_] => {
+                let mut site = found.remove(0);
+                site.imported_from = Some(file.path.clone());
+                site.origin = SiteOrigin::Managed;
+                names.push(site.name.clone());
+                plan.sites.push(site);
+                continue;
+            }
+            several => SkipReason::SeveralSites(several.len()),
+        };
+        plan.skipped.push(Skipped { path: file.path.clone(), reason });
+    }
+    plan
+}
@@ -0,0 +1,639 @@
+// MIT License
+// 
+// Copyright (c) 2026 Laffinty
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+
+// ==============================================================================
+// Takeover Module - 接管已安装的 nginx
+// ==============================================================================
+// TakeoverScanRequest -> 查找已安装的 nginx 及各自可以导入的站点 -> TakeoverScanned
+// TakeoverRequest(TakeOver) 作为一次事务（见 transaction）写入：
+//   1. 把可以整体导入的站点加入 sites.toml（imported_from 指向原文件，文件不改动）
+//   2. 在 nginx.conf 的 http 块中 include sites-enabled.conf（已有时跳过）
+//   3. 接管记录 <config_dir>/takeover.toml：接管前的设置、被导入和被修改的文件内容
+// 先用 nginx -t 测试暂存的配置，通过后才替换文件并重载；最后把 nginx 程序和
+// 主配置文件写入设置。
+// TakeoverRequest(Undo) 同样经过测试后写回这些文件、移除导入的站点并恢复设置；
+// 接管后被手动修改过的文件保留（nginx.conf 只移除加入的 include）。
+// 完成后发布 SiteRefreshRequest，由 site 模块重新读取站点列表。
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::any::{Any, TypeId};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use crate::{MessageEnvelope, MessageBus, Module, module_init};
+use crate::model::backup;
+use crate::model::controller::parse_test_output;
+use crate::model::nginx::{self, Config, Directive, Node};
+use crate::model::settings::{NginxSettings, Settings};
+use crate::model::site::{self, Site, SiteRefreshRequest};
+use crate::model::transaction::{ReloadOutcome, Transaction};
+
+pub mod detect;
+
+pub use detect::{detect, plan_import, ImportPlan, Installation, SkipReason, Skipped};
+
+/// 请求查找已安装的 nginx，结果以 TakeoverScanned 广播
+#[derive(Clone, Debug, Default)]
+pub struct TakeoverScanRequest;
+
+impl crate::Message for TakeoverScanRequest {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn message_type(&self) -> TypeId {
+        TypeId::of::<TakeoverScanRequest>()
+    }
+    
+    fn clone_box(&self) -> Box<dyn crate::Message> {
+        Box::new(self.clone())
+    }
+}
+
+/// 一个已安装的 nginx 及接管时会导入的站点
+#[derive(Clone, Debug)]
+pub struct Candidate {
+    pub installation: Installation,
+    /// 读取配置失败时为错误
+    pub plan: Result<ImportPlan, String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct TakeoverScanned {
+    pub candidates: Vec<Candidate>,
+    /// 已经接管时的记录
+    pub current: Option<TakeoverSummary>,
+}
+
+impl crate::Message for TakeoverScanned {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn message_type(&self) -> TypeId {
+        TypeId::of::<TakeoverScanned>()
+    }
+    
+    fn clone_box(&self) -> Box<dyn crate::Message> {
+        Box::new(self.clone())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TakeoverAction {
+    /// 接管指定的 nginx
+    TakeOver { binary: PathBuf },
+    /// 撤销接管
+    Undo,
+}
+
+#[derive(Clone, Debug)]
+pub struct TakeoverRequest {
+    /// 请求方生成的编号，原样返回在响应中
+    pub id: u64,
+    pub action: TakeoverAction,
+    pub requester: String,
+}
+
+impl TakeoverRequest {
+    pub fn new(id: u64, action: TakeoverAction, requester: &str) -> Self {
+        Self {
+            id,
+            action,
+            requester: requester.to_string(),
+        }
+    }
+}
+
+impl crate::Message for TakeoverRequest {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn message_type(&self) -> TypeId {
+        TypeId::of::<TakeoverRequest>()
+    }
+    
+    fn clone_box(&self) -> Box<dyn crate::Message> {
+        Box::new(self.clone())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TakeoverResponse {
+    pub id: u64,
+    pub requester: String,
+    pub action: TakeoverAction,
+    pub error: Option<String>,
+    /// 导入（撤销时为移除）的站点
+    pub sites: Vec<String>,
+    /// 撤销时因接管后被手动修改而保留的文件
+    pub kept: Vec<PathBuf>,
+}
+
+impl crate::Message for TakeoverResponse {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn message_type(&self) -> TypeId {
+        TypeId::of::<TakeoverResponse>()
+    }
+    
+    fn clone_box(&self) -> Box<dyn crate::Message> {
+        Box::new(self.clone())
+    }
+}
+
+// ==============================================================================
+// Record
+// ==============================================================================
+
+/// 接管了哪个 nginx
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TakeoverSummary {
+    /// Unix 时间（秒）
+    pub taken_at: u64,
+    pub binary: PathBuf,
+    pub conf_path: PathBuf,
+    /// 导入的站点
+    pub sites: Vec<String>,
+}
+
+/// 撤销接管所需的全部信息
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+struct TakeoverRecord {
+    summary: TakeoverSummary,
+    previous_settings: NginxSettings,
+    /// 接管前的文件内容
+    #[serde(default)]
+    files: Vec<OriginalFile>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct OriginalFile {
+    path: PathBuf,
+    content: String,
+    /// 接管时写入的内容（没有改动的文件为 None）
+    #[serde(default)]
+    written: Option<String>,
+}
+
+pub fn record_path() -> PathBuf {
+    crate::model::config_dir().join("takeover.toml")
+}
+
+fn load_record() -> Option<TakeoverRecord> {
+    let text = std::fs::read_to_string(record_path()).ok()?;
+    match toml::from_str(&text) {
+        Ok(record) => Some(record),
+        Err(e) => {
+            eprintln!("[Takeover] Ignoring invalid {}: {}", record_path().display(), e.message());
+            None
+        }
+    }
+}
+
+/// 当前的接管记录
+pub fn current() -> Option<TakeoverSummary> {
+    load_record().map(|record| record.summary)
+}
+
+// ==============================================================================
+// Takeover
+// ==============================================================================
+
+/// 在 http 块的末尾 include 指定文件，缩进与块中第一条指令相同
+pub fn add_include(config: &mut Config, include: &str) -> Result<(), String> {
+    let http = config.directives_mut()
+        .find(|directive| directive.name() == "http")
+        .and_then(Directive::block_mut)
+        .ok_or("the main configuration file has no http block")?;
+    let indent = http.nodes.iter()
+        .take_while(|node| !matches!(node, Node::Directive(_)))
+        .filter_map(|node| match node {
+            Node::Whitespace(text) => text.rsplit_once('\n').map(|(_, indent)| indent.to_string()),
+            _ => None,
+        })
+        .last()
+        .unwrap_or_else(|| "    ".into());
+    http.push(Directive::new("include", &[include]), &indent);
+    Ok(())
+}
+
+/// 配置中是否已经 include 了指定文件
+fn includes(files: &[nginx::ConfigFile], include: &str) -> bool {
+    files.iter().any(|file| {
+        let mut found = false;
+        file.config.walk(&mut |directive| {
+            found |= directive.name() == "include" && directive.args().first().is_some_and(|arg| arg == include);
+        });
+        found
+    })
+}
+
+fn read(path: &Path) -> Result<OriginalFile, String> {
+    std::fs::read_to_string(path)
+        .map(|content| OriginalFile { path: path.to_path_buf(), content, written: None })
+        .map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn record_toml(record: &TakeoverRecord) -> Result<String, String> {
+    toml::to_string_pretty(record).map_err(|e| format!("{}: {}", record_path().display(), e))
+}
+
+fn sites_toml(sites: &[Site]) -> Result<String, String> {
+    site::sites_toml(sites).map_err(|e| format!("{}: {}", site::store_path().display(), e))
+}
+
+/// 提交事务；修改没有生效时返回错误
+fn commit(transaction: &Transaction, settings: &NginxSettings, what: &str) -> Result<(), String> {
+    match transaction.commit(settings)? {
+        ReloadOutcome::Reloaded | ReloadOutcome::NotRunning => Ok(()),
+        ReloadOutcome::TestFailed(output) => {
+            let report = parse_test_output(false, &output);
+            let message = report.issues.first().map(|issue| issue.message.clone()).unwrap_or(report.output);
+            Err(format!("nginx -t failed {}, nothing was changed: {}", what, message))
+        }
+        ReloadOutcome::RolledBack(error) => Err(format!("nginx stopped serving {}, the previous files have been put back: {}", what, error)),
+        // 文件已经替换，接管记录也已写入
+        ReloadOutcome::ReloadFailed(error) => {
+            eprintln!("[Takeover] Reloading nginx {} failed: {}", what, error);
+            Ok(())
+        }
+    }
+}
+
+/// 接管；返回导入的站点
+///
+/// 站点列表、sites-enabled.conf、加入 include 的 nginx.conf 和接管记录作为一次事务写入：
+/// 先测试暂存的配置，任何一步失败都不会留下没有记录的修改。
+fn take_over(binary: &Path) -> Result<Vec<String>, String> {
+    if load_record().is_some() {
+        return Err("nginx has already been taken over; undo the takeover first".into());
+    }
+    let installation = Installation::probe(binary)
+        .ok_or_else(|| format!("{} is not a usable nginx", binary.display()))?;
+    let main = &installation.conf_path;
+    let files = nginx::load_tree(main).map_err(|e| e.to_string())?;
+    let mut sites = site::load_sites()?;
+    backup::auto_snapshot(&format!("Before taking over {}", binary.display()))?;
+    let taken: Vec<String> = sites.iter().map(|site| site.name.clone()).collect();
+    let plan = plan_import(&files, main, &site::available_dir(), &taken);
+
+    let mut originals = plan.sites.iter()
+        .filter_map(|site| site.imported_from.as_deref())
+        .map(read)
+        .collect::<Result<Vec<_>, _>>()?;
+    sites.extend(plan.sites.iter().cloned());
+    let mut transaction = Transaction::new();
+    transaction.write(site::enabled_include_path(), site::enabled_include(&sites));
+    transaction.write(site::store_path(), sites_toml(&sites)?);
+
+    let include = site::enabled_include_path().display().to_string();
+    if !includes(&files, &include) {
+        let mut original = read(main)?;
+        let mut config = files[0].config.clone();
+        add_include(&mut config, &include)?;
+        let text = config.to_string();
+        transaction.write(main.clone(), text.clone());
+        original.written = Some(text);
+        originals.push(original);
+    }
+
+    let previous_settings = Settings::load().nginx;
+    let settings = NginxSettings {
+        binary: binary.display().to_string(),
+        conf_path: Some(main.display().to_string()),
+        prefix: None,
+        probe_url: previous_settings.probe_url.clone(),
+    };
+    let names: Vec<String> = plan.sites.iter().map(|site| site.name.clone()).collect();
+    let record = TakeoverRecord {
+        summary: TakeoverSummary {
+            taken_at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs())
+                .unwrap_or_default(),
+            binary: binary.to_path_buf(),
+            conf_path: main.clone(),
+            sites: names.clone(),
+        },
+        previous_settings,
+        files: originals,
+    };
+    transaction.write(record_path(), record_toml(&record)?);
+
+    commit(&transaction, &settings, "after adding the include")?;
+    Settings::update(|current| current.nginx = settings)
+        .map_err(|e| format!("{}: {}; undo the takeover to revert the other changes", Settings::path().display(), e))?;
+    Ok(names)
+}
+
+/// 撤销接管时如何处理一个文件
+#[derive(Debug, PartialEq)]
+enum Restore {
+    /// 内容与接管前相同
+    Unchanged,
+    Write(String),
+    /// 接管后被手动修改过，保留
+    Keep,
+}
+
+/// 只恢复接管或 easyNginx 写入的内容，不覆盖之后的手动修改
+///
+/// written 为 easyNginx 最后写入的内容（接管时写入的 nginx.conf，或按站点设置生成的配置）；
+/// nginx.conf 被手动修改过时只移除接管时加入的 include。
+fn plan_restore(file: &OriginalFile, current: Option<&str>, written: Option<&str>, include: &str) -> Restore {
+    let Some(current) = current else {
+        return Restore::Write(file.content.clone());
+    };
+    if current == file.content {
+        return Restore::Unchanged;
+    }
+    if Some(current) == written {
+        return Restore::Write(file.content.clone());
+    }
+    if file.written.is_some() {
+        if let Ok(mut config) = Config::parse(current) {
+            if remove_include(&mut config, include) {
+                return Restore::Write(config.to_string());
+            }
+        }
+    }
+    Restore::Keep
+}
+
+/// 从 http 块中移除 include 指定文件的指令；返回是否找到
+fn remove_include(config: &mut Config, include: &str) -> bool {
+    let Some(http) = config.directives_mut()
+        .find(|directive| directive.name() == "http")
+        .and_then(Directive::block_mut) else {
+        return false;
+    };
+    let is_include = |node: &Node| matches!(node, Node::Directive(directive)
+        if directive.name() == "include" && directive.args().first().is_some_and(|arg| arg == include));
+    let Some(index) = http.nodes.iter().position(is_include) else {
+        return false;
+    };
+    http.nodes.remove(index);
+    // 同时去掉指令前的缩进
+    if index > 0 {
+        if let Some(Node::Whitespace(text)) = http.nodes.get_mut(index - 1) {
+            if let Some((before, indent)) = text.rsplit_once('\n') {
+                if indent.trim().is_empty() {
+                    let len = before.len();
+                    text.truncate(len);
+                    if text.is_empty() {
+                        http.nodes.remove(index - 1);
+                    }
+                }
+            }
+        }
+    }
+    true
+}
+
+/// 撤销接管；返回移除的站点和因手动修改而保留的文件
+fn undo() -> Result<(Vec<String>, Vec<PathBuf>), String> {
+    let record = load_record().ok_or("nginx has not been taken over")?;
+    let mut sites = site::load_sites()?;
+    backup::auto_snapshot("Before undoing the takeover")?;
+    let include = site::enabled_include_path().display().to_string();
+
+    let mut transaction = Transaction::new();
+    let mut kept = Vec::new();
+    for file in &record.files {
+        let current = std::fs::read_to_string(&file.path).ok();
+        // 导入的站点在 easyNginx 中保存过时，文件内容为生成的配置
+        let written = file.written.clone().or_else(|| {
+            sites.iter()
+                .find(|site| site.imported_from.as_ref() == Some(&file.path))
+                .map(site::config::render)
+        });
+        match plan_restore(file, current.as_deref(), written.as_deref(), &include) {
+            Restore::Unchanged => {}
+            Restore::Write(content) => transaction.write(file.path.clone(), content),
+            Restore::Keep => kept.push(file.path.clone()),
+        }
+    }
+    sites.retain(|site| site.imported_from.is_none());
+    transaction.write(site::enabled_include_path(), site::enabled_include(&sites));
+    transaction.write(site::store_path(), sites_toml(&sites)?);
+    transaction.remove(record_path());
+
+    // 用接管后的设置测试和重载（仍是被接管的 nginx）
+    commit(&transaction, &Settings::load().nginx, "after undoing the takeover")?;
+    for path in &kept {
+        eprintln!("[Takeover] {} was changed after the takeover and has been left as it is", path.display());
+    }
+    Settings::update(|settings| settings.nginx = record.previous_settings.clone())
+        .map_err(|e| format!("{}: {}", Settings::path().display(), e))?;
+    Ok((record.summary.sites, kept))
+}
+
+/// 查找已安装的 nginx 并为每个生成导入计划
+fn scan() -> TakeoverScanned {
+    let taken: Vec<String> = site::load_sites().unwrap_or_default().iter().map(|site| site.name.clone()).collect();
+    let candidates = detect().into_iter()
+        .map(|installation| {
+            let plan = nginx::load_tree(&installation.conf_path)
+                .map(|files| plan_import(&files, &installation.conf_path, &site::available_dir(), &taken))
+                .map_err(|e| e.to_string());
+            Candidate { installation, plan }
+        })
+        .collect();
+    TakeoverScanned { candidates, current: current() }
+}
+
+// ==============================================================================
+// Module
+// ==============================================================================
+
+pub struct TakeoverModule {
+    name: &'static str,
+    bus: Arc<RwLock<Option<Arc<MessageBus>>>>,
+}
+
+impl TakeoverModule {
+    pub fn new() -> Self {
+        Self {
+            name: "takeover",
+            bus: Arc::new(RwLock::new(None)),
+        }
+    }
+}
+
+impl Default for TakeoverModule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Module for TakeoverModule {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+    
+    async fn initialize(&mut self, bus: Arc<MessageBus>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        *self.bus.write().await = Some(bus.clone());
+        
+        let scan_request_type = bus.register_message_type::<TakeoverScanRequest>().await;
+        let request_type = bus.register_message_type::<TakeoverRequest>().await;
+        bus.register_message_type::<TakeoverScanned>().await;
+        bus.register_message_type::<TakeoverResponse>().await;
+        
+        bus.subscribe(scan_request_type, self.name().to_string()).await;
+        bus.subscribe(request_type, self.name().to_string()).await;
+        Ok(())
+    }
+    
+    async fn process_message(&self, envelope: MessageEnvelope) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let Some(bus) = self.bus.read().await.clone() else {
+            return Ok(());
+        };
+        if envelope.message_type == TypeId::of::<TakeoverScanRequest>() {
+            let scanned = tokio::task::spawn_blocking(scan).await?;
+            bus.publish(scanned).await?;
+        } else if envelope.message_type == TypeId::of::<TakeoverRequest>() {
+            if let Some(msg) = envelope.payload.as_any().downcast_ref::<TakeoverRequest>() {
+                let action = msg.action.clone();
+                let result = tokio::task::spawn_blocking(move || match &action {
+                    TakeoverAction::TakeOver { binary } => take_over(binary).map(|sites| (sites, Vec::new())),
+                    TakeoverAction::Undo => undo(),
+                }).await?;
+                match &result {
+                    Ok((sites, _)) => println!("[Takeover] {:?} done, {} site(s)", msg.action, sites.len()),
+                    Err(error) => eprintln!("[Takeover] {:?} failed: {}", msg.action, error),
+                }
+                let changed = result.is_ok();
+                let (sites, kept, error) = match result {
+                    Ok((sites, kept)) => (sites, kept, None),
+                    Err(error) => (Vec::new(), Vec::new(), Some(error)),
+                };
+                bus.publish(TakeoverResponse {
+                    id: msg.id,
+                    requester: msg.requester.clone(),
+                    action: msg.action.clone(),
+                    error,
+                    sites,
+                    kept,
+                }).await?;
+                if changed {
+                    bus.publish(SiteRefreshRequest).await?;
+                }
+            }
+        }
+        
+        Ok(())
+    }
+    
+    async fn shutdown(&mut self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        Ok(())
+    }
+}
+
+module_init!(TakeoverModule, "takeover");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::site::SiteType;
+
+    fn corpus(name: &str) -> String {
+        std::fs::read_to_string(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/corpus/nginx").join(name)).unwrap()
+    }
+
+    #[test]
+    fn plans_import_per_file() {
+        let dir = std::env::temp_dir().join(format!("easynginx-takeover-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("conf.d")).unwrap();
+        std::fs::write(dir.join("nginx.conf"), "events {}\nhttp {\n  include conf.d/*.conf;\n  server { listen 8080; root /srv; }\n}\n").unwrap();
+        std::fs::write(dir.join("fastcgi_params"), corpus("fastcgi_params")).unwrap();
+        for name in ["wordpress.conf", "reverse-proxy.conf"] {
+            std::fs::write(dir.join("conf.d").join(name), corpus(name)).unwrap();
+        }
+        std::fs::write(dir.join("conf.d/status.conf"), "server { listen 8081; return 204; }\n").unwrap();
+        std::fs::write(dir.join("conf.d/pair.conf"), "server { root /a; }\nserver { listen 81; root /b; }\n").unwrap();
+
+        let main = dir.join("nginx.conf");
+        let files = nginx::load_tree(&main).unwrap();
+        let plan = plan_import(&files, &main, Path::new("/nonexistent"), &["wordpress".to_string()]);
+        let imported: Vec<(&str, SiteType)> = plan.sites.iter().map(|site| (site.name.as_str(), site.site_type())).collect();
+        assert_eq!(imported, [("reverse-proxy", SiteType::Proxy), ("wordpress-2", SiteType::Php)]);
+        assert!(plan.sites.iter().all(|site| site.is_managed() && site.imported_from.as_ref().is_some_and(|path| path.starts_with(dir.join("conf.d")))));
+        let skipped: Vec<(String, SkipReason)> = plan.skipped.iter()
+            .map(|skipped| (skipped.path.file_name().unwrap().to_string_lossy().into_owned(), skipped.reason.clone()))
+            .collect();
+        assert_eq!(skipped, [
+            ("nginx.conf".to_string(), SkipReason::MainConfig),
+            ("pair.conf".to_string(), SkipReason::SeveralSites(2)),
+            ("status.conf".to_string(), SkipReason::Custom),
+        ]);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn adds_include_to_http_block() {
+        let mut config = Config::parse("events {}\nhttp {\n\tinclude mime.types;\n\tserver { listen 80; }\n}\n").unwrap();
+        add_include(&mut config, "/home/me/.config/easyNginx/nginx/sites-enabled.conf").unwrap();
+        assert_eq!(
+            config.to_string(),
+            "events {}\nhttp {\n\tinclude mime.types;\n\tserver { listen 80; }\n\tinclude /home/me/.config/easyNginx/nginx/sites-enabled.conf;\n}\n"
+        );
+        assert!(add_include(&mut Config::parse("events {}\n").unwrap(), "x.conf").is_err());
+    }
+
+    #[test]
+    fn undo_keeps_manual_edits() {
+        let include = "/home/me/.config/easyNginx/nginx/sites-enabled.conf";
+        let original = "events {}\nhttp {\n    include mime.types;\n}\n";
+        let mut config = Config::parse(original).unwrap();
+        add_include(&mut config, include).unwrap();
+        let written = config.to_string();
+        let main = OriginalFile { path: "/etc/nginx/nginx.conf".into(), content: original.into(), written: Some(written.clone()) };
+
+        let restore = |file: &OriginalFile, current: Option<&str>, written: Option<&str>| plan_restore(file, current, written, include);
+        assert_eq!(restore(&main, Some(original), Some(&written)), Restore::Unchanged);
+        assert_eq!(restore(&main, Some(&written), Some(&written)), Restore::Write(original.into()));
+        assert_eq!(restore(&main, None, Some(&written)), Restore::Write(original.into()));
+        // 接管后手动修改过：保留修改，只移除 include
+        let edited = written.replace("include mime.types;", "include mime.types;\n    gzip on;");
+        assert_eq!(
+            restore(&main, Some(&edited), Some(&written)),
+            Restore::Write("events {}\nhttp {\n    include mime.types;\n    gzip on;\n}\n".into())
+        );
+        // include 已被手动删除
+        let removed = "events {}\nhttp {\n    gzip on;\n}\n";
+        assert_eq!(restore(&main, Some(removed), Some(&written)), Restore::Keep);
+
+        // 导入的站点文件：easyNginx 生成的内容可以恢复，手动修改的保留
+        let imported = OriginalFile { path: "/etc/nginx/conf.d/blog.conf".into(), content: "server { listen 80; }\n".into(), written: None };
+        let rendered = "server {\n    listen 8080;\n}\n";
+        assert_eq!(restore(&imported, Some(rendered), Some(rendered)), Restore::Write(imported.content.clone()));
+        assert_eq!(restore(&imported, Some("server { listen 81; }\n"), Some(rendered)), Restore::Keep);
+    }
+}
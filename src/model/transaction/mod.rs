@@ -0,0 +1,227 @@
+// MIT License
+// 
+// Copyright (c) 2026 Laffinty
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+
+
+// ==============================================================================
+// Transaction - 事务式写入配置
+// ==============================================================================
+// 一次修改要写入的文件先收集在 Transaction 中，提交时：
+//   1. 把修改后的配置树暂存到临时目录，运行 nginx -t -c 测试（见 stage）；
+//      测试未通过时不写入任何文件
+//   2. 逐个原子替换文件（写临时文件再重命名），保留原来的内容
+//   3. nginx 正在运行时重载，并确认它仍在服务（见 verify）
+//   4. 重载后 nginx 没有正常服务时恢复原来的文件，再重载一次
+// 没有安装 nginx 时只替换文件。
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use crate::model::backup::store::write_atomic;
+use crate::model::controller::{locate_binary, NginxController, SystemNginx};
+use crate::model::settings::NginxSettings;
+
+pub mod stage;
+pub mod verify;
+
+/// 路径 -> 新内容（None 表示删除）
+pub type Writes = BTreeMap<PathBuf, Option<Vec<u8>>>;
+
+/// 提交的结果
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReloadOutcome {
+    /// 已写入并重载，nginx 仍在正常服务
+    Reloaded,
+    /// 已写入；nginx 没有运行，不需要重载
+    NotRunning,
+    /// 修改后的配置未通过 nginx -t，附带 nginx 的输出；没有写入任何文件
+    TestFailed(String),
+    /// 重载后 nginx 没有正常服务，已恢复原来的文件并重新加载
+    RolledBack(String),
+    /// 重载失败且无法恢复原来的文件，修改后的文件仍在磁盘上
+    ReloadFailed(String),
+}
+
+impl ReloadOutcome {
+    /// 修改后的文件是否留在了磁盘上
+    pub fn is_applied(&self) -> bool {
+        matches!(self, Self::Reloaded | Self::NotRunning | Self::ReloadFailed(_))
+    }
+}
+
+/// 一组要一起写入的文件
+#[derive(Clone, Debug, Default)]
+pub struct Transaction {
+    writes: Writes,
+}
+
+/// 已替换的文件及其原来的内容
+#[derive(Debug)]
+pub struct Swapped {
+    originals: Vec<(PathBuf, Option<Vec<u8>>)>,
+}
+
+impl Transaction {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn write(&mut self, path: impl Into<PathBuf>, data: impl Into<Vec<u8>>) {
+        self.writes.insert(path.into(), Some(data.into()));
+    }
+
+    pub fn remove(&mut self, path: impl Into<PathBuf>) {
+        self.writes.insert(path.into(), None);
+    }
+
+    pub fn writes(&self) -> &Writes {
+        &self.writes
+    }
+
+    /// 只替换文件，不测试也不重载；失败时恢复已替换的文件
+    pub fn apply(&self) -> Result<Swapped, String> {
+        let mut swapped = Swapped { originals: Vec::new() };
+        for (path, data) in &self.writes {
+            let original = match std::fs::read(path) {
+                Ok(original) => Some(original),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+                Err(e) => {
+                    let _ = swapped.rollback();
+                    return Err(format!("{}: {}", path.display(), e));
+                }
+            };
+            swapped.originals.push((path.clone(), original));
+            if let Err(e) = put(path, data.as_deref()) {
+                let _ = swapped.rollback();
+                return Err(format!("{}: {}", path.display(), e));
+            }
+        }
+        Ok(swapped)
+    }
+
+    /// 测试、替换文件、重载并确认 nginx 仍在服务；Err 为替换文件失败（已恢复）
+    pub fn commit(&self, settings: &NginxSettings) -> Result<ReloadOutcome, String> {
+        // 没有安装 nginx 时无法测试，只写入文件
+        if locate_binary(&settings.binary).is_none() {
+            self.apply()?;
+            return Ok(ReloadOutcome::NotRunning);
+        }
+        let nginx = SystemNginx::from_settings(settings);
+        if let Err(output) = self.test(&nginx) {
+            return Ok(ReloadOutcome::TestFailed(output));
+        }
+
+        let master = nginx.running_pid();
+        let before = master.map(verify::workers).unwrap_or_default();
+        let swapped = self.apply()?;
+        let Some(master) = master else {
+            return Ok(ReloadOutcome::NotRunning);
+        };
+        let result = nginx.reload()
+            .and_then(|()| verify::wait_until_serving(&nginx, master, &before, settings.probe_url.as_deref()));
+        let Err(error) = result else {
+            return Ok(ReloadOutcome::Reloaded);
+        };
+
+        eprintln!("[Transaction] nginx is not serving after the reload, rolling back: {}", error);
+        if let Err(e) = swapped.rollback() {
+            return Ok(ReloadOutcome::ReloadFailed(format!("{}; restoring the previous files failed: {}", error, e)));
+        }
+        // master 已退出时无法重载，原来的文件已恢复，下次启动时使用
+        if nginx.running_pid().is_some() {
+            if let Err(e) = nginx.reload() {
+                return Ok(ReloadOutcome::RolledBack(format!("{}; reloading the previous configuration failed: {}", error, e)));
+            }
+        }
+        Ok(ReloadOutcome::RolledBack(error))
+    }
+
+    /// 暂存修改后的配置树并运行 nginx -t -c；返回 nginx 的输出（路径已换回原路径）
+    fn test(&self, nginx: &SystemNginx) -> Result<(), String> {
+        let info = nginx.build_info()?;
+        let (_, main) = nginx.paths(&info);
+        let staged = stage::stage(&main, &self.writes, &staging_dir()).map_err(|e| e.to_string())?;
+        let report = nginx.test_config(&staged.main);
+        if report.success {
+            Ok(())
+        } else {
+            Err(staged.unstage(&report.output))
+        }
+    }
+}
+
+impl Swapped {
+    /// 恢复原来的文件
+    pub fn rollback(&self) -> std::io::Result<()> {
+        for (path, original) in self.originals.iter().rev() {
+            put(path, original.as_deref())?;
+        }
+        Ok(())
+    }
+}
+
+fn put(path: &std::path::Path, data: Option<&[u8]>) -> std::io::Result<()> {
+    match data {
+        Some(data) => write_atomic(path, data),
+        None => match std::fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        },
+    }
+}
+
+/// 每次提交使用新的暂存目录
+fn staging_dir() -> PathBuf {
+    static NEXT: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(1);
+    let n = NEXT.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    let dir = std::env::temp_dir().join(format!("easynginx-stage-{}-{}", std::process::id(), n));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn applies_and_rolls_back() {
+        let dir = std::env::temp_dir().join(format!("easynginx-transaction-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("kept.conf"), "old").unwrap();
+        std::fs::write(dir.join("removed.conf"), "removed").unwrap();
+
+        let mut transaction = Transaction::new();
+        transaction.write(dir.join("kept.conf"), "new");
+        transaction.write(dir.join("sub/added.conf"), "added");
+        transaction.remove(dir.join("removed.conf"));
+        let swapped = transaction.apply().unwrap();
+        assert_eq!(std::fs::read_to_string(dir.join("kept.conf")).unwrap(), "new");
+        assert_eq!(std::fs::read_to_string(dir.join("sub/added.conf")).unwrap(), "added");
+        assert!(!dir.join("removed.conf").exists());
+
+        swapped.rollback().unwrap();
+        assert_eq!(std::fs::read_to_string(dir.join("kept.conf")).unwrap(), "old");
+        assert!(!dir.join("sub/added.conf").exists());
+        assert_eq!(std::fs::read_to_string(dir.join("removed.conf")).unwrap(), "removed");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,260 @@
+// MIT License
+// 
+// Copyright (c) 2026 Laffinty
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+
+
+// ==============================================================================
+// stage - 暂存配置树
+// ==============================================================================
+// 把主配置文件及其 include 的文件（叠加事务中的修改）复制到临时目录，原来的
+// /a/b.conf 对应 <dir>/a/b.conf。include 改写为暂存目录中的绝对路径，证书等相对
+// 于配置目录的文件改写为原来的绝对路径，这样 nginx -t -c <dir>/<主配置> 测试的
+// 就是修改后的配置，而磁盘上的文件保持不变。
+
+use std::path::{Component, Path, PathBuf};
+use crate::model::nginx::include::{glob_match, has_wildcard};
+use crate::model::nginx::{resolve_include, Config, LoadError, Node};
+use super::Writes;
+
+/// 参数是相对于配置目录的文件的指令（include 除外）
+const FILE_DIRECTIVES: &[&str] = &[
+    "ssl_certificate",
+    "ssl_certificate_key",
+    "ssl_trusted_certificate",
+    "ssl_client_certificate",
+    "ssl_dhparam",
+    "ssl_crl",
+    "ssl_password_file",
+    "ssl_stapling_file",
+    "auth_basic_user_file",
+];
+
+/// 暂存的配置树；drop 时删除暂存目录
+#[derive(Debug)]
+pub struct Staged {
+    pub dir: PathBuf,
+    /// 暂存的主配置文件
+    pub main: PathBuf,
+    /// (暂存路径, 原路径)
+    files: Vec<(PathBuf, PathBuf)>,
+}
+
+impl Staged {
+    /// 暂存的文件（原路径）
+    pub fn originals(&self) -> impl Iterator<Item = &Path> {
+        self.files.iter().map(|(_, original)| original.as_path())
+    }
+
+    /// 把 nginx 输出中的暂存路径换回原路径
+    pub fn unstage(&self, text: &str) -> String {
+        let mut files: Vec<&(PathBuf, PathBuf)> = self.files.iter().collect();
+        // 先替换较长的路径，避免替换掉另一个路径的开头
+        files.sort_by_key(|(staged, _)| std::cmp::Reverse(staged.as_os_str().len()));
+        files.iter().fold(text.to_string(), |text, (staged, original)| {
+            text.replace(&staged.display().to_string(), &original.display().to_string())
+        })
+    }
+}
+
+impl Drop for Staged {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// 在 dir 中暂存 main 的配置树；writes 中的内容优先于磁盘上的文件
+pub fn stage(main: &Path, writes: &Writes, dir: &Path) -> Result<Staged, LoadError> {
+    let prefix = main.parent().map(Path::to_path_buf).unwrap_or_default();
+    let mut files = Vec::new();
+    load(main, &prefix, writes, &mut files)?;
+
+    let mut staged = Staged { dir: dir.to_path_buf(), main: staged_path(dir, main), files: Vec::new() };
+    for (path, mut config) in files {
+        rewrite(&mut config.nodes, &prefix, dir);
+        let target = staged_path(dir, &path);
+        let io_error = |e: std::io::Error| LoadError { path: target.clone(), message: e.to_string() };
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent).map_err(io_error)?;
+        }
+        std::fs::write(&target, config.to_string()).map_err(io_error)?;
+        staged.files.push((target, path));
+    }
+    Ok(staged)
+}
+
+/// 与 include::load_tree 相同，但读取 writes 中的内容
+fn load(path: &Path, prefix: &Path, writes: &Writes, files: &mut Vec<(PathBuf, Config)>) -> Result<(), LoadError> {
+    if files.iter().any(|(loaded, _)| loaded == path) {
+        return Ok(());
+    }
+    let error = |message: String| LoadError { path: path.to_path_buf(), message };
+    let text = match writes.get(path) {
+        Some(Some(data)) => String::from_utf8_lossy(data).into_owned(),
+        Some(None) => return Err(error("the file is being removed".into())),
+        None => std::fs::read_to_string(path).map_err(|e| error(e.to_string()))?,
+    };
+    let config = Config::parse(&text).map_err(|e| error(e.to_string()))?;
+    let mut includes = Vec::new();
+    config.walk(&mut |directive| {
+        if directive.name() == "include" {
+            if let Some(pattern) = directive.args().first() {
+                includes.push(pattern.clone());
+            }
+        }
+    });
+    files.push((path.to_path_buf(), config));
+
+    for pattern in includes {
+        let paths = resolve(&pattern, prefix, writes);
+        if paths.is_empty() && !has_wildcard(&pattern) {
+            return Err(error(format!("included file '{}' not found", pattern)));
+        }
+        for included in paths {
+            load(&included, prefix, writes, files)?;
+        }
+    }
+    Ok(())
+}
+
+/// resolve_include，并计入事务中新建和删除的文件
+fn resolve(pattern: &str, prefix: &Path, writes: &Writes) -> Vec<PathBuf> {
+    let full = absolute(pattern, prefix);
+    let mut paths: Vec<PathBuf> = resolve_include(pattern, prefix)
+        .into_iter()
+        .filter(|path| !matches!(writes.get(path), Some(None)))
+        .collect();
+    for (path, data) in writes {
+        if data.is_some() && !paths.contains(path) && path_matches(&full, path) {
+            paths.push(path.clone());
+        }
+    }
+    paths.sort();
+    paths
+}
+
+fn absolute(path: &str, prefix: &Path) -> PathBuf {
+    let path = Path::new(path);
+    if path.is_absolute() { path.to_path_buf() } else { prefix.join(path) }
+}
+
+/// 逐级匹配带通配符的路径
+fn path_matches(pattern: &Path, path: &Path) -> bool {
+    let pattern: Vec<Component> = pattern.components().collect();
+    let path: Vec<Component> = path.components().collect();
+    pattern.len() == path.len() && pattern.iter().zip(&path).all(|(p, c)| match (p, c) {
+        (Component::Normal(p), Component::Normal(c)) => glob_match(&p.to_string_lossy(), &c.to_string_lossy()),
+        _ => p == c,
+    })
+}
+
+/// 原路径在暂存目录中的位置
+fn staged_path(dir: &Path, path: &Path) -> PathBuf {
+    let mut staged = dir.to_path_buf();
+    for component in path.components() {
+        match component {
+            // Windows 的盘符变成一级目录
+            Component::Prefix(prefix) => staged.push(prefix.as_os_str().to_string_lossy().replace(':', "")),
+            Component::RootDir => {}
+            other => staged.push(other.as_os_str()),
+        }
+    }
+    staged
+}
+
+fn rewrite(nodes: &mut [Node], prefix: &Path, dir: &Path) {
+    for node in nodes {
+        let Node::Directive(directive) = node else {
+            continue;
+        };
+        let name = directive.name();
+        let first = directive.args().into_iter().next();
+        if let Some(arg) = first {
+            if name == "include" {
+                let staged = staged_path(dir, &absolute(&arg, prefix));
+                directive.set_args(&[&staged.display().to_string()]);
+            } else if FILE_DIRECTIVES.contains(&name.as_str()) && is_relative_file(&arg) {
+                let mut args = directive.args();
+                args[0] = absolute(&arg, prefix).display().to_string();
+                directive.set_args(&args.iter().map(String::as_str).collect::<Vec<_>>());
+            }
+        }
+        if let Some(block) = directive.block_mut() {
+            rewrite(&mut block.nodes, prefix, dir);
+        }
+    }
+}
+
+/// 相对路径的文件（不含变量，也不是 data:、engine: 等）
+fn is_relative_file(arg: &str) -> bool {
+    !arg.contains(['$', ':']) && Path::new(arg).is_relative()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stages_tree_with_pending_writes() {
+        let root = std::env::temp_dir().join(format!("easynginx-stage-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let conf = root.join("conf");
+        std::fs::create_dir_all(conf.join("conf.d")).unwrap();
+        std::fs::write(conf.join("nginx.conf"), "events {}\nhttp {\n    include conf.d/*.conf;\n    include sites.conf;\n}\n").unwrap();
+        std::fs::write(conf.join("conf.d/a.conf"), "server { listen 81; ssl_certificate certs/a.pem; }\n").unwrap();
+        std::fs::write(conf.join("conf.d/old.conf"), "server { listen 82; }\n").unwrap();
+        std::fs::write(conf.join("sites.conf"), "").unwrap();
+
+        let site = root.join("sites/blog.conf");
+        let mut writes = Writes::new();
+        writes.insert(conf.join("conf.d/old.conf"), None);
+        writes.insert(conf.join("conf.d/new.conf"), Some(b"server { listen 83; }\n".to_vec()));
+        writes.insert(conf.join("sites.conf"), Some(format!("include {};\n", site.display()).into_bytes()));
+        writes.insert(site.clone(), Some(b"server { listen 84; }\n".to_vec()));
+
+        let dir = root.join("staging");
+        let staged = stage(&conf.join("nginx.conf"), &writes, &dir).unwrap();
+        let originals: Vec<&Path> = staged.originals().collect();
+        assert_eq!(originals, [
+            conf.join("nginx.conf").as_path(),
+            conf.join("conf.d/a.conf").as_path(),
+            conf.join("conf.d/new.conf").as_path(),
+            conf.join("sites.conf").as_path(),
+            site.as_path(),
+        ]);
+
+        let main = std::fs::read_to_string(&staged.main).unwrap();
+        assert!(main.contains(&format!("include {};", staged_path(&dir, &conf.join("conf.d/*.conf")).display())));
+        let a = std::fs::read_to_string(staged_path(&dir, &conf.join("conf.d/a.conf"))).unwrap();
+        assert!(a.contains(&format!("ssl_certificate {};", conf.join("certs/a.pem").display())));
+        assert!(!staged_path(&dir, &conf.join("conf.d/old.conf")).exists());
+        // 暂存目录中的 glob 只匹配暂存的文件
+        assert_eq!(resolve_include(&staged_path(&dir, &conf.join("conf.d/*.conf")).display().to_string(), Path::new("/")).len(), 2);
+        assert_eq!(std::fs::read_to_string(conf.join("conf.d/old.conf")).unwrap(), "server { listen 82; }\n");
+
+        let output = format!("nginx: [emerg] bad in {}:1", staged_path(&dir, &site).display());
+        assert_eq!(staged.unstage(&output), format!("nginx: [emerg] bad in {}:1", site.display()));
+
+        drop(staged);
+        assert!(!dir.exists());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,151 @@
+// MIT License
+// 
+// Copyright (c) 2026 Laffinty
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+
+
+// ==============================================================================
+// verify - 确认重载后 nginx 仍在服务
+// ==============================================================================
+// 重载后 master 进程必须还是原来的进程；新配置加载成功时 master 会启动新的
+// worker，加载失败（如端口被占用）时只保留旧的 worker 并把错误写到日志，
+// 所以在 Linux 上等待新 worker 出现。设置了 probe_url 时还要求本地 HTTP
+// 请求得到 5xx 以外的响应。
+
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+use crate::model::controller::SystemNginx;
+
+/// 等待 nginx 使用新配置的最长时间
+const VERIFY_TIMEOUT: Duration = Duration::from_secs(5);
+/// 单次 HTTP 探测的超时
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// master 当前的子进程；只在 Linux 上可用，其它平台返回空
+pub fn workers(master: u32) -> Vec<u32> {
+    if cfg!(target_os = "linux") {
+        crate::model::monitor::process::child_pids(master)
+    } else {
+        Vec::new()
+    }
+}
+
+/// 重载后等待 nginx 使用新配置服务；before 为重载前的 worker，返回失败原因
+pub fn wait_until_serving(nginx: &SystemNginx, master: u32, before: &[u32], probe_url: Option<&str>) -> Result<(), String> {
+    let deadline = Instant::now() + VERIFY_TIMEOUT;
+    loop {
+        if nginx.running_pid() != Some(master) {
+            return Err(format!("nginx (pid {}) exited after the reload", master));
+        }
+        if before.is_empty() || workers(master).iter().any(|pid| !before.contains(pid)) {
+            break;
+        }
+        if Instant::now() >= deadline {
+            return Err("nginx did not start new worker processes, see its error log".into());
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+
+    let Some(url) = probe_url else {
+        return Ok(());
+    };
+    loop {
+        let result = probe(url);
+        if matches!(result, Ok(status) if status < 500) {
+            return Ok(());
+        }
+        if Instant::now() >= deadline {
+            return Err(match result {
+                Ok(status) => format!("{} answered with HTTP {}", url, status),
+                Err(error) => format!("{}: {}", url, error),
+            });
+        }
+        std::thread::sleep(Duration::from_millis(200));
+    }
+}
+
+/// 发送 GET 请求并返回状态码；只支持 http://host[:port][/path]
+pub fn probe(url: &str) -> Result<u16, String> {
+    let rest = url.strip_prefix("http://").ok_or("only http:// URLs can be probed")?;
+    let (authority, path) = match rest.find('/') {
+        Some(i) => (&rest[..i], &rest[i..]),
+        None => (rest, "/"),
+    };
+    let address = if authority.rsplit_once(':').is_some_and(|(_, port)| port.parse::<u16>().is_ok()) {
+        authority.to_string()
+    } else {
+        format!("{}:80", authority)
+    };
+    let address = address.to_socket_addrs()
+        .map_err(|e| e.to_string())?
+        .next()
+        .ok_or_else(|| format!("cannot resolve {}", authority))?;
+
+    let mut stream = TcpStream::connect_timeout(&address, PROBE_TIMEOUT).map_err(|e| e.to_string())?;
+    stream.set_read_timeout(Some(PROBE_TIMEOUT)).map_err(|e| e.to_string())?;
+    stream.set_write_timeout(Some(PROBE_TIMEOUT)).map_err(|e| e.to_string())?;
+    let request = format!("GET {} HTTP/1.0\r\nHost: {}\r\nUser-Agent: easyNginx\r\nConnection: close\r\n\r\n", path, authority);
+    stream.write_all(request.as_bytes()).map_err(|e| e.to_string())?;
+
+    // 只需要状态行
+    let mut head = Vec::new();
+    let mut buf = [0u8; 256];
+    while !head.contains(&b'\n') && head.len() < 1024 {
+        match stream.read(&mut buf).map_err(|e| e.to_string())? {
+            0 => break,
+            n => head.extend_from_slice(&buf[..n]),
+        }
+    }
+    let head = String::from_utf8_lossy(&head);
+    let line = head.lines().next().unwrap_or_default();
+    line.split_whitespace()
+        .nth(1)
+        .filter(|_| line.starts_with("HTTP/"))
+        .and_then(|status| status.parse().ok())
+        .ok_or_else(|| format!("unexpected response: {:?}", line))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    #[test]
+    fn probes_local_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = std::thread::spawn(move || {
+            for status in ["200 OK", "502 Bad Gateway"] {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = [0u8; 1024];
+                let n = stream.read(&mut request).unwrap();
+                assert!(String::from_utf8_lossy(&request[..n]).starts_with("GET /health HTTP/1.0\r\n"));
+                write!(stream, "HTTP/1.1 {}\r\nContent-Length: 0\r\n\r\n", status).unwrap();
+            }
+        });
+        let url = format!("http://127.0.0.1:{}/health", port);
+        assert_eq!(probe(&url), Ok(200));
+        assert_eq!(probe(&url), Ok(502));
+        server.join().unwrap();
+        assert!(probe("https://127.0.0.1/").is_err());
+    }
+}
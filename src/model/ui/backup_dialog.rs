@@ -0,0 +1,317 @@
+// MIT License
+// 
+// Copyright (c) 2026 Laffinty
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+
+// ==============================================================================
+// Backup Dialog - 备份、比较与恢复
+// ==============================================================================
+// 列出全部备份（最新的在前），可以手动备份、比较任意两个备份（或与当前文件比较）
+// 以及确认后恢复某个备份。
+
+use eframe::egui;
+use std::time::{Duration, SystemTime};
+use crate::model::backup::{BackupAction, BackupRequest, BackupResponse, BackupResult, BackupsListed, DiffLine, FileDiff, FileStatus, Snapshot};
+use crate::model::l18n::{format_message, Key, Language, TranslationArgs};
+use super::main_window::format_elapsed;
+use super::nginx_result::NginxResultDialog;
+use super::site_editor::COLOR_ERROR;
+
+const DIALOG_WIDTH: f32 = 720.0;
+const LIST_HEIGHT: f32 = 220.0;
+const DIFF_HEIGHT: f32 = 280.0;
+const COLOR_ADDED: egui::Color32 = egui::Color32::from_rgb(40, 150, 60);
+
+#[derive(Default)]
+pub struct BackupDialog {
+    open: bool,
+    /// None 表示正在读取
+    snapshots: Option<Vec<Snapshot>>,
+    note: String,
+    /// 比较的起点；也是要恢复的备份
+    from: Option<String>,
+    /// 比较的终点，None 表示当前文件
+    to: Option<String>,
+    confirm_restore: bool,
+    /// 等待响应的请求编号
+    pending: Option<u64>,
+    response: Option<BackupResponse>,
+    diff: Option<Vec<FileDiff>>,
+}
+
+fn ago(created_at: u64, language: &Language, translate_fn: &dyn Fn(Key) -> String) -> String {
+    let created = SystemTime::UNIX_EPOCH + Duration::from_secs(created_at);
+    let elapsed = SystemTime::now().duration_since(created).unwrap_or_default();
+    let args = TranslationArgs::new().text("elapsed", &format_elapsed(elapsed, language, translate_fn));
+    format_message(&translate_fn(Key::BackupTimeAgo), language, &args)
+}
+
+impl BackupDialog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 打开对话框；调用方随后发布 BackupListRequest
+    pub fn open(&mut self) {
+        *self = Self {
+            open: true,
+            ..Self::default()
+        };
+    }
+
+    pub fn handle_list(&mut self, listed: &BackupsListed) {
+        if !self.open {
+            return;
+        }
+        let exists = |id: &Option<String>| id.as_ref().is_none_or(|id| listed.snapshots.iter().any(|snapshot| &snapshot.id == id));
+        if !exists(&self.from) {
+            self.from = None;
+        }
+        if !exists(&self.to) {
+            self.to = None;
+        }
+        self.snapshots = Some(listed.snapshots.clone());
+    }
+
+    pub fn handle_response(&mut self, response: &BackupResponse) {
+        if self.pending != Some(response.id) {
+            return;
+        }
+        self.pending = None;
+        match &response.result {
+            Ok(BackupResult::Diff(diff)) => {
+                self.diff = Some(diff.clone());
+                self.response = None;
+            }
+            Ok(BackupResult::Created(_)) => {
+                self.note.clear();
+                self.response = Some(response.clone());
+            }
+            _ => self.response = Some(response.clone()),
+        }
+    }
+
+    /// 返回需要发布的请求
+    pub fn ui(&mut self, ctx: &egui::Context, language: &Language, translate_fn: &dyn Fn(Key) -> String) -> Option<BackupRequest> {
+        if !self.open {
+            return None;
+        }
+
+        let mut action = None;
+        let mut close = false;
+        egui::Window::new(translate_fn(Key::MenuBackupConfig))
+            .id(egui::Id::new("backup_dialog"))
+            .collapsible(false)
+            .resizable(false)
+            .default_width(DIALOG_WIDTH)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.set_width(DIALOG_WIDTH);
+                let idle = self.pending.is_none();
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::singleline(&mut self.note)
+                        .hint_text(translate_fn(Key::BackupNoteHint))
+                        .desired_width(DIALOG_WIDTH - 160.0));
+                    if ui.add_enabled(idle, egui::Button::new(translate_fn(Key::BackupCreate))).clicked() {
+                        action = Some(BackupAction::Create { note: self.note.clone() });
+                    }
+                });
+                ui.add_space(8.0);
+
+                match &self.snapshots {
+                    None => {
+                        ui.spinner();
+                    }
+                    Some(snapshots) if snapshots.is_empty() => {
+                        ui.label(translate_fn(Key::BackupNone));
+                    }
+                    Some(snapshots) => {
+                        let snapshots = snapshots.clone();
+                        self.render_list(ui, &snapshots, language, translate_fn);
+                        ui.add_space(8.0);
+                        if let Some(selected) = self.render_actions(ui, &snapshots, idle, language, translate_fn) {
+                            action = Some(selected);
+                        }
+                    }
+                }
+
+                ui.add_space(8.0);
+                if !idle {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(translate_fn(Key::NginxResultWorking));
+                    });
+                } else if let Some(response) = &self.response {
+                    Self::render_response(ui, response, language, translate_fn);
+                }
+                if let Some(diff) = &self.diff {
+                    Self::render_diff(ui, diff, language, translate_fn);
+                }
+
+                ui.add_space(12.0);
+                ui.separator();
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.add_enabled(idle, egui::Button::new(translate_fn(Key::BackupClose))).clicked() {
+                        close = true;
+                    }
+                });
+            });
+
+        if close || (self.pending.is_none() && ctx.input(|i| i.key_pressed(egui::Key::Escape))) {
+            self.open = false;
+        }
+        let action = action?;
+        if matches!(action, BackupAction::Diff { .. }) {
+            self.diff = None;
+        }
+        self.response = None;
+        let id = crate::model::site::next_request_id();
+        self.pending = Some(id);
+        Some(BackupRequest::new(id, action, "ui"))
+    }
+
+    fn render_list(&mut self, ui: &mut egui::Ui, snapshots: &[Snapshot], language: &Language, translate_fn: &dyn Fn(Key) -> String) {
+        egui::ScrollArea::vertical()
+            .id_source("backup_list")
+            .max_height(LIST_HEIGHT)
+            .show(ui, |ui| {
+                egui::Grid::new("backup_grid").num_columns(5).striped(true).spacing([12.0, 4.0]).show(ui, |ui| {
+                    ui.strong(translate_fn(Key::BackupColumnFrom));
+                    ui.strong(translate_fn(Key::BackupColumnTo));
+                    ui.strong(translate_fn(Key::BackupColumnTime));
+                    ui.strong(translate_fn(Key::BackupColumnAuthor));
+                    ui.strong(translate_fn(Key::BackupColumnSummary));
+                    ui.end_row();
+
+                    ui.label("");
+                    ui.radio_value(&mut self.to, None, "");
+                    ui.label("");
+                    ui.label("");
+                    ui.label(translate_fn(Key::BackupCurrent));
+                    ui.end_row();
+
+                    for snapshot in snapshots {
+                        let id = Some(snapshot.id.clone());
+                        ui.radio_value(&mut self.from, id.clone(), "");
+                        ui.radio_value(&mut self.to, id, "");
+                        ui.label(ago(snapshot.created_at, language, translate_fn));
+                        ui.label(&snapshot.author);
+                        let response = ui.horizontal(|ui| {
+                            ui.label(&snapshot.summary);
+                            if !snapshot.note.is_empty() {
+                                ui.weak(&snapshot.note);
+                            }
+                        }).response;
+                        if !snapshot.changed.is_empty() {
+                            let changed: Vec<String> = snapshot.changed.iter().map(|path| path.display().to_string()).collect();
+                            response.on_hover_text(changed.join("\n"));
+                        }
+                        ui.end_row();
+                    }
+                });
+            });
+    }
+
+    fn render_actions(&mut self, ui: &mut egui::Ui, snapshots: &[Snapshot], idle: bool, language: &Language, translate_fn: &dyn Fn(Key) -> String) -> Option<BackupAction> {
+        let from = self.from.clone()?;
+        if self.confirm_restore {
+            let created_at = snapshots.iter().find(|snapshot| snapshot.id == from).map(|snapshot| snapshot.created_at).unwrap_or_default();
+            let args = TranslationArgs::new().text("time", &ago(created_at, language, translate_fn));
+            ui.label(format_message(&translate_fn(Key::BackupRestoreConfirm), language, &args));
+            let mut action = None;
+            ui.horizontal(|ui| {
+                if ui.add_enabled(idle, egui::Button::new(translate_fn(Key::BackupRestoreYes))).clicked() {
+                    action = Some(BackupAction::Restore { id: from.clone() });
+                    self.confirm_restore = false;
+                }
+                if ui.button(translate_fn(Key::BackupRestoreNo)).clicked() {
+                    self.confirm_restore = false;
+                }
+            });
+            return action;
+        }
+        let mut action = None;
+        ui.horizontal(|ui| {
+            if ui.add_enabled(idle, egui::Button::new(translate_fn(Key::BackupCompare))).clicked() {
+                action = Some(BackupAction::Diff { from: from.clone(), to: self.to.clone() });
+            }
+            if ui.add_enabled(idle, egui::Button::new(translate_fn(Key::BackupRestore))).clicked() {
+                self.confirm_restore = true;
+            }
+        });
+        action
+    }
+
+    fn render_response(ui: &mut egui::Ui, response: &BackupResponse, language: &Language, translate_fn: &dyn Fn(Key) -> String) {
+        match &response.result {
+            Ok(BackupResult::Created(_)) => {
+                ui.label(translate_fn(Key::BackupCreated));
+            }
+            Ok(BackupResult::Restored) => {
+                ui.label(translate_fn(Key::BackupRestored));
+            }
+            Ok(BackupResult::Diff(_)) => {}
+            Err(error) => {
+                let args = TranslationArgs::new().text("error", error);
+                ui.colored_label(COLOR_ERROR, format_message(&translate_fn(Key::BackupFailed), language, &args));
+            }
+        }
+        if let Some(report) = &response.report {
+            NginxResultDialog::render_report(ui, report, language, translate_fn);
+        }
+    }
+
+    fn render_diff(ui: &mut egui::Ui, diff: &[FileDiff], language: &Language, translate_fn: &dyn Fn(Key) -> String) {
+        if diff.is_empty() {
+            ui.label(translate_fn(Key::BackupNoChanges));
+            return;
+        }
+        egui::ScrollArea::vertical()
+            .id_source("backup_diff")
+            .max_height(DIFF_HEIGHT)
+            .show(ui, |ui| {
+                for file in diff {
+                    let status = match file.status {
+                        FileStatus::Added => Key::BackupFileAdded,
+                        FileStatus::Removed => Key::BackupFileRemoved,
+                        FileStatus::Modified => Key::BackupFileModified,
+                    };
+                    ui.horizontal(|ui| {
+                        ui.strong(file.path.display().to_string());
+                        ui.weak(translate_fn(status));
+                    });
+                    for line in &file.lines {
+                        let text = match line {
+                            DiffLine::Context(text) => egui::RichText::new(format!("  {}", text)),
+                            DiffLine::Added(text) => egui::RichText::new(format!("+ {}", text)).color(COLOR_ADDED),
+                            DiffLine::Removed(text) => egui::RichText::new(format!("- {}", text)).color(COLOR_ERROR),
+                            DiffLine::Skipped(count) => {
+                                let args = TranslationArgs::new().count("count", *count);
+                                egui::RichText::new(format_message(&translate_fn(Key::BackupLinesSkipped), language, &args)).weak()
+                            }
+                        };
+                        ui.label(text.monospace());
+                    }
+                    ui.add_space(6.0);
+                }
+            });
+    }
+}
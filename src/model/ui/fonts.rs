@@ -0,0 +1,122 @@
+// MIT License
+// 
+// Copyright (c) 2026 Laffinty
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+
+// ==============================================================================
+// Fonts - 内置字体与系统字体回退
+// ==============================================================================
+// 内置的文泉驿微米黑覆盖中文，但日文假名的字形和部分拉丁扩展字符（如法语的
+// U+202F 窄空格、’）需要其它字体。这里在运行时查找常见的系统字体，作为回退
+// 加入字体族：
+//   - 日文界面：日文字体优先，汉字使用日文字形
+//   - 其它界面：文泉驿优先，其后是日文字体和拉丁字体
+// egui 自带的字体始终排在最后。找不到系统字体时只使用内置字体。
+
+use eframe::egui;
+use std::sync::OnceLock;
+use crate::model::l18n::Language;
+
+const BUILTIN_FONT: &str = "wqy-microhei";
+const JAPANESE_FONT: &str = "system-japanese";
+const LATIN_FONT: &str = "system-latin";
+
+/// 日文字体候选：(路径, TTC 中的索引)
+const JAPANESE_CANDIDATES: &[(&str, u32)] = &[
+    // Linux
+    ("/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc", 0),
+    ("/usr/share/fonts/noto-cjk/NotoSansCJK-Regular.ttc", 0),
+    ("/usr/share/fonts/google-noto-cjk/NotoSansCJK-Regular.ttc", 0),
+    ("/usr/share/fonts/opentype/ipafont-gothic/ipagp.ttf", 0),
+    ("/usr/share/fonts/truetype/takao-gothic/TakaoPGothic.ttf", 0),
+    // macOS
+    ("/System/Library/Fonts/ヒラギノ角ゴシック W3.ttc", 0),
+    ("/System/Library/Fonts/Hiragino Sans W3.ttc", 0),
+    // Windows
+    ("C:\\Windows\\Fonts\\YuGothM.ttc", 0),
+    ("C:\\Windows\\Fonts\\meiryo.ttc", 0),
+    ("C:\\Windows\\Fonts\\msgothic.ttc", 0),
+];
+
+/// 拉丁字体候选（覆盖拉丁扩展字符）
+const LATIN_CANDIDATES: &[(&str, u32)] = &[
+    // Linux
+    ("/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf", 0),
+    ("/usr/share/fonts/TTF/DejaVuSans.ttf", 0),
+    ("/usr/share/fonts/dejavu/DejaVuSans.ttf", 0),
+    ("/usr/share/fonts/truetype/noto/NotoSans-Regular.ttf", 0),
+    // macOS
+    ("/System/Library/Fonts/Supplemental/Arial.ttf", 0),
+    ("/Library/Fonts/Arial.ttf", 0),
+    // Windows
+    ("C:\\Windows\\Fonts\\segoeui.ttf", 0),
+    ("C:\\Windows\\Fonts\\arial.ttf", 0),
+];
+
+/// 第一个存在的候选字体；只读取一次，之后切换语言时复用
+fn load_first(cache: &'static OnceLock<Option<egui::FontData>>, candidates: &[(&str, u32)]) -> Option<egui::FontData> {
+    cache.get_or_init(|| {
+        candidates.iter().find_map(|(path, index)| {
+            let bytes = std::fs::read(path).ok()?;
+            eprintln!("[GUI] Using fallback font {}", path);
+            // 字体数据在整个进程中使用，泄漏以便之后廉价地克隆
+            let mut data = egui::FontData::from_static(Box::leak(bytes.into_boxed_slice()));
+            data.index = *index;
+            Some(data)
+        })
+    }).clone()
+}
+
+/// 按界面语言配置字体
+pub fn configure_fonts(ctx: &egui::Context, language: &Language) {
+    static JAPANESE: OnceLock<Option<egui::FontData>> = OnceLock::new();
+    static LATIN: OnceLock<Option<egui::FontData>> = OnceLock::new();
+
+    let mut fonts = egui::FontDefinitions::default();
+    fonts.font_data.insert(
+        BUILTIN_FONT.to_owned(),
+        egui::FontData::from_static(include_bytes!("../../../font/wqy-microhei.ttc")),
+    );
+
+    let mut order = vec![BUILTIN_FONT];
+    if let Some(data) = load_first(&JAPANESE, JAPANESE_CANDIDATES) {
+        fonts.font_data.insert(JAPANESE_FONT.to_owned(), data);
+        if language.primary() == "ja" {
+            order.insert(0, JAPANESE_FONT);
+        } else {
+            order.push(JAPANESE_FONT);
+        }
+    }
+    if let Some(data) = load_first(&LATIN, LATIN_CANDIDATES) {
+        fonts.font_data.insert(LATIN_FONT.to_owned(), data);
+        order.push(LATIN_FONT);
+    }
+
+    for family in [egui::FontFamily::Proportional, egui::FontFamily::Monospace] {
+        let names = fonts.families.entry(family).or_default();
+        for (position, name) in order.iter().enumerate() {
+            names.insert(position, (*name).to_owned());
+        }
+    }
+
+    ctx.set_fonts(fonts);
+    eprintln!("[GUI] Fonts configured for {}: {}", language, order.join(", "));
+}
@@ -0,0 +1,146 @@
+// MIT License
+// 
+// Copyright (c) 2026 Laffinty
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+
+
+// ==============================================================================
+// Lint Dialog - 配置检查结果
+// ==============================================================================
+// 打开时发布 LintRequest，收到 LintFinished 后按严重程度着色列出问题，
+// 每个问题显示规则、说明以及所在的文件和行。
+
+use eframe::egui;
+use crate::model::lint::{LintFinished, LintReport, LintRequest, Severity};
+use crate::model::l18n::{format_message, Key, Language, TranslationArgs};
+use super::nginx_result::COLOR_WARNING;
+use super::site_editor::COLOR_ERROR;
+
+const DIALOG_WIDTH: f32 = 640.0;
+const LIST_HEIGHT: f32 = 360.0;
+
+#[derive(Default)]
+pub struct LintDialog {
+    open: bool,
+    /// None 表示正在检查
+    report: Option<LintReport>,
+}
+
+impl LintDialog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 打开对话框；调用方随后发布 LintRequest
+    pub fn open(&mut self) {
+        self.open = true;
+        self.report = None;
+    }
+
+    pub fn handle_finished(&mut self, finished: &LintFinished) {
+        if self.open {
+            self.report = Some(finished.report.clone());
+        }
+    }
+
+    /// 点击重新检查时返回要发布的请求
+    pub fn ui(&mut self, ctx: &egui::Context, language: &Language, translate_fn: &dyn Fn(Key) -> String) -> Option<LintRequest> {
+        if !self.open {
+            return None;
+        }
+
+        let mut request = None;
+        let mut close = false;
+        egui::Window::new(translate_fn(Key::MenuLintConfig))
+            .id(egui::Id::new("lint_dialog"))
+            .collapsible(false)
+            .resizable(false)
+            .default_width(DIALOG_WIDTH)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.set_width(DIALOG_WIDTH);
+                match &self.report {
+                    None => {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label(translate_fn(Key::LintRunning));
+                        });
+                    }
+                    Some(report) => Self::render_report(ui, report, language, translate_fn),
+                }
+
+                ui.add_space(12.0);
+                ui.separator();
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button(translate_fn(Key::LintClose)).clicked() {
+                        close = true;
+                    }
+                    if ui.add_enabled(self.report.is_some(), egui::Button::new(translate_fn(Key::LintCheckAgain))).clicked() {
+                        request = Some(LintRequest);
+                    }
+                });
+            });
+
+        if close || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+            self.open = false;
+        }
+        if request.is_some() {
+            self.report = None;
+        }
+        request
+    }
+
+    fn render_report(ui: &mut egui::Ui, report: &LintReport, language: &Language, translate_fn: &dyn Fn(Key) -> String) {
+        for error in &report.errors {
+            ui.colored_label(COLOR_ERROR, error);
+        }
+        if report.lints.is_empty() {
+            if report.errors.is_empty() {
+                ui.label(translate_fn(Key::LintClean));
+            }
+            return;
+        }
+
+        let args = TranslationArgs::new()
+            .count("errors", report.count(Severity::Error))
+            .count("warnings", report.count(Severity::Warning));
+        ui.strong(format_message(&translate_fn(Key::LintSummary), language, &args));
+        ui.add_space(4.0);
+        egui::ScrollArea::vertical()
+            .id_source("lint_list")
+            .max_height(LIST_HEIGHT)
+            .show(ui, |ui| {
+                for lint in &report.lints {
+                    ui.add_space(6.0);
+                    let (color, severity) = match lint.severity {
+                        Severity::Error => (COLOR_ERROR, Key::LintSeverityError),
+                        Severity::Warning => (COLOR_WARNING, Key::LintSeverityWarning),
+                    };
+                    ui.colored_label(color, format!("{}: {}", translate_fn(severity), translate_fn(lint.rule.translation_key())));
+                    ui.label(&lint.message);
+                    let args = TranslationArgs::new()
+                        .text("file", &lint.location.path.display().to_string())
+                        .text("line", &lint.location.line.to_string());
+                    ui.weak(format_message(&translate_fn(Key::NginxIssueLocation), language, &args));
+                }
+            });
+    }
+}
@@ -21,66 +21,37 @@
 // SOFTWARE.
 
 use eframe::egui;
-use crate::model::l18n::{Language, LanguageChangeRequest, BatchTranslationRequest};
+use crate::model::l18n::{Key, Language, LanguageInfo, LanguageChangeRequest, BatchTranslationRequest, AvailableLanguagesRequest, TranslationArgs, format_message};
 use std::sync::Arc;
-use std::collections::HashMap;
-use tokio::sync::RwLock;
+use std::time::{Duration, SystemTime};
 use crate::MessageBus;
-
-/// 所有需要翻译的键列表 - 用于初始化时批量加载
-const ALL_TRANSLATION_KEYS: &[&str] = &[
-    // Menu
-    "menu_file", "menu_operation", "menu_language", "menu_help",
-    // File menu
-    "menu_takeover_nginx", "menu_startup_on_boot", "menu_new_proxy", "menu_new_php", 
-    "menu_new_static", "menu_exit",
-    // Operation menu
-    "menu_start_nginx", "menu_stop_nginx", "menu_reload_config", "menu_refresh_sites",
-    "menu_test_config", "menu_backup_config",
-    // Language menu
-    "menu_english", "menu_chinese",
-    // Help menu
-    "menu_about",
-    // Site list
-    "site_list_site", "site_list_type", "site_list_port", "site_list_domain", "site_list_https",
-    "site_list_https_yes", "site_list_https_no", "site_list_edit", "site_list_delete",
-    // Status bar
-    "status_nginx_stopped", "status_nginx_running", "status_sites",
-    // About dialog
-    "about_title", "about_app_name", "about_version", "about_description",
-    "about_author_label", "about_author", "about_license_label", "about_license",
-    "about_website_label", "about_website", "about_copyright", "about_ok",
-    // Site type
-    "site_type_static", "site_type_php", "site_type_proxy",
-];
+use crate::model::service::StartupOnBootRequest;
+use std::sync::atomic::{AtomicBool, Ordering};
+use super::translation_cache::TranslationCache;
+use super::site_editor::SiteEditor;
+use super::site_wizard::SiteWizard;
+use super::site_list::{SiteAction, SiteListPanel};
+use super::nginx_result::{NginxResultDialog, NginxUiState};
+use super::takeover_dialog::TakeoverDialog;
+use super::backup_dialog::BackupDialog;
+use super::lint_dialog::LintDialog;
+use crate::model::controller::{NginxCommand, NginxCommandRequest};
+use crate::model::monitor::{NginxState, NginxStatusRequest};
+use crate::model::takeover::TakeoverScanRequest;
+use crate::model::backup::BackupListRequest;
+use crate::model::lint::LintRequest;
+use crate::model::site::{next_request_id, Site, SiteChange, SiteChangeRequest, SiteChangeResponse, SiteListRequest, SiteRefreshRequest, SiteType};
 
 // ==============================================================================
 // Constants - UI Configuration
 // =============================================================================-
 
-/// Site list table column definitions
-const COLUMN_CONFIG: [(Option<f32>, &str); 5] = [
-    (Some(200.0), "site_list_site"),    // Site name
-    (Some(100.0), "site_list_type"),    // Type (Static/PHP/Proxy)
-    (Some(100.0), "site_list_port"),    // Port number
-    (None,        "site_list_domain"),  // Domain (flexible width)
-    (Some(80.0),  "site_list_https"),   // HTTPS status
-];
-
-const SPACING: f32 = 16.0;
-const HEADER_HEIGHT: f32 = 32.0;
-const ROW_HEIGHT: f32 = 40.0;
-const MIN_DOMAIN_WIDTH: f32 = 120.0;
-const ROW_PADDING_LEFT: f32 = 8.0;
-const CONTEXT_MENU_WIDTH: f32 = 120.0;
-const CONTEXT_MENU_BUTTON_HEIGHT: f32 = 28.0;
-const FONT_SIZE: f32 = 14.0;
-const HEADER_FONT_SIZE: f32 = 15.0;
-
 // Color constants
-const COLOR_SELECTED: egui::Color32 = egui::Color32::from_rgb(200, 230, 255);
-const COLOR_HOVER: egui::Color32 = egui::Color32::from_rgb(240, 248, 255);
 const COLOR_TRANSPARENT: egui::Color32 = egui::Color32::TRANSPARENT;
+const COLOR_NGINX_RUNNING: egui::Color32 = egui::Color32::from_rgb(40, 150, 60);
+const COLOR_NGINX_STOPPED: egui::Color32 = egui::Color32::GRAY;
+const COLOR_NGINX_RELOADING: egui::Color32 = egui::Color32::from_rgb(200, 140, 0);
+const COLOR_NGINX_ERROR: egui::Color32 = egui::Color32::from_rgb(200, 50, 50);
 
 // ==============================================================================
 // About Dialog Component
@@ -92,6 +63,7 @@ pub struct AboutDialog {
     is_open: bool,
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for AboutDialog {
     fn default() -> Self {
         Self { is_open: false }
@@ -116,12 +88,12 @@ impl AboutDialog {
     }
     
     /// Render the about dialog window
-    pub fn ui(&mut self, ctx: &egui::Context, _language: Language, translate_fn: &dyn Fn(&str) -> String) {
+    pub fn ui(&mut self, ctx: &egui::Context, _language: &Language, translate_fn: &dyn Fn(Key) -> String) {
         if !self.is_open {
             return;
         }
         
-        let window_title = translate_fn("about_title");
+        let window_title = translate_fn(Key::AboutTitle);
         
         let response = egui::Window::new(window_title)
             .collapsible(false)
@@ -174,9 +146,9 @@ impl AboutDialog {
         }
     }
     
-    fn render_app_info(&self, ui: &mut egui::Ui, translate_fn: &dyn Fn(&str) -> String) {
+    fn render_app_info(&self, ui: &mut egui::Ui, translate_fn: &dyn Fn(Key) -> String) {
         ui.label(
-            egui::RichText::new(translate_fn("about_app_name"))
+            egui::RichText::new(translate_fn(Key::AboutAppName))
                 .size(24.0)
                 .strong()
         );
@@ -184,7 +156,7 @@ impl AboutDialog {
         ui.add_space(4.0);
         
         ui.label(
-            egui::RichText::new(translate_fn("about_version"))
+            egui::RichText::new(translate_fn(Key::AboutVersion))
                 .size(14.0)
                 .color(ui.visuals().weak_text_color())
         );
@@ -192,32 +164,32 @@ impl AboutDialog {
         ui.add_space(8.0);
         
         ui.label(
-            egui::RichText::new(translate_fn("about_description"))
+            egui::RichText::new(translate_fn(Key::AboutDescription))
                 .size(13.0)
         );
     }
     
-    fn render_details(&self, ui: &mut egui::Ui, translate_fn: &dyn Fn(&str) -> String) {
+    fn render_details(&self, ui: &mut egui::Ui, translate_fn: &dyn Fn(Key) -> String) {
         let label_color = ui.visuals().weak_text_color();
         
         ui.horizontal(|ui| {
-            ui.label(egui::RichText::new(translate_fn("about_author_label")).size(12.0).color(label_color));
-            ui.label(egui::RichText::new(translate_fn("about_author")).size(12.0));
+            ui.label(egui::RichText::new(translate_fn(Key::AboutAuthorLabel)).size(12.0).color(label_color));
+            ui.label(egui::RichText::new(translate_fn(Key::AboutAuthor)).size(12.0));
         });
         
         ui.add_space(4.0);
         
         ui.horizontal(|ui| {
-            ui.label(egui::RichText::new(translate_fn("about_license_label")).size(12.0).color(label_color));
-            ui.label(egui::RichText::new(translate_fn("about_license")).size(12.0));
+            ui.label(egui::RichText::new(translate_fn(Key::AboutLicenseLabel)).size(12.0).color(label_color));
+            ui.label(egui::RichText::new(translate_fn(Key::AboutLicense)).size(12.0));
         });
         
         ui.add_space(4.0);
         
         ui.horizontal(|ui| {
-            ui.label(egui::RichText::new(translate_fn("about_website_label")).size(12.0).color(label_color));
+            ui.label(egui::RichText::new(translate_fn(Key::AboutWebsiteLabel)).size(12.0).color(label_color));
             ui.hyperlink_to(
-                egui::RichText::new(translate_fn("about_website")).size(12.0),
+                egui::RichText::new(translate_fn(Key::AboutWebsite)).size(12.0),
                 "https://github.com/laffinty/easyNginx"
             );
         });
@@ -225,18 +197,18 @@ impl AboutDialog {
         ui.add_space(4.0);
         
         ui.label(
-            egui::RichText::new(translate_fn("about_copyright"))
+            egui::RichText::new(translate_fn(Key::AboutCopyright))
                 .size(11.0)
                 .color(label_color)
         );
     }
     
-    fn render_ok_button(&self, ui: &mut egui::Ui, translate_fn: &dyn Fn(&str) -> String) {
+    fn render_ok_button(&self, ui: &mut egui::Ui, translate_fn: &dyn Fn(Key) -> String) {
         ui.vertical_centered(|ui| {
             if ui.add_sized(
                 [100.0, 32.0],
                 egui::Button::new(
-                    egui::RichText::new(translate_fn("about_ok")).size(13.0)
+                    egui::RichText::new(translate_fn(Key::AboutOk)).size(13.0)
                 ).rounding(6.0)
             ).clicked() {
                 // Window will close due to the check at the start of ui()
@@ -247,365 +219,6 @@ impl AboutDialog {
 
 // 注意：about_translate 函数已移除，翻译通过消息机制从 l18n 模块获取
 
-// ==============================================================================
-// Site List Components
-// ==============================================================================
-
-/// Represents a site configuration entry
-#[derive(Clone, Debug, PartialEq)]
-struct SiteListItem {
-    name: String,
-    site_type: SiteType,
-    port: String,
-    domain: String,
-    enable_https: bool,
-    enable_http_redirect: bool,
-}
-
-#[derive(Clone, Debug, PartialEq)]
-enum SiteType {
-    Static,
-    Php,
-    Proxy,
-}
-
-impl SiteType {
-    fn translation_key(&self) -> &'static str {
-        match self {
-            SiteType::Static => "site_type_static",
-            SiteType::Php => "site_type_php",
-            SiteType::Proxy => "site_type_proxy",
-        }
-    }
-}
-
-/// Site list panel component
-struct SiteListPanel {
-    sites: Vec<SiteListItem>,
-    selected_site: Option<String>,
-    show_context_menu: bool,
-    context_menu_pos: egui::Pos2,
-}
-
-impl SiteListPanel {
-    pub fn new(_language: Language) -> Self {
-        let sites = vec![
-            SiteListItem {
-                name: "example-static".into(),
-                site_type: SiteType::Static,
-                port: "80".into(),
-                domain: "static.example.com".into(),
-                enable_https: false,
-                enable_http_redirect: false,
-            },
-            SiteListItem {
-                name: "example-php".into(),
-                site_type: SiteType::Php,
-                port: "8080".into(),
-                domain: "php.example.com".into(),
-                enable_https: true,
-                enable_http_redirect: true,
-            },
-            SiteListItem {
-                name: "example-proxy".into(),
-                site_type: SiteType::Proxy,
-                port: "3000".into(),
-                domain: "proxy.example.com".into(),
-                enable_https: false,
-                enable_http_redirect: false,
-            },
-        ];
-        
-        Self {
-            sites,
-            selected_site: None,
-            show_context_menu: false,
-            context_menu_pos: egui::Pos2::ZERO,
-        }
-    }
-    
-    pub fn set_language(&mut self, _language: Language, _cache: &HashMap<String, String>) {
-        // 语言切换时，site list 会自动使用新的翻译缓存
-        // 不需要额外操作，因为 translate_fn 会传入当前的缓存
-    }
-    
-    pub fn ui(&mut self, ctx: &egui::Context, ui: &mut egui::Ui, translate_fn: &dyn Fn(&str) -> String) {
-        egui::ScrollArea::vertical()
-            .auto_shrink([false, false])
-            .show(ui, |ui| {
-                let dynamic_width = self.calculate_dynamic_width(ui.available_width());
-                
-                self.render_header(ui, dynamic_width, translate_fn);
-                ui.separator();
-                self.render_rows(ui, ctx, dynamic_width, translate_fn);
-            });
-    }
-    
-    fn calculate_dynamic_width(&self, available_width: f32) -> f32 {
-        let fixed_width: f32 = COLUMN_CONFIG.iter()
-            .filter_map(|(w, _)| *w)
-            .sum();
-        let spacing_total = SPACING * (COLUMN_CONFIG.len() - 1) as f32;
-        
-        if fixed_width + spacing_total < available_width {
-            available_width - fixed_width - spacing_total - ROW_PADDING_LEFT * 2.0
-        } else {
-            MIN_DOMAIN_WIDTH
-        }
-    }
-    
-    fn render_header(&self, ui: &mut egui::Ui, dynamic_width: f32, translate_fn: &dyn Fn(&str) -> String) {
-        let rect = ui.available_rect_before_wrap();
-        let rect = rect.with_max_y(rect.min.y + HEADER_HEIGHT);
-        ui.advance_cursor_after_rect(rect);
-        
-        let painter = ui.painter();
-        let start_x = rect.left() + ROW_PADDING_LEFT;
-        let center_y = rect.center().y;
-        let mut x = start_x;
-        
-        for (col_width, key) in &COLUMN_CONFIG {
-            let width = col_width.unwrap_or(dynamic_width);
-            let text = translate_fn(key);
-            
-            Self::draw_centered_text(
-                painter,
-                &text,
-                x,
-                center_y,
-                width,
-                ui.visuals().strong_text_color(),
-                HEADER_FONT_SIZE,
-            );
-            
-            x += width + SPACING;
-        }
-    }
-    
-    fn render_rows(&mut self, ui: &mut egui::Ui, ctx: &egui::Context, dynamic_width: f32, translate_fn: &dyn Fn(&str) -> String) {
-        let mut context_menu_action: Option<(String, egui::Pos2)> = None;
-        let mut double_click_action: Option<String> = None;
-        
-        // Clone sites to avoid borrow issues
-        let sites: Vec<_> = self.sites.clone();
-        
-        for site in &sites {
-            let selected = self.selected_site.as_ref() == Some(&site.name);
-            let row_rect = self.allocate_row_rect(ui);
-            let row_response = ui.interact(row_rect, ui.id().with(&site.name), egui::Sense::click());
-            
-            // Draw background
-            self.draw_row_background(ui, row_rect, selected, row_response.hovered());
-            
-            // Draw content
-            self.draw_row_content(ui, row_rect, site, selected, dynamic_width, translate_fn);
-            
-            // Handle interactions
-            if row_response.clicked() {
-                self.selected_site = Some(site.name.clone());
-            }
-            if row_response.double_clicked() {
-                self.selected_site = Some(site.name.clone());
-                double_click_action = Some(site.name.clone());
-            }
-            if row_response.secondary_clicked() {
-                self.selected_site = Some(site.name.clone());
-                let pos = row_response.interact_pointer_pos()
-                    .unwrap_or_else(|| row_rect.center());
-                context_menu_action = Some((site.name.clone(), pos));
-            }
-        }
-        
-        // Process actions after iteration
-        if let Some(name) = double_click_action {
-            self.edit_site(&name);
-        }
-        if let Some((name, pos)) = context_menu_action {
-            self.show_context_menu_at(ctx, ui, &name, pos);
-        }
-        
-        // Render context menu if open
-        if self.show_context_menu {
-            self.render_context_menu(ctx, ui, translate_fn);
-        }
-    }
-    
-    fn allocate_row_rect(&self, ui: &mut egui::Ui) -> egui::Rect {
-        let width = ui.available_width();
-        let pos = ui.cursor().min;
-        let rect = egui::Rect::from_min_size(pos, egui::vec2(width, ROW_HEIGHT));
-        ui.advance_cursor_after_rect(rect);
-        rect
-    }
-    
-    fn draw_row_background(&self, ui: &egui::Ui, rect: egui::Rect, selected: bool, hovered: bool) {
-        let color = if selected {
-            COLOR_SELECTED
-        } else if hovered {
-            COLOR_HOVER
-        } else {
-            COLOR_TRANSPARENT
-        };
-        
-        if color != COLOR_TRANSPARENT {
-            ui.painter().rect_filled(rect, 4.0, color);
-        }
-    }
-    
-    fn draw_row_content(&self, ui: &egui::Ui, rect: egui::Rect, site: &SiteListItem, selected: bool, dynamic_width: f32, translate_fn: &dyn Fn(&str) -> String) {
-        let painter = ui.painter();
-        let start_x = rect.left() + ROW_PADDING_LEFT;
-        let center_y = rect.center().y;
-        let mut x = start_x;
-        let text_color = if selected {
-            ui.visuals().strong_text_color()
-        } else {
-            ui.visuals().text_color()
-        };
-        
-        for (i, (col_width, _)) in COLUMN_CONFIG.iter().enumerate() {
-            let width = col_width.unwrap_or(dynamic_width);
-            let text = self.get_column_text(site, i, translate_fn);
-            
-            Self::draw_centered_text(
-                painter,
-                &text,
-                x,
-                center_y,
-                width,
-                text_color,
-                FONT_SIZE,
-            );
-            
-            x += width + SPACING;
-        }
-    }
-    
-    fn get_column_text(&self, site: &SiteListItem, column_index: usize, translate_fn: &dyn Fn(&str) -> String) -> String {
-        match column_index {
-            0 => site.name.clone(),
-            1 => translate_fn(site.site_type.translation_key()),
-            2 => {
-                if site.enable_https && site.enable_http_redirect {
-                    format!("{}/80(redirect)", site.port)
-                } else {
-                    site.port.clone()
-                }
-            }
-            3 => site.domain.clone(),
-            4 => {
-                if site.enable_https {
-                    translate_fn("site_list_https_yes")
-                } else {
-                    translate_fn("site_list_https_no")
-                }
-            }
-            _ => String::new(),
-        }
-    }
-    
-    fn draw_centered_text(
-        painter: &egui::Painter,
-        text: &str,
-        x: f32,
-        center_y: f32,
-        max_width: f32,
-        color: egui::Color32,
-        font_size: f32,
-    ) {
-        let font_id = egui::FontId::proportional(font_size);
-        
-        // Measure text
-        let galley = painter.layout(text.into(), font_id.clone(), color, f32::INFINITY);
-        let text_width = galley.size().x.min(max_width);
-        let offset = (max_width - text_width) / 2.0;
-        
-        // Recreate with proper wrap width
-        let galley = painter.layout(text.into(), font_id, color, max_width);
-        let text_height = galley.size().y;
-        
-        let pos = egui::pos2(x + offset, center_y - text_height / 2.0);
-        painter.galley(pos, galley, color);
-    }
-    
-    fn show_context_menu_at(&mut self, ctx: &egui::Context, _ui: &egui::Ui, site_name: &str, pos: egui::Pos2) {
-        self.selected_site = Some(site_name.into());
-        self.show_context_menu = true;
-        
-        // Ensure menu doesn't go off screen
-        let screen_rect = ctx.screen_rect();
-        let menu_width = CONTEXT_MENU_WIDTH;
-        let menu_height = CONTEXT_MENU_BUTTON_HEIGHT * 2.0 + 8.0; // 2 buttons + padding
-        
-        let mut adjusted_pos = pos;
-        if pos.x + menu_width > screen_rect.max.x {
-            adjusted_pos.x = screen_rect.max.x - menu_width - 10.0;
-        }
-        if pos.y + menu_height > screen_rect.max.y {
-            adjusted_pos.y = screen_rect.max.y - menu_height - 10.0;
-        }
-        
-        self.context_menu_pos = adjusted_pos;
-    }
-    
-    fn render_context_menu(&mut self, ctx: &egui::Context, ui: &egui::Ui, translate_fn: &dyn Fn(&str) -> String) {
-        if let Some(site) = self.selected_site.clone() {
-            egui::Window::new("site_context_menu")
-                .title_bar(false)
-                .resizable(false)
-                .fixed_pos(self.context_menu_pos)
-                .show(ctx, |ui| {
-                    ui.set_min_width(CONTEXT_MENU_WIDTH);
-                    
-                    ui.vertical(|ui| {
-                        ui.set_width(CONTEXT_MENU_WIDTH);
-                        
-                        if self.menu_button(ui, "site_list_edit", &translate_fn) {
-                            self.show_context_menu = false;
-                            self.edit_site(&site);
-                        }
-                        
-                        if self.menu_button(ui, "site_list_delete", &translate_fn) {
-                            self.show_context_menu = false;
-                            self.delete_site(&site);
-                        }
-                    });
-                });
-            
-            // Close menu when clicking outside
-            let clicked_outside = ui.input(|i| i.pointer.any_click())
-                && !ctx.is_pointer_over_area();
-            if clicked_outside {
-                self.show_context_menu = false;
-            }
-        } else {
-            self.show_context_menu = false;
-        }
-    }
-    
-    fn menu_button(&self, ui: &mut egui::Ui, key: &str, translate_fn: &dyn Fn(&str) -> String) -> bool {
-        ui.add_sized(
-            [CONTEXT_MENU_WIDTH, CONTEXT_MENU_BUTTON_HEIGHT],
-            egui::Button::new(translate_fn(key))
-        ).clicked()
-    }
-    
-    fn edit_site(&self, site: &str) {
-        println!("Edit site: {}", site);
-        // TODO: Implement edit functionality
-    }
-    
-    fn delete_site(&mut self, site: &str) {
-        println!("Delete site: {}", site);
-        self.sites.retain(|s| s.name != site);
-        if self.selected_site.as_deref() == Some(site) {
-            self.selected_site = None;
-        }
-    }
-}
-
-// 注意：site_list_translate 函数已移除，翻译通过消息机制从 l18n 模块获取
-
 // ==============================================================================
 // Main Window
 // ==============================================================================
@@ -613,127 +226,160 @@ impl SiteListPanel {
 pub struct MainWindow {
     site_list_panel: SiteListPanel,
     about_dialog: AboutDialog,
+    site_editor: SiteEditor,
+    site_wizard: SiteWizard,
     current_language: Language,
     bus: Option<Arc<MessageBus>>,
-    /// 翻译缓存 - 与 UiModule 共享
-    translation_cache: Arc<RwLock<HashMap<String, String>>>,
-    /// 当前语言 - 与 UiModule 共享
-    current_language_shared: Arc<RwLock<Language>>,
-    /// 记录已发送请求但尚未响应的键（避免重复请求）
-    pending_keys: std::collections::HashSet<String>,
-    /// 缓存的本地读取副本（避免每帧都加锁）
-    local_cache: HashMap<String, String>,
-    /// 上次同步缓存的时间
-    last_cache_sync: std::time::Instant,
+    /// 翻译缓存 - 与 UiModule 共享，按 (语言, 键) 同步读取
+    translation_cache: Arc<TranslationCache>,
+    /// 开机启动状态 - 与 UiModule 共享
+    startup_on_boot: Arc<AtomicBool>,
+    /// 可用语言 - 与 UiModule 共享
+    available_languages: Arc<std::sync::RwLock<Vec<LanguageInfo>>>,
+    /// 是否已请求可用语言列表和站点列表
+    initial_requests_sent: bool,
+    /// 站点修改的响应 - 由 UiModule 写入，每帧取出处理
+    site_responses: Arc<std::sync::Mutex<Vec<SiteChangeResponse>>>,
+    /// 站点列表发出的启用、停用或删除请求
+    site_list_request: Option<u64>,
+    /// nginx 状态和操作结果 - 与 UiModule 共享
+    nginx: Arc<NginxUiState>,
+    nginx_result: NginxResultDialog,
+    takeover_dialog: TakeoverDialog,
+    backup_dialog: BackupDialog,
+    lint_dialog: LintDialog,
 }
 
 impl MainWindow {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         bus: Option<Arc<MessageBus>>,
-        translation_cache: Arc<RwLock<HashMap<String, String>>>,
-        current_language: Arc<RwLock<Language>>,
+        translation_cache: Arc<TranslationCache>,
+        language: Language,
+        startup_on_boot: Arc<AtomicBool>,
+        available_languages: Arc<std::sync::RwLock<Vec<LanguageInfo>>>,
+        sites: Arc<std::sync::RwLock<Arc<Vec<Site>>>>,
+        site_responses: Arc<std::sync::Mutex<Vec<SiteChangeResponse>>>,
+        nginx: Arc<NginxUiState>,
     ) -> Self {
-        let language = Language::ChineseSimplified;
+        // 初始语言由 UiModule 决定（设置文件或系统 locale）
         Self {
-            site_list_panel: SiteListPanel::new(language),
+            site_list_panel: SiteListPanel::new(sites),
             about_dialog: AboutDialog::new(),
+            site_editor: SiteEditor::new(),
+            site_wizard: SiteWizard::new(),
             current_language: language,
             bus,
             translation_cache,
-            current_language_shared: current_language,
-            pending_keys: std::collections::HashSet::new(),
-            local_cache: HashMap::new(),
-            last_cache_sync: std::time::Instant::now(),
+            startup_on_boot,
+            available_languages,
+            initial_requests_sent: false,
+            site_responses,
+            site_list_request: None,
+            nginx,
+            nginx_result: NginxResultDialog::new(),
+            takeover_dialog: TakeoverDialog::new(),
+            backup_dialog: BackupDialog::new(),
+            lint_dialog: LintDialog::new(),
         }
     }
     
-    /// 同步共享缓存到本地缓存（定期调用）
-    fn sync_cache(&mut self) {
-        let now = std::time::Instant::now();
-        // 每 100ms 同步一次
-        if now.duration_since(self.last_cache_sync).as_millis() > 100 {
-            if let Ok(cache) = self.translation_cache.try_read() {
-                if !cache.is_empty() {
-                    self.local_cache = cache.clone();
-                }
-            }
-            self.last_cache_sync = now;
-        }
+    /// 从缓存获取翻译，尚未加载时返回 key（翻译到达后重绘）
+    fn translate(&self, key: Key) -> String {
+        self.translation_cache.get_or_key(&self.current_language, key)
     }
     
-    /// 从缓存获取翻译，如果缺失则返回 key 并触发异步加载
-    fn translate(&mut self, key: &str) -> String {
-        // 先同步缓存
-        self.sync_cache();
-        
-        // 从本地缓存查找
-        if let Some(value) = self.local_cache.get(key) {
-            return value.clone();
-        }
-        
-        // 避免重复请求
-        if !self.pending_keys.contains(key) {
-            self.pending_keys.insert(key.to_string());
-            // 发送异步请求加载这个翻译
-            if let Some(bus) = &self.bus {
-                let bus_clone = bus.clone();
-                let key_clone = key.to_string();
-                let lang = self.current_language;
-                tokio::spawn(async move {
-                    use crate::model::l18n::TranslationRequest;
-                    let _ = bus_clone.publish(TranslationRequest::new(&key_clone, lang)).await;
-                });
-            }
+    /// 当前语言在当前 generation 下尚未请求过时，批量请求所有翻译
+    ///
+    /// 首次渲染、切换到新语言以及翻译资源重新加载后都会触发
+    fn ensure_translations_requested(&self) {
+        let Some(generation) = self.translation_cache.begin_load(&self.current_language) else {
+            return;
+        };
+        if let Some(bus) = &self.bus {
+            let bus_clone = bus.clone();
+            let lang = self.current_language.clone();
+            tokio::spawn(async move {
+                let request = BatchTranslationRequest::new(Key::ALL, lang, "ui").with_generation(generation);
+                let _ = bus_clone.publish(request).await;
+            });
         }
-        
-        // 返回 key 作为后备（首次渲染时会显示 key，后续帧会更新）
-        key.to_string()
     }
     
-    /// 请求批量加载所有翻译
-    fn request_all_translations(&mut self) {
+    /// 在后台发布消息
+    fn publish<M: crate::Message + 'static>(&self, message: M) {
         if let Some(bus) = &self.bus {
             let bus_clone = bus.clone();
-            let lang = self.current_language;
-            let keys: Vec<&str> = ALL_TRANSLATION_KEYS.to_vec();
-            
-            // 标记所有键为 pending
-            for key in &keys {
-                self.pending_keys.insert(key.to_string());
-            }
-            
             tokio::spawn(async move {
-                let request = BatchTranslationRequest::new(keys, lang, "ui");
-                let _ = bus_clone.publish(request).await;
+                let _ = bus_clone.publish(message).await;
             });
         }
     }
     
-    pub fn set_language(&mut self, language: Language) {
-        self.current_language = language;
-        self.local_cache.clear();
-        self.pending_keys.clear();
-        self.site_list_panel.set_language(language, &self.local_cache);
-        
-        // 更新共享的语言设置
-        if let Ok(mut lang) = self.current_language_shared.try_write() {
-            *lang = language;
+    /// 处理站点列表中的操作（编辑、启用或停用、删除）
+    fn handle_site_action(&mut self, action: SiteAction) {
+        match action {
+            SiteAction::Edit(name) => {
+                let sites = self.site_list_panel.sites();
+                if let Some(site) = sites.iter().find(|site| site.name == name && site.is_managed()) {
+                    let other_names = sites.iter()
+                        .filter(|other| other.name != name)
+                        .map(|other| other.name.clone())
+                        .collect();
+                    self.site_editor.open_edit(site, other_names);
+                }
+            }
+            SiteAction::SetEnabled(name, enabled) => {
+                let id = next_request_id();
+                self.site_list_request = Some(id);
+                self.publish(SiteChangeRequest::new(id, SiteChange::SetEnabled { name, enabled }, "ui"));
+            }
+            SiteAction::Delete { name, archive } => {
+                let id = next_request_id();
+                self.site_list_request = Some(id);
+                self.publish(SiteChangeRequest::new(id, SiteChange::Delete { name, archive }, "ui"));
+            }
         }
-        
-        // 清空共享缓存并请求新语言的翻译
-        if let Ok(mut cache) = self.translation_cache.try_write() {
-            cache.clear();
+    }
+    
+    /// 处理 SiteModule 的响应
+    fn handle_site_responses(&mut self) {
+        let responses = std::mem::take(&mut *self.site_responses.lock().unwrap_or_else(|e| e.into_inner()));
+        for response in responses {
+            if let Some(error) = &response.error {
+                eprintln!("[UI] Site change {} failed: {}", response.id, error);
+            }
+            if self.site_list_request == Some(response.id) {
+                self.site_list_request = None;
+                let error = response.error.clone().or_else(|| {
+                    let problems: Vec<String> = response.validation_errors.iter().map(|e| self.translate(e.problem)).collect();
+                    (!problems.is_empty()).then(|| problems.join("\n"))
+                });
+                self.site_list_panel.set_error(error);
+            }
+            self.site_editor.handle_response(&response);
+            self.site_wizard.handle_response(&response);
         }
-        self.request_all_translations();
+    }
+    
+    /// 切换界面语言 - 缓存按语言区分，不需要清空，下一帧按需请求
+    pub fn set_language(&mut self, language: Language) {
+        self.current_language = language.clone();
+        self.site_list_panel.set_language(language);
     }
 }
 
 impl eframe::App for MainWindow {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        // 首次渲染时请求翻译
-        if self.local_cache.is_empty() && self.pending_keys.is_empty() {
-            self.request_all_translations();
+        self.ensure_translations_requested();
+        if !self.initial_requests_sent {
+            self.initial_requests_sent = true;
+            self.publish(AvailableLanguagesRequest::new("ui"));
+            self.publish(SiteListRequest);
+            self.publish(NginxStatusRequest);
         }
+        self.handle_site_responses();
+        self.handle_nginx_responses();
         
         egui::TopBottomPanel::top("menu_bar")
             .exact_height(36.0)
@@ -743,17 +389,38 @@ impl eframe::App for MainWindow {
                 self.render_menu_bar(ui);
             });
 
+        let cache = self.translation_cache.clone();
+        let language = self.current_language.clone();
+        let translate = move |key: Key| cache.get_or_key(&language, key);
+        
         egui::CentralPanel::default().show(ctx, |ui| {
-            let translate = |key: &str| self.local_cache.get(key).cloned().unwrap_or_else(|| key.to_string());
-            self.site_list_panel.ui(ctx, ui, &translate);
+            self.site_list_panel.ui(ctx, ui, &self.current_language, &translate);
         });
+        if let Some(action) = self.site_list_panel.take_action() {
+            self.handle_site_action(action);
+        }
 
         egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
             self.render_status_bar(ui);
         });
 
-        let translate = |key: &str| self.local_cache.get(key).cloned().unwrap_or_else(|| key.to_string());
-        self.about_dialog.ui(ctx, self.current_language, &translate);
+        self.about_dialog.ui(ctx, &self.current_language, &translate);
+        if let Some(request) = self.site_editor.ui(ctx, &self.current_language, &translate) {
+            self.publish(request);
+        }
+        if let Some(request) = self.site_wizard.ui(ctx, &self.current_language, &translate) {
+            self.publish(request);
+        }
+        self.nginx_result.ui(ctx, &self.current_language, &translate);
+        if let Some(request) = self.takeover_dialog.ui(ctx, &self.current_language, &translate) {
+            self.publish(request);
+        }
+        if let Some(request) = self.backup_dialog.ui(ctx, &self.current_language, &translate) {
+            self.publish(request);
+        }
+        if let Some(request) = self.lint_dialog.ui(ctx, &self.current_language, &translate) {
+            self.publish(request);
+        }
     }
 }
 
@@ -768,25 +435,32 @@ impl MainWindow {
     }
     
     fn render_file_menu(&mut self, ui: &mut egui::Ui) {
-        ui.menu_button(self.translate("menu_file"), |ui| {
-            if ui.button(self.translate("menu_takeover_nginx")).clicked() {
+        ui.menu_button(self.translate(Key::MenuFile), |ui| {
+            if ui.button(self.translate(Key::MenuTakeoverNginx)).clicked() {
                 ui.close_menu();
+                self.takeover_dialog.open();
+                self.publish(TakeoverScanRequest);
             }
-            if ui.button(self.translate("menu_startup_on_boot")).clicked() {
+            let mut startup_on_boot = self.startup_on_boot.load(Ordering::SeqCst);
+            if ui.checkbox(&mut startup_on_boot, self.translate(Key::MenuStartupOnBoot)).clicked() {
                 ui.close_menu();
+                self.set_startup_on_boot(startup_on_boot);
             }
             ui.separator();
-            if ui.button(self.translate("menu_new_proxy")).clicked() {
+            if ui.button(self.translate(Key::MenuNewProxy)).clicked() {
                 ui.close_menu();
+                self.site_wizard.open(SiteType::Proxy, self.site_list_panel.sites().to_vec());
             }
-            if ui.button(self.translate("menu_new_php")).clicked() {
+            if ui.button(self.translate(Key::MenuNewPhp)).clicked() {
                 ui.close_menu();
+                self.site_wizard.open(SiteType::Php, self.site_list_panel.sites().to_vec());
             }
-            if ui.button(self.translate("menu_new_static")).clicked() {
+            if ui.button(self.translate(Key::MenuNewStatic)).clicked() {
                 ui.close_menu();
+                self.site_wizard.open(SiteType::Static, self.site_list_panel.sites().to_vec());
             }
             ui.separator();
-            if ui.button(self.translate("menu_exit")).clicked() {
+            if ui.button(self.translate(Key::MenuExit)).clicked() {
                 ui.close_menu();
                 std::process::exit(0);
             }
@@ -794,49 +468,62 @@ impl MainWindow {
     }
     
     fn render_operation_menu(&mut self, ui: &mut egui::Ui) {
-        ui.menu_button(self.translate("menu_operation"), |ui| {
-            if ui.button(self.translate("menu_start_nginx")).clicked() {
+        ui.menu_button(self.translate(Key::MenuOperation), |ui| {
+            if ui.button(self.translate(Key::MenuStartNginx)).clicked() {
+                self.run_nginx_command(NginxCommand::Start);
                 ui.close_menu();
             }
-            if ui.button(self.translate("menu_stop_nginx")).clicked() {
+            if ui.button(self.translate(Key::MenuStopNginx)).clicked() {
+                self.run_nginx_command(NginxCommand::Stop);
                 ui.close_menu();
             }
-            if ui.button(self.translate("menu_reload_config")).clicked() {
+            if ui.button(self.translate(Key::MenuReloadConfig)).clicked() {
+                self.run_nginx_command(NginxCommand::Reload);
                 ui.close_menu();
             }
             ui.separator();
-            if ui.button(self.translate("menu_refresh_sites")).clicked() {
+            if ui.button(self.translate(Key::MenuRefreshSites)).clicked() {
+                self.publish(SiteRefreshRequest);
                 ui.close_menu();
             }
             ui.separator();
-            if ui.button(self.translate("menu_test_config")).clicked() {
+            if ui.button(self.translate(Key::MenuTestConfig)).clicked() {
+                self.run_nginx_command(NginxCommand::Test);
+                ui.close_menu();
+            }
+            if ui.button(self.translate(Key::MenuBackupConfig)).clicked() {
                 ui.close_menu();
+                self.backup_dialog.open();
+                self.publish(BackupListRequest);
             }
-            if ui.button(self.translate("menu_backup_config")).clicked() {
+            if ui.button(self.translate(Key::MenuLintConfig)).clicked() {
                 ui.close_menu();
+                self.lint_dialog.open();
+                self.publish(LintRequest);
             }
         });
     }
     
     fn render_language_menu(&mut self, ui: &mut egui::Ui) {
-        ui.menu_button(self.translate("menu_language"), |ui| {
-            let is_english = self.current_language == Language::English;
-            let is_chinese = self.current_language == Language::ChineseSimplified;
-            
-            if ui.radio(is_english, self.translate("menu_english")).clicked() {
-                self.change_language(Language::English);
-                ui.close_menu();
-            }
-            if ui.radio(is_chinese, self.translate("menu_chinese")).clicked() {
-                self.change_language(Language::ChineseSimplified);
-                ui.close_menu();
+        ui.menu_button(self.translate(Key::MenuLanguage), |ui| {
+            // 菜单项由已加载的翻译资源生成，显示各语言的自称
+            let languages = self.available_languages.read()
+                .map(|languages| languages.clone())
+                .unwrap_or_else(|e| e.into_inner().clone());
+            for info in languages {
+                let selected = self.current_language == info.language;
+                if ui.radio(selected, &info.name).clicked() {
+                    super::fonts::configure_fonts(ui.ctx(), &info.language);
+                    self.change_language(info.language);
+                    ui.close_menu();
+                }
             }
         });
     }
     
     fn render_help_menu(&mut self, ui: &mut egui::Ui) {
-        ui.menu_button(self.translate("menu_help"), |ui| {
-            if ui.button(self.translate("menu_about")).clicked() {
+        ui.menu_button(self.translate(Key::MenuHelp), |ui| {
+            if ui.button(self.translate(Key::MenuAbout)).clicked() {
                 ui.close_menu();
                 self.about_dialog.open();
             }
@@ -844,7 +531,7 @@ impl MainWindow {
     }
     
     fn change_language(&mut self, language: Language) {
-        self.set_language(language);
+        self.set_language(language.clone());
         if let Some(bus) = &self.bus {
             let bus_clone = bus.clone();
             tokio::spawn(async move {
@@ -853,37 +540,126 @@ impl MainWindow {
         }
     }
     
+    fn set_startup_on_boot(&mut self, enable: bool) {
+        // 先更新显示，service 模块的响应会带回实际状态
+        self.startup_on_boot.store(enable, Ordering::SeqCst);
+        if let Some(bus) = &self.bus {
+            let bus_clone = bus.clone();
+            tokio::spawn(async move {
+                let _ = bus_clone.publish(StartupOnBootRequest::new(enable)).await;
+            });
+        }
+    }
+    
+    /// 发布 nginx 操作并显示结果对话框
+    fn run_nginx_command(&mut self, command: NginxCommand) {
+        let id = next_request_id();
+        self.nginx_result.open(command, id);
+        self.publish(NginxCommandRequest::new(id, command, "ui"));
+    }
+    
+    /// 处理 controller、takeover、backup 和 lint 模块的响应
+    fn handle_nginx_responses(&mut self) {
+        let responses = std::mem::take(&mut *self.nginx.responses.lock().unwrap_or_else(|e| e.into_inner()));
+        for response in responses {
+            self.nginx_result.handle_response(&response);
+        }
+        let scans = std::mem::take(&mut *self.nginx.takeover_scans.lock().unwrap_or_else(|e| e.into_inner()));
+        for scanned in scans {
+            self.takeover_dialog.handle_scan(&scanned);
+        }
+        let responses = std::mem::take(&mut *self.nginx.takeover_responses.lock().unwrap_or_else(|e| e.into_inner()));
+        for response in responses {
+            self.takeover_dialog.handle_response(&response);
+        }
+        let lists = std::mem::take(&mut *self.nginx.backup_lists.lock().unwrap_or_else(|e| e.into_inner()));
+        for listed in lists {
+            self.backup_dialog.handle_list(&listed);
+        }
+        let responses = std::mem::take(&mut *self.nginx.backup_responses.lock().unwrap_or_else(|e| e.into_inner()));
+        for response in responses {
+            self.backup_dialog.handle_response(&response);
+        }
+        let reports = std::mem::take(&mut *self.nginx.lint_reports.lock().unwrap_or_else(|e| e.into_inner()));
+        for finished in reports {
+            self.lint_dialog.handle_finished(&finished);
+        }
+    }
+    
+    /// 状态栏中着色的 nginx 状态，悬停显示详情
+    fn render_nginx_status(&self, ui: &mut egui::Ui) {
+        let snapshot = self.nginx.status.read().unwrap_or_else(|e| e.into_inner()).clone();
+        let (key, color) = match snapshot.state {
+            NginxState::Running => (Key::StatusNginxRunning, COLOR_NGINX_RUNNING),
+            NginxState::Stopped => (Key::StatusNginxStopped, COLOR_NGINX_STOPPED),
+            NginxState::Reloading => (Key::StatusNginxReloading, COLOR_NGINX_RELOADING),
+            NginxState::Error => (Key::StatusNginxError, COLOR_NGINX_ERROR),
+        };
+        let response = ui.label(egui::RichText::new(self.translate(key)).color(color));
+        response.on_hover_ui(|ui| {
+            let now = SystemTime::now();
+            let line = |ui: &mut egui::Ui, key: Key, args: TranslationArgs| {
+                ui.label(format_message(&self.translate(key), &self.current_language, &args));
+            };
+            if let Some(pid) = snapshot.master_pid {
+                line(ui, Key::StatusTooltipPid, TranslationArgs::new().text("pid", &pid.to_string()));
+                line(ui, Key::StatusTooltipWorkers, TranslationArgs::new().count("count", snapshot.workers.len()));
+                if let Some(uptime) = snapshot.uptime(now) {
+                    line(ui, Key::StatusTooltipUptime, TranslationArgs::new().text("elapsed", &format_elapsed(uptime, &self.current_language, &|key| self.translate(key))));
+                }
+            }
+            if let Some(version) = &snapshot.version {
+                line(ui, Key::StatusTooltipVersion, TranslationArgs::new().text("version", version));
+            }
+            match snapshot.last_reload.and_then(|time| now.duration_since(time).ok()) {
+                Some(elapsed) => line(ui, Key::StatusTooltipLastReload, TranslationArgs::new().text("elapsed", &format_elapsed(elapsed, &self.current_language, &|key| self.translate(key)))),
+                None if snapshot.master_pid.is_some() => line(ui, Key::StatusTooltipNeverReloaded, TranslationArgs::new()),
+                None => {}
+            }
+            match snapshot.config_ok {
+                Some(true) => line(ui, Key::StatusTooltipConfigOk, TranslationArgs::new()),
+                Some(false) => line(ui, Key::StatusTooltipConfigFailed, TranslationArgs::new()),
+                None => {}
+            }
+            if let Some(error) = &snapshot.error {
+                line(ui, Key::StatusTooltipError, TranslationArgs::new().text("error", error));
+            }
+        });
+    }
+    
     fn render_status_bar(&mut self, ui: &mut egui::Ui) {
         ui.horizontal(|ui| {
-            ui.label(self.translate("status_nginx_stopped"));
+            self.render_nginx_status(ui);
             ui.separator();
             
             let stats = self.calculate_site_stats();
-            let text = self.translate("status_sites")
-                .replace("{total}", &stats.total.to_string())
-                .replace("{static}", &stats.static_count.to_string())
-                .replace("{php}", &stats.php_count.to_string())
-                .replace("{proxy}", &stats.proxy_count.to_string());
+            let args = TranslationArgs::new()
+                .count("total", stats.total)
+                .count("static", stats.static_count)
+                .count("php", stats.php_count)
+                .count("proxy", stats.proxy_count);
+            let text = format_message(&self.translate(Key::StatusSites), &self.current_language, &args);
             ui.label(text);
             
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                ui.label("easyNginx v1.0.0");
+                ui.label(self.translate(Key::StatusVersion));
             });
         });
     }
     
     fn calculate_site_stats(&self) -> SiteStats {
+        let sites = self.site_list_panel.sites();
         SiteStats {
-            total: self.site_list_panel.sites.len(),
-            static_count: self.count_sites_by_type(&SiteType::Static),
-            php_count: self.count_sites_by_type(&SiteType::Php),
-            proxy_count: self.count_sites_by_type(&SiteType::Proxy),
+            total: sites.len(),
+            static_count: Self::count_sites_by_type(&sites, SiteType::Static),
+            php_count: Self::count_sites_by_type(&sites, SiteType::Php),
+            proxy_count: Self::count_sites_by_type(&sites, SiteType::Proxy),
         }
     }
     
-    fn count_sites_by_type(&self, site_type: &SiteType) -> usize {
-        self.site_list_panel.sites.iter()
-            .filter(|s| &s.site_type == site_type)
+    fn count_sites_by_type(sites: &[Site], site_type: SiteType) -> usize {
+        sites.iter()
+            .filter(|s| s.site_type() == site_type)
             .count()
     }
 }
@@ -897,10 +673,55 @@ struct SiteStats {
 
 // 注意：所有翻译硬编码函数已移除，现在通过消息机制从 l18n 模块获取翻译
 
+/// 如 "3d 4h"、"5m 12s"，只保留最大的两个单位；单位写法来自翻译
+pub(super) fn format_elapsed(elapsed: Duration, language: &Language, translate_fn: &dyn Fn(Key) -> String) -> String {
+    let secs = elapsed.as_secs() as usize;
+    let (days, hours, minutes, seconds) = (secs / 86_400, secs / 3_600 % 24, secs / 60 % 60, secs % 60);
+    let (key, args) = if days > 0 {
+        (Key::ElapsedDaysHours, TranslationArgs::new().count("days", days).count("hours", hours))
+    } else if hours > 0 {
+        (Key::ElapsedHoursMinutes, TranslationArgs::new().count("hours", hours).count("minutes", minutes))
+    } else if minutes > 0 {
+        (Key::ElapsedMinutesSeconds, TranslationArgs::new().count("minutes", minutes).count("seconds", seconds))
+    } else {
+        (Key::ElapsedSeconds, TranslationArgs::new().count("seconds", seconds))
+    };
+    format_message(&translate_fn(key), language, &args)
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn create_main_window(
     bus: Option<Arc<MessageBus>>,
-    translation_cache: Arc<RwLock<HashMap<String, String>>>,
-    current_language: Arc<RwLock<Language>>,
+    translation_cache: Arc<TranslationCache>,
+    language: Language,
+    startup_on_boot: Arc<AtomicBool>,
+    available_languages: Arc<std::sync::RwLock<Vec<LanguageInfo>>>,
+    sites: Arc<std::sync::RwLock<Arc<Vec<Site>>>>,
+    site_responses: Arc<std::sync::Mutex<Vec<SiteChangeResponse>>>,
+    nginx: Arc<NginxUiState>,
 ) -> Box<dyn eframe::App> {
-    Box::new(MainWindow::new(bus, translation_cache, current_language))
+    Box::new(MainWindow::new(bus, translation_cache, language, startup_on_boot, available_languages, sites, site_responses, nginx))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::l18n::loader::load_translations_from;
+
+    #[test]
+    fn formats_elapsed_with_translated_units() {
+        let loaded = load_translations_from(&std::env::temp_dir().join("easynginx-no-locales"));
+        let format = |secs: u64, tag: &str| {
+            let language = Language::parse(tag).unwrap();
+            let translate = |key: Key| loaded.translations[&(key.as_str().to_string(), language.clone())].clone();
+            format_elapsed(Duration::from_secs(secs), &language, &translate)
+        };
+
+        assert_eq!(format(42, "en"), "42s");
+        assert_eq!(format(5 * 60 + 12, "en"), "5m 12s");
+        assert_eq!(format(2 * 3_600 + 59, "en"), "2h 0m");
+        assert_eq!(format(3 * 86_400 + 4 * 3_600 + 5, "en"), "3d 4h");
+        assert_eq!(format(5 * 60 + 12, "zh-CN"), "5分12秒");
+        assert_eq!(format(3 * 86_400 + 4 * 3_600, "de"), "3 T. 4 Std.");
+    }
 }
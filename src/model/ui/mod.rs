@@ -21,6 +21,15 @@
 // SOFTWARE.
 
 pub mod main_window;
+pub mod fonts;
+pub mod translation_cache;
+pub mod site_editor;
+pub mod site_list;
+pub mod site_wizard;
+pub mod nginx_result;
+pub mod takeover_dialog;
+pub mod backup_dialog;
+pub mod lint_dialog;
 
 use async_trait::async_trait;
 use std::sync::Arc;
@@ -30,8 +39,16 @@ use std::any::TypeId;
 use tokio::sync::RwLock;
 use std::sync::atomic::{AtomicBool, Ordering};
 use eframe::egui;
-use std::collections::HashMap;
-use crate::model::l18n::{BatchTranslationResponse, TranslationResponse, Language};
+use translation_cache::TranslationCache;
+use crate::model::l18n::{BatchTranslationResponse, TranslationResponse, Language, LanguageInfo, AvailableLanguagesResponse, TranslationsReloaded};
+use crate::model::service::StartupOnBootResponse;
+use crate::model::site::{Site, SiteChangeResponse, SitesUpdated};
+use crate::model::controller::NginxCommandResponse;
+use crate::model::monitor::NginxStatusChanged;
+use crate::model::takeover::{TakeoverResponse, TakeoverScanned};
+use crate::model::backup::{BackupResponse, BackupsListed};
+use crate::model::lint::LintFinished;
+use nginx_result::NginxUiState;
 
 #[derive(Clone)]
 pub struct UiModule {
@@ -39,9 +56,21 @@ pub struct UiModule {
     bus: Arc<RwLock<Option<Arc<MessageBus>>>>,
     is_running: Arc<AtomicBool>,
     /// 共享的翻译缓存 - 供 MainWindow 读取，UiModule 写入
-    translation_cache: Arc<RwLock<HashMap<String, String>>>,
-    /// 当前语言
-    current_language: Arc<RwLock<Language>>,
+    translation_cache: Arc<TranslationCache>,
+    /// 启动时的界面语言
+    initial_language: Language,
+    /// 开机启动状态 - 由 service 模块的响应更新
+    startup_on_boot: Arc<AtomicBool>,
+    /// 可用语言 - 用于生成语言菜单（egui 线程同步读取）
+    available_languages: Arc<std::sync::RwLock<Vec<LanguageInfo>>>,
+    /// 站点列表 - 由 site 模块的 SitesUpdated 更新
+    sites: Arc<std::sync::RwLock<Arc<Vec<Site>>>>,
+    /// 待 MainWindow 处理的站点修改响应
+    site_responses: Arc<std::sync::Mutex<Vec<SiteChangeResponse>>>,
+    /// nginx 状态和操作结果 - 由 controller 模块的消息更新
+    nginx: Arc<NginxUiState>,
+    /// GUI 创建后的 egui 上下文，用于在收到新数据时请求重绘
+    egui_ctx: Arc<std::sync::OnceLock<egui::Context>>,
 }
 
 impl UiModule {
@@ -50,8 +79,25 @@ impl UiModule {
             name: "ui",
             bus: Arc::new(RwLock::new(None)),
             is_running: Arc::new(AtomicBool::new(false)),
-            translation_cache: Arc::new(RwLock::new(HashMap::new())),
-            current_language: Arc::new(RwLock::new(Language::ChineseSimplified)),
+            translation_cache: Arc::new(TranslationCache::new()),
+            initial_language: crate::model::l18n::initial_language(),
+            startup_on_boot: Arc::new(AtomicBool::new(false)),
+            available_languages: Arc::new(std::sync::RwLock::new(Vec::new())),
+            sites: Arc::new(std::sync::RwLock::new(Arc::new(Vec::new()))),
+            site_responses: Arc::new(std::sync::Mutex::new(Vec::new())),
+            nginx: Arc::new(NginxUiState::default()),
+            egui_ctx: Arc::new(std::sync::OnceLock::new()),
+        }
+    }
+    
+    fn set_available_languages(&self, languages: &[LanguageInfo]) {
+        let mut available = self.available_languages.write().unwrap_or_else(|e| e.into_inner());
+        *available = languages.to_vec();
+    }
+    
+    fn request_repaint(&self) {
+        if let Some(ctx) = self.egui_ctx.get() {
+            ctx.request_repaint();
         }
     }
 }
@@ -71,6 +117,12 @@ impl Module for UiModule {
     async fn initialize(&mut self, bus: Arc<MessageBus>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         eprintln!("[UI Module] === INITIALIZATION START ===");
         
+        // 守护进程模式下没有显示器，不创建窗口
+        if crate::run_mode() == crate::RunMode::Daemon {
+            eprintln!("[UI Module] Daemon mode, not starting the GUI");
+            return Ok(());
+        }
+        
         *self.bus.write().await = Some(bus.clone());
         
         // 注册翻译响应消息类型并订阅
@@ -78,10 +130,42 @@ impl Module for UiModule {
         let translation_response_type = bus.register_message_type::<TranslationResponse>().await;
         bus.subscribe(batch_response_type, self.name().to_string()).await;
         bus.subscribe(translation_response_type, self.name().to_string()).await;
+        let startup_response_type = bus.register_message_type::<StartupOnBootResponse>().await;
+        bus.subscribe(startup_response_type, self.name().to_string()).await;
+        let languages_response_type = bus.register_message_type::<AvailableLanguagesResponse>().await;
+        bus.subscribe(languages_response_type, self.name().to_string()).await;
+        let reloaded_type = bus.register_message_type::<TranslationsReloaded>().await;
+        bus.subscribe(reloaded_type, self.name().to_string()).await;
+        let sites_updated_type = bus.register_message_type::<SitesUpdated>().await;
+        bus.subscribe(sites_updated_type, self.name().to_string()).await;
+        let site_response_type = bus.register_message_type::<SiteChangeResponse>().await;
+        bus.subscribe(site_response_type, self.name().to_string()).await;
+        let nginx_status_type = bus.register_message_type::<NginxStatusChanged>().await;
+        bus.subscribe(nginx_status_type, self.name().to_string()).await;
+        let nginx_response_type = bus.register_message_type::<NginxCommandResponse>().await;
+        bus.subscribe(nginx_response_type, self.name().to_string()).await;
+        let takeover_scanned_type = bus.register_message_type::<TakeoverScanned>().await;
+        bus.subscribe(takeover_scanned_type, self.name().to_string()).await;
+        let takeover_response_type = bus.register_message_type::<TakeoverResponse>().await;
+        bus.subscribe(takeover_response_type, self.name().to_string()).await;
+        let backups_listed_type = bus.register_message_type::<BackupsListed>().await;
+        bus.subscribe(backups_listed_type, self.name().to_string()).await;
+        let backup_response_type = bus.register_message_type::<BackupResponse>().await;
+        bus.subscribe(backup_response_type, self.name().to_string()).await;
+        let lint_finished_type = bus.register_message_type::<LintFinished>().await;
+        bus.subscribe(lint_finished_type, self.name().to_string()).await;
+        
+        self.startup_on_boot.store(crate::model::service::is_startup_enabled(), Ordering::SeqCst);
         
         let is_running = self.is_running.clone();
         let translation_cache = self.translation_cache.clone();
-        let current_language = self.current_language.clone();
+        let initial_language = self.initial_language.clone();
+        let startup_on_boot = self.startup_on_boot.clone();
+        let available_languages = self.available_languages.clone();
+        let sites = self.sites.clone();
+        let site_responses = self.site_responses.clone();
+        let nginx = self.nginx.clone();
+        let egui_ctx = self.egui_ctx.clone();
         
         eprintln!("[UI Module] Starting GUI in spawn_blocking...");
         self.is_running.store(true, Ordering::SeqCst);
@@ -103,6 +187,7 @@ impl Module for UiModule {
             eprintln!("[GUI] Creating minimal eframe window...");
             
             // Create native options with Windows-specific any_thread support
+            #[cfg_attr(not(windows), allow(unused_mut))]
             let mut native_options = eframe::NativeOptions {
                 viewport: egui::ViewportBuilder::default()
                     .with_title("easyNginx Test")
@@ -127,34 +212,12 @@ impl Module for UiModule {
             let result = eframe::run_native(
                 "easyNginx",
                 native_options,
-                Box::new(|cc| {
+                Box::new(move |cc| {
                     eprintln!("[GUI] Creating MainWindow instance...");
+                    let _ = egui_ctx.set(cc.egui_ctx.clone());
                     
-                    // 配置中文字体支持和系统字体跟随
-                    eprintln!("[GUI] Configuring Chinese font support and system font follow...");
-                    
-                    // 配置使用系统字体并确保UTF-8显示
-                    eprintln!("[GUI] Configuring to use system fonts with UTF-8 support");
-                    
-                    // 配置字体以确保中文正确显示
-                    eprintln!("[GUI] Configuring fonts for Chinese support");
-                    
-                    // 使用指定的字体文件：font/wqy-microhei.ttc
-                    let mut fonts = egui::FontDefinitions::default();
-                    
-                    // 添加文泉驿微米黑字体
-                    fonts.font_data.insert(
-                        "wqy-microhei".to_owned(),
-                        egui::FontData::from_static(include_bytes!("../../../font/wqy-microhei.ttc"))
-                    );
-                    
-                    // 将该字体设置为默认字体
-                    fonts.families.get_mut(&egui::FontFamily::Proportional).unwrap().insert(0, "wqy-microhei".to_owned());
-                    fonts.families.get_mut(&egui::FontFamily::Monospace).unwrap().insert(0, "wqy-microhei".to_owned());
-                    
-                    // 应用字体配置
-                    cc.egui_ctx.set_fonts(fonts);
-                    eprintln!("[GUI] Font configuration applied: using wqy-microhei.ttc");
+                    // 内置中文字体 + 系统字体回退（日文假名、拉丁扩展字符）
+                    fonts::configure_fonts(&cc.egui_ctx, &initial_language);
                     
                     // 打印当前系统的编码信息
                     eprintln!("[GUI] System codepage: 936 (GBK)");
@@ -170,7 +233,12 @@ impl Module for UiModule {
                     let window = main_window::create_main_window(
                         Some(bus_for_window),
                         translation_cache,
-                        current_language,
+                        initial_language,
+                        startup_on_boot,
+                        available_languages,
+                        sites,
+                        site_responses,
+                        nginx,
                     );
                     eprintln!("[GUI] MainWindow created successfully");
                     window
@@ -239,21 +307,93 @@ impl Module for UiModule {
             if let Some(response) = envelope.payload.as_any().downcast_ref::<BatchTranslationResponse>() {
                 if response.requester == "ui" {
                     println!("[UI Module] Received batch translation response with {} entries", response.translations.len());
-                    // 更新共享缓存
-                    let mut cache = self.translation_cache.write().await;
-                    for (key, value) in &response.translations {
-                        cache.insert(key.clone(), value.clone());
+                    if self.translation_cache.insert_batch(response.generation, &response.language, &response.translations) {
+                        self.request_repaint();
+                    } else {
+                        println!("[UI Module] Discarded stale translations for {} (generation {})", response.language, response.generation);
                     }
-                    // 更新当前语言
-                    let mut lang = self.current_language.write().await;
-                    *lang = response.language;
                 }
             }
         } else if envelope.message_type == TypeId::of::<TranslationResponse>() {
             if let Some(response) = envelope.payload.as_any().downcast_ref::<TranslationResponse>() {
-                // 更新单个翻译到缓存
-                let mut cache = self.translation_cache.write().await;
-                cache.insert(response.key.clone(), response.translation.clone());
+                // 更新单个翻译到缓存（其它模块请求的、带参数的和过期的响应被丢弃）
+                if self.translation_cache.insert_response("ui", response) {
+                    self.request_repaint();
+                }
+            }
+        } else if envelope.message_type == TypeId::of::<StartupOnBootResponse>() {
+            if let Some(response) = envelope.payload.as_any().downcast_ref::<StartupOnBootResponse>() {
+                if let Some(error) = &response.error {
+                    eprintln!("[UI Module] Startup on boot change failed: {}", error);
+                }
+                self.startup_on_boot.store(response.enabled, Ordering::SeqCst);
+            }
+        } else if envelope.message_type == TypeId::of::<AvailableLanguagesResponse>() {
+            if let Some(response) = envelope.payload.as_any().downcast_ref::<AvailableLanguagesResponse>() {
+                if response.requester == "ui" {
+                    self.set_available_languages(&response.languages);
+                }
+            }
+        } else if envelope.message_type == TypeId::of::<TranslationsReloaded>() {
+            if let Some(event) = envelope.payload.as_any().downcast_ref::<TranslationsReloaded>() {
+                println!("[UI Module] Translations reloaded, refreshing {} language(s)", event.languages.len());
+                self.translation_cache.invalidate();
+                self.set_available_languages(&event.languages);
+                // 下一帧 MainWindow 发现缓存为空，按新的 generation 重新请求
+                self.request_repaint();
+            }
+        } else if envelope.message_type == TypeId::of::<SitesUpdated>() {
+            if let Some(event) = envelope.payload.as_any().downcast_ref::<SitesUpdated>() {
+                *self.sites.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(event.sites.clone());
+                self.request_repaint();
+            }
+        } else if envelope.message_type == TypeId::of::<SiteChangeResponse>() {
+            if let Some(response) = envelope.payload.as_any().downcast_ref::<SiteChangeResponse>() {
+                if response.requester == "ui" {
+                    self.site_responses.lock().unwrap_or_else(|e| e.into_inner()).push(response.clone());
+                    self.request_repaint();
+                }
+            }
+        } else if envelope.message_type == TypeId::of::<NginxStatusChanged>() {
+            if let Some(event) = envelope.payload.as_any().downcast_ref::<NginxStatusChanged>() {
+                *self.nginx.status.write().unwrap_or_else(|e| e.into_inner()) = event.snapshot.clone();
+                self.request_repaint();
+            }
+        } else if envelope.message_type == TypeId::of::<NginxCommandResponse>() {
+            if let Some(response) = envelope.payload.as_any().downcast_ref::<NginxCommandResponse>() {
+                if response.requester == "ui" {
+                    self.nginx.responses.lock().unwrap_or_else(|e| e.into_inner()).push(response.clone());
+                    self.request_repaint();
+                }
+            }
+        } else if envelope.message_type == TypeId::of::<TakeoverScanned>() {
+            if let Some(scanned) = envelope.payload.as_any().downcast_ref::<TakeoverScanned>() {
+                self.nginx.takeover_scans.lock().unwrap_or_else(|e| e.into_inner()).push(scanned.clone());
+                self.request_repaint();
+            }
+        } else if envelope.message_type == TypeId::of::<TakeoverResponse>() {
+            if let Some(response) = envelope.payload.as_any().downcast_ref::<TakeoverResponse>() {
+                if response.requester == "ui" {
+                    self.nginx.takeover_responses.lock().unwrap_or_else(|e| e.into_inner()).push(response.clone());
+                    self.request_repaint();
+                }
+            }
+        } else if envelope.message_type == TypeId::of::<BackupsListed>() {
+            if let Some(listed) = envelope.payload.as_any().downcast_ref::<BackupsListed>() {
+                self.nginx.backup_lists.lock().unwrap_or_else(|e| e.into_inner()).push(listed.clone());
+                self.request_repaint();
+            }
+        } else if envelope.message_type == TypeId::of::<BackupResponse>() {
+            if let Some(response) = envelope.payload.as_any().downcast_ref::<BackupResponse>() {
+                if response.requester == "ui" {
+                    self.nginx.backup_responses.lock().unwrap_or_else(|e| e.into_inner()).push(response.clone());
+                    self.request_repaint();
+                }
+            }
+        } else if envelope.message_type == TypeId::of::<LintFinished>() {
+            if let Some(finished) = envelope.payload.as_any().downcast_ref::<LintFinished>() {
+                self.nginx.lint_reports.lock().unwrap_or_else(|e| e.into_inner()).push(finished.clone());
+                self.request_repaint();
             }
         }
         
@@ -0,0 +1,190 @@
+// MIT License
+// 
+// Copyright (c) 2026 Laffinty
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+
+// ==============================================================================
+// Nginx Result - 启动、停止、重载、测试的结果对话框
+// ==============================================================================
+// 操作菜单发布 NginxCommandRequest 后打开，收到响应前显示进度；
+// nginx -t 报告的问题按级别着色，并显示所在的文件和行。
+
+use eframe::egui;
+use crate::model::controller::{IssueLevel, NginxCommand, NginxCommandResponse, TestReport};
+use crate::model::monitor::NginxSnapshot;
+use crate::model::backup::{BackupResponse, BackupsListed};
+use crate::model::takeover::{TakeoverResponse, TakeoverScanned};
+use crate::model::lint::LintFinished;
+use crate::model::l18n::{format_message, Key, Language, TranslationArgs};
+use super::site_editor::COLOR_ERROR;
+
+const DIALOG_WIDTH: f32 = 520.0;
+const OUTPUT_HEIGHT: f32 = 200.0;
+pub(super) const COLOR_WARNING: egui::Color32 = egui::Color32::from_rgb(190, 130, 0);
+
+/// 与 UiModule 共享的 nginx 状态
+#[derive(Default)]
+pub struct NginxUiState {
+    /// 由 NginxStatusChanged 更新
+    pub status: std::sync::RwLock<NginxSnapshot>,
+    /// 待 MainWindow 处理的操作结果
+    pub responses: std::sync::Mutex<Vec<NginxCommandResponse>>,
+    /// 待 MainWindow 处理的接管查找结果和响应
+    pub takeover_scans: std::sync::Mutex<Vec<TakeoverScanned>>,
+    pub takeover_responses: std::sync::Mutex<Vec<TakeoverResponse>>,
+    /// 待 MainWindow 处理的备份列表和响应
+    pub backup_lists: std::sync::Mutex<Vec<BackupsListed>>,
+    pub backup_responses: std::sync::Mutex<Vec<BackupResponse>>,
+    /// 待 MainWindow 处理的配置检查结果
+    pub lint_reports: std::sync::Mutex<Vec<LintFinished>>,
+}
+
+fn command_key(command: NginxCommand) -> Key {
+    match command {
+        NginxCommand::Start => Key::MenuStartNginx,
+        NginxCommand::Stop => Key::MenuStopNginx,
+        NginxCommand::Reload => Key::MenuReloadConfig,
+        NginxCommand::Test => Key::MenuTestConfig,
+    }
+}
+
+/// 操作结果对话框
+#[derive(Default)]
+pub struct NginxResultDialog {
+    command: Option<NginxCommand>,
+    /// 等待响应的请求编号
+    pending: Option<u64>,
+    response: Option<NginxCommandResponse>,
+}
+
+impl NginxResultDialog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open(&mut self, command: NginxCommand, id: u64) {
+        *self = Self {
+            command: Some(command),
+            pending: Some(id),
+            response: None,
+        };
+    }
+
+    pub fn handle_response(&mut self, response: &NginxCommandResponse) {
+        if self.pending == Some(response.id) {
+            self.pending = None;
+            self.response = Some(response.clone());
+        }
+    }
+
+    pub fn ui(&mut self, ctx: &egui::Context, language: &Language, translate_fn: &dyn Fn(Key) -> String) {
+        let Some(command) = self.command else {
+            return;
+        };
+
+        let mut close = false;
+        egui::Window::new(translate_fn(command_key(command)))
+            .id(egui::Id::new("nginx_result"))
+            .collapsible(false)
+            .resizable(false)
+            .default_width(DIALOG_WIDTH)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.set_width(DIALOG_WIDTH);
+                match &self.response {
+                    None => {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label(translate_fn(Key::NginxResultWorking));
+                        });
+                    }
+                    Some(response) => Self::render_response(ui, response, language, translate_fn),
+                }
+                ui.add_space(12.0);
+                ui.separator();
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.add_enabled(self.pending.is_none(), egui::Button::new(translate_fn(Key::NginxResultClose))).clicked() {
+                        close = true;
+                    }
+                });
+            });
+
+        if close || (self.pending.is_none() && ctx.input(|i| i.key_pressed(egui::Key::Escape))) {
+            self.command = None;
+        }
+    }
+
+    fn render_response(ui: &mut egui::Ui, response: &NginxCommandResponse, language: &Language, translate_fn: &dyn Fn(Key) -> String) {
+        let test_failed = response.report.as_ref().is_some_and(|report| !report.success);
+        match &response.error {
+            None => {
+                let key = match response.command {
+                    NginxCommand::Start => Key::NginxResultStarted,
+                    NginxCommand::Stop => Key::NginxResultStopped,
+                    NginxCommand::Reload => Key::NginxResultReloaded,
+                    NginxCommand::Test => Key::NginxResultTestPassed,
+                };
+                ui.label(translate_fn(key));
+            }
+            Some(_) if test_failed => {
+                ui.colored_label(COLOR_ERROR, translate_fn(Key::NginxResultTestFailed));
+            }
+            Some(error) => {
+                let args = TranslationArgs::new().text("error", error);
+                ui.colored_label(COLOR_ERROR, format_message(&translate_fn(Key::NginxResultFailed), language, &args));
+            }
+        }
+        if let Some(report) = &response.report {
+            Self::render_report(ui, report, language, translate_fn);
+        }
+    }
+
+    /// nginx -t 报告的问题及完整输出
+    pub(super) fn render_report(ui: &mut egui::Ui, report: &TestReport, language: &Language, translate_fn: &dyn Fn(Key) -> String) {
+        for issue in &report.issues {
+            ui.add_space(4.0);
+            let color = match issue.level {
+                IssueLevel::Error => COLOR_ERROR,
+                IssueLevel::Warning => COLOR_WARNING,
+                IssueLevel::Notice => ui.visuals().text_color(),
+            };
+            ui.colored_label(color, &issue.message);
+            if let (Some(file), Some(line)) = (&issue.file, issue.line) {
+                let args = TranslationArgs::new()
+                    .text("file", &file.display().to_string())
+                    .text("line", &line.to_string());
+                ui.weak(format_message(&translate_fn(Key::NginxIssueLocation), language, &args));
+            }
+        }
+        if !report.output.is_empty() {
+            ui.add_space(8.0);
+            ui.collapsing(translate_fn(Key::NginxResultOutput), |ui| {
+                egui::ScrollArea::vertical()
+                    .max_height(OUTPUT_HEIGHT)
+                    .show(ui, |ui| {
+                        ui.add(egui::TextEdit::multiline(&mut report.output.as_str())
+                            .font(egui::TextStyle::Monospace)
+                            .desired_width(f32::INFINITY));
+                    });
+            });
+        }
+    }
+}
@@ -0,0 +1,382 @@
+// MIT License
+// 
+// Copyright (c) 2026 Laffinty
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+
+// ==============================================================================
+// Site Editor - 站点编辑对话框
+// ==============================================================================
+// 模态对话框：编辑站点的全部字段，每帧重新校验并把错误显示在字段下方。
+// 保存时生成 SiteChangeRequest，由 MainWindow 发布到总线；对话框等待
+// SiteModule 的响应，成功后关闭，失败时显示错误并允许继续编辑。
+
+use eframe::egui;
+use crate::model::l18n::{format_message, Key, Language, TranslationArgs};
+use crate::model::site::{next_request_id, parse_port, Site, SiteChange, SiteChangeRequest, SiteChangeResponse, SiteField, SiteKind, SiteOrigin, SiteType, ValidationError};
+
+const EDITOR_WIDTH: f32 = 480.0;
+pub(super) const LABEL_WIDTH: f32 = 140.0;
+pub(super) const COLOR_ERROR: egui::Color32 = egui::Color32::from_rgb(200, 40, 40);
+const COLOR_BACKDROP: egui::Color32 = egui::Color32::from_black_alpha(96);
+
+/// 编辑中的字段 - 全部以文本保存，切换类型时保留其它类型已填写的内容
+#[derive(Clone, Debug, Default)]
+pub(super) struct SiteDraft {
+    name: String,
+    site_type: Option<SiteType>,
+    port: String,
+    domain: String,
+    enable_https: bool,
+    enable_http_redirect: bool,
+    ipv6: bool,
+    http2: bool,
+    certificate: String,
+    certificate_key: String,
+    enabled: bool,
+    root: String,
+    fpm_socket: String,
+    /// 以空格分隔
+    index_files: String,
+    upstream: String,
+}
+
+impl SiteDraft {
+    /// 新站点的默认字段
+    pub(super) fn new_site(site_type: SiteType) -> Self {
+        Self::from_site(&Site::new("", site_type))
+    }
+
+    pub(super) fn from_site(site: &Site) -> Self {
+        // 先填入各类型的默认值，再用站点自身的字段覆盖
+        let mut draft = Self {
+            name: site.name.clone(),
+            site_type: Some(site.site_type()),
+            port: site.port.to_string(),
+            domain: site.domain.clone(),
+            enable_https: site.enable_https,
+            enable_http_redirect: site.enable_http_redirect,
+            ipv6: site.ipv6,
+            http2: site.http2,
+            certificate: site.certificate.clone(),
+            certificate_key: site.certificate_key.clone(),
+            enabled: site.enabled,
+            ..Self::default()
+        };
+        for site_type in SiteType::EDITABLE {
+            draft.set_kind(&SiteKind::default_for(site_type));
+        }
+        draft.set_kind(&site.kind);
+        draft
+    }
+
+    fn set_kind(&mut self, kind: &SiteKind) {
+        match kind {
+            SiteKind::Static { root } => self.root = root.clone(),
+            SiteKind::Php { root, fpm_socket, index_files } => {
+                self.root = root.clone();
+                self.fpm_socket = fpm_socket.clone();
+                self.index_files = index_files.join(" ");
+            }
+            SiteKind::Proxy { upstream } => self.upstream = upstream.clone(),
+            SiteKind::Custom => {}
+        }
+    }
+
+    pub(super) fn site_type(&self) -> SiteType {
+        self.site_type.unwrap_or(SiteType::Static)
+    }
+
+    /// 转换为站点；无法解析的端口记为 0，由校验报告
+    pub(super) fn to_site(&self) -> Site {
+        let kind = match self.site_type() {
+            SiteType::Static => SiteKind::Static {
+                root: self.root.trim().to_string(),
+            },
+            SiteType::Php => SiteKind::Php {
+                root: self.root.trim().to_string(),
+                fpm_socket: self.fpm_socket.trim().to_string(),
+                index_files: self.index_files.split_whitespace().map(str::to_string).collect(),
+            },
+            SiteType::Proxy => SiteKind::Proxy {
+                upstream: self.upstream.trim().to_string(),
+            },
+            SiteType::Custom => SiteKind::Custom,
+        };
+        Site {
+            name: self.name.trim().to_string(),
+            port: parse_port(&self.port).unwrap_or(0),
+            domain: self.domain.split_whitespace().collect::<Vec<_>>().join(" "),
+            enable_https: self.enable_https,
+            enable_http_redirect: self.enable_https && self.enable_http_redirect,
+            ipv6: self.ipv6,
+            http2: self.enable_https && self.http2,
+            certificate: self.certificate.trim().to_string(),
+            certificate_key: self.certificate_key.trim().to_string(),
+            enabled: self.enabled,
+            imported_from: None,
+            kind,
+            origin: SiteOrigin::Managed,
+        }
+    }
+}
+
+/// 站点编辑对话框
+#[derive(Default)]
+pub struct SiteEditor {
+    is_open: bool,
+    /// 正在编辑的站点的原名称
+    original_name: Option<String>,
+    /// 其它站点的名称（用于检查重名）
+    other_names: Vec<String>,
+    draft: SiteDraft,
+    /// 已发送、等待响应的请求编号
+    pending: Option<u64>,
+    /// SiteModule 返回的错误
+    server_errors: Vec<ValidationError>,
+    save_error: Option<String>,
+}
+
+impl SiteEditor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    /// 打开对话框编辑已有站点
+    pub fn open_edit(&mut self, site: &Site, other_names: Vec<String>) {
+        *self = Self {
+            is_open: true,
+            original_name: Some(site.name.clone()),
+            other_names,
+            draft: SiteDraft::from_site(site),
+            ..Self::default()
+        };
+    }
+
+    pub fn close(&mut self) {
+        self.is_open = false;
+        self.pending = None;
+    }
+
+    /// 处理 SiteModule 的响应：成功时关闭，失败时显示错误
+    pub fn handle_response(&mut self, response: &SiteChangeResponse) {
+        if self.pending != Some(response.id) {
+            return;
+        }
+        self.pending = None;
+        if response.is_ok() {
+            self.close();
+        } else {
+            self.server_errors = response.validation_errors.clone();
+            self.save_error = response.error.clone();
+        }
+    }
+
+    /// 绘制对话框；点击保存时返回要发布的请求
+    pub fn ui(&mut self, ctx: &egui::Context, language: &Language, translate_fn: &dyn Fn(Key) -> String) -> Option<SiteChangeRequest> {
+        if !self.is_open {
+            return None;
+        }
+
+        render_backdrop(ctx);
+
+        let site = self.draft.to_site();
+        let mut errors = site.validate(&self.other_names);
+        // 服务端错误在字段修改前一直显示
+        errors.extend(self.server_errors.iter().filter(|e| !errors.contains(e)).copied().collect::<Vec<_>>());
+
+        let title = format_message(
+            &translate_fn(Key::SiteEditorTitleEdit),
+            language,
+            &TranslationArgs::new().text("name", self.original_name.as_deref().unwrap_or_default()),
+        );
+
+        let mut request = None;
+        let mut cancel = false;
+        let response = egui::Window::new(title)
+            .id(egui::Id::new("site_editor"))
+            .collapsible(false)
+            .resizable(false)
+            .default_width(EDITOR_WIDTH)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.set_width(EDITOR_WIDTH);
+                let before = self.draft.to_site();
+                ui.add_enabled_ui(self.pending.is_none(), |ui| {
+                    egui::Grid::new("site_editor_fields")
+                        .num_columns(2)
+                        .spacing([12.0, 8.0])
+                        .min_col_width(LABEL_WIDTH)
+                        .show(ui, |ui| {
+                            render_general_fields(ui, &mut self.draft, &errors, true, translate_fn);
+                            render_tls_fields(ui, &mut self.draft, &errors, translate_fn);
+                            render_kind_fields(ui, &mut self.draft, &errors, translate_fn);
+                        });
+                });
+                if self.draft.to_site() != before {
+                    self.server_errors.clear();
+                }
+
+                if let Some(error) = &self.save_error {
+                    ui.add_space(8.0);
+                    let text = format_message(
+                        &translate_fn(Key::SiteEditorSaveFailed),
+                        language,
+                        &TranslationArgs::new().text("error", error),
+                    );
+                    ui.colored_label(COLOR_ERROR, text);
+                }
+
+                ui.add_space(12.0);
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        let can_save = errors.is_empty() && self.pending.is_none();
+                        if ui.add_enabled(can_save, egui::Button::new(translate_fn(Key::SiteEditorSave))).clicked() {
+                            request = Some(self.save_request());
+                        }
+                        if ui.add_enabled(self.pending.is_none(), egui::Button::new(translate_fn(Key::SiteEditorCancel))).clicked() {
+                            cancel = true;
+                        }
+                        if self.pending.is_some() {
+                            ui.spinner();
+                            ui.label(translate_fn(Key::SiteEditorSaving));
+                        }
+                    });
+                });
+            });
+
+        // 对话框始终位于遮罩之上
+        if let Some(inner) = &response {
+            ctx.move_to_top(inner.response.layer_id);
+        }
+        if cancel || (self.pending.is_none() && ctx.input(|i| i.key_pressed(egui::Key::Escape))) {
+            self.close();
+        }
+        request
+    }
+
+    fn save_request(&mut self) -> SiteChangeRequest {
+        let id = next_request_id();
+        self.pending = Some(id);
+        self.save_error = None;
+        let change = SiteChange::Save {
+            original_name: self.original_name.clone(),
+            site: Box::new(self.draft.to_site()),
+        };
+        SiteChangeRequest::new(id, change, "ui")
+    }
+}
+
+/// 半透明遮罩，挡住对话框之外的点击
+pub(super) fn render_backdrop(ctx: &egui::Context) {
+    let screen = ctx.screen_rect();
+    egui::Area::new(egui::Id::new("site_editor_backdrop"))
+        .order(egui::Order::PanelResizeLine)
+        .fixed_pos(screen.min)
+        .show(ctx, |ui| {
+            ui.painter().rect_filled(screen, 0.0, COLOR_BACKDROP);
+            ui.allocate_rect(screen, egui::Sense::click_and_drag());
+        });
+}
+
+fn error_for(errors: &[ValidationError], field: SiteField) -> Option<Key> {
+    errors.iter().find(|e| e.field == field).map(|e| e.problem)
+}
+
+/// 名称、[类型]、端口、域名（在 Grid 中绘制）
+pub(super) fn render_general_fields(ui: &mut egui::Ui, draft: &mut SiteDraft, errors: &[ValidationError], with_type: bool, translate_fn: &dyn Fn(Key) -> String) {
+    text_row(ui, translate_fn(Key::SiteEditorName), &mut draft.name, error_for(errors, SiteField::Name), translate_fn);
+
+    if with_type {
+        ui.label(translate_fn(Key::SiteEditorType));
+        ui.horizontal(|ui| {
+            for site_type in SiteType::EDITABLE {
+                let selected = draft.site_type() == site_type;
+                if ui.radio(selected, translate_fn(site_type.translation_key())).clicked() {
+                    draft.site_type = Some(site_type);
+                }
+            }
+        });
+        ui.end_row();
+    }
+
+    text_row(ui, translate_fn(Key::SiteEditorPort), &mut draft.port, error_for(errors, SiteField::Port), translate_fn);
+    ui.label("");
+    ui.checkbox(&mut draft.ipv6, translate_fn(Key::SiteEditorIpv6));
+    ui.end_row();
+    text_row(ui, translate_fn(Key::SiteEditorDomain), &mut draft.domain, error_for(errors, SiteField::Domain), translate_fn);
+}
+
+/// HTTPS、重定向和证书（在 Grid 中绘制）
+pub(super) fn render_tls_fields(ui: &mut egui::Ui, draft: &mut SiteDraft, errors: &[ValidationError], translate_fn: &dyn Fn(Key) -> String) {
+    ui.label("");
+    ui.checkbox(&mut draft.enable_https, translate_fn(Key::SiteEditorHttps));
+    ui.end_row();
+
+    if draft.enable_https {
+        ui.label("");
+        ui.checkbox(&mut draft.enable_http_redirect, translate_fn(Key::SiteEditorHttpRedirect));
+        ui.end_row();
+        ui.label("");
+        ui.checkbox(&mut draft.http2, translate_fn(Key::SiteEditorHttp2));
+        ui.end_row();
+        text_row(ui, translate_fn(Key::SiteEditorCertificate), &mut draft.certificate, error_for(errors, SiteField::Certificate), translate_fn);
+        text_row(ui, translate_fn(Key::SiteEditorCertificateKey), &mut draft.certificate_key, error_for(errors, SiteField::CertificateKey), translate_fn);
+    }
+}
+
+/// 当前类型特有的字段（在 Grid 中绘制）
+pub(super) fn render_kind_fields(ui: &mut egui::Ui, draft: &mut SiteDraft, errors: &[ValidationError], translate_fn: &dyn Fn(Key) -> String) {
+    match draft.site_type() {
+        SiteType::Static => {
+            text_row(ui, translate_fn(Key::SiteEditorRoot), &mut draft.root, error_for(errors, SiteField::Root), translate_fn);
+        }
+        SiteType::Php => {
+            text_row(ui, translate_fn(Key::SiteEditorRoot), &mut draft.root, error_for(errors, SiteField::Root), translate_fn);
+            text_row(ui, translate_fn(Key::SiteEditorFpmSocket), &mut draft.fpm_socket, error_for(errors, SiteField::FpmSocket), translate_fn);
+            text_row(ui, translate_fn(Key::SiteEditorIndexFiles), &mut draft.index_files, error_for(errors, SiteField::IndexFiles), translate_fn);
+        }
+        SiteType::Proxy => {
+            text_row(ui, translate_fn(Key::SiteEditorUpstream), &mut draft.upstream, error_for(errors, SiteField::Upstream), translate_fn);
+        }
+        SiteType::Custom => {}
+    }
+}
+
+/// 一行：标签 | 输入框（有错误时下方显示错误）
+fn text_row(ui: &mut egui::Ui, label: String, value: &mut String, error: Option<Key>, translate_fn: &dyn Fn(Key) -> String) {
+    ui.label(label);
+    ui.vertical(|ui| {
+        let mut edit = egui::TextEdit::singleline(value).desired_width(f32::INFINITY);
+        if error.is_some() {
+            edit = edit.text_color(COLOR_ERROR);
+        }
+        ui.add(edit);
+        if let Some(problem) = error {
+            ui.label(egui::RichText::new(translate_fn(problem)).size(12.0).color(COLOR_ERROR));
+        }
+    });
+    ui.end_row();
+}
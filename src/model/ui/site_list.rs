@@ -0,0 +1,1045 @@
+// MIT License
+// 
+// Copyright (c) 2026 Laffinty
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+
+// ==============================================================================
+// Site List - 站点列表面板
+// ==============================================================================
+// 搜索框按名称或域名过滤，另可按类型和 HTTPS 过滤；点击表头排序。
+// 列可以拖动边缘调整宽度、拖动表头调整顺序、在右键菜单中隐藏，
+// 布局和排序保存在设置文件的 [site_list] 中，下次启动时恢复。
+//
+// 为了在数千个站点时保持流畅：过滤和排序的结果（SiteView）只在数据、
+// 条件或排序变化时重建；ScrollArea::show_rows 只绘制可见的行；单元格文字
+// 的排版结果缓存在 GalleyCache 中，语言、数据或列宽变化时清空。
+//
+// 启用列和右键菜单可以启用或停用站点，停用的站点显示为灰色。删除站点前需要确认，
+// 并可选择先归档站点的文件。
+
+use eframe::egui;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::Arc;
+use crate::model::l18n::{format_message, Key, Language, TranslationArgs};
+use crate::model::settings::{ColumnSettings, Settings, SiteListSettings};
+use crate::model::site::{Site, SiteOrigin, SiteType};
+use super::site_editor::{render_backdrop, COLOR_ERROR};
+
+const SPACING: f32 = 16.0;
+const HEADER_HEIGHT: f32 = 32.0;
+const ROW_HEIGHT: f32 = 40.0;
+const MIN_COLUMN_WIDTH: f32 = 48.0;
+const ROW_PADDING_LEFT: f32 = 8.0;
+const SEARCH_WIDTH: f32 = 220.0;
+const CONTEXT_MENU_WIDTH: f32 = 120.0;
+const CONTEXT_MENU_BUTTON_HEIGHT: f32 = 28.0;
+const CONTEXT_MENU_BUTTONS: f32 = 3.0;
+const DELETE_DIALOG_WIDTH: f32 = 360.0;
+const FONT_SIZE: f32 = 14.0;
+const HEADER_FONT_SIZE: f32 = 15.0;
+/// 排版缓存上限 - 超过后清空（只有可见行会重新排版）
+const MAX_CACHED_GALLEYS: usize = 4096;
+
+// Color constants
+const COLOR_SELECTED: egui::Color32 = egui::Color32::from_rgb(200, 230, 255);
+const COLOR_HOVER: egui::Color32 = egui::Color32::from_rgb(240, 248, 255);
+const COLOR_TRANSPARENT: egui::Color32 = egui::Color32::TRANSPARENT;
+const COLOR_DROP_MARKER: egui::Color32 = egui::Color32::from_rgb(60, 130, 220);
+
+/// Action requested from the site list, handled by MainWindow
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum SiteAction {
+    Edit(String),
+    SetEnabled(String, bool),
+    /// 已确认的删除；archive 为 true 时先归档站点的文件
+    Delete { name: String, archive: bool },
+}
+
+// ==============================================================================
+// Columns, filter and sorting
+// ==============================================================================
+
+/// 站点列表的列
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum SiteColumn {
+    Enabled,
+    Name,
+    Type,
+    Port,
+    Domain,
+    Https,
+}
+
+impl SiteColumn {
+    const ALL: [SiteColumn; 6] = [SiteColumn::Enabled, SiteColumn::Name, SiteColumn::Type, SiteColumn::Port, SiteColumn::Domain, SiteColumn::Https];
+
+    /// 保存在设置中的标识
+    fn id(self) -> &'static str {
+        match self {
+            SiteColumn::Enabled => "enabled",
+            SiteColumn::Name => "name",
+            SiteColumn::Type => "type",
+            SiteColumn::Port => "port",
+            SiteColumn::Domain => "domain",
+            SiteColumn::Https => "https",
+        }
+    }
+
+    fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|column| column.id() == id)
+    }
+
+    fn title_key(self) -> Key {
+        match self {
+            SiteColumn::Enabled => Key::SiteListEnabled,
+            SiteColumn::Name => Key::SiteListSite,
+            SiteColumn::Type => Key::SiteListType,
+            SiteColumn::Port => Key::SiteListPort,
+            SiteColumn::Domain => Key::SiteListDomain,
+            SiteColumn::Https => Key::SiteListHttps,
+        }
+    }
+
+    fn default_width(self) -> f32 {
+        match self {
+            SiteColumn::Enabled => 64.0,
+            SiteColumn::Name => 200.0,
+            SiteColumn::Type => 100.0,
+            SiteColumn::Port => 100.0,
+            SiteColumn::Domain => 120.0,
+            SiteColumn::Https => 80.0,
+        }
+    }
+
+    /// 域名列占用剩余宽度（设置中的宽度为最小宽度）
+    fn is_flexible(self) -> bool {
+        self == SiteColumn::Domain
+    }
+
+    fn compare(self, a: &Site, b: &Site) -> Ordering {
+        let type_rank = |site: &Site| SiteType::ALL.iter().position(|t| *t == site.site_type());
+        match self {
+            SiteColumn::Enabled => a.enabled.cmp(&b.enabled),
+            SiteColumn::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            SiteColumn::Type => type_rank(a).cmp(&type_rank(b)),
+            SiteColumn::Port => a.port.cmp(&b.port),
+            SiteColumn::Domain => a.domain.to_lowercase().cmp(&b.domain.to_lowercase()),
+            SiteColumn::Https => a.enable_https.cmp(&b.enable_https),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct ColumnState {
+    column: SiteColumn,
+    width: f32,
+    visible: bool,
+}
+
+/// 列的顺序、宽度、可见性和排序
+#[derive(Clone, Debug)]
+struct ColumnLayout {
+    columns: Vec<ColumnState>,
+    /// (排序列, 是否降序)
+    sort: Option<(SiteColumn, bool)>,
+}
+
+impl Default for ColumnLayout {
+    fn default() -> Self {
+        Self::from_settings(&SiteListSettings::default())
+    }
+}
+
+impl ColumnLayout {
+    /// 从设置恢复；忽略未知的列，缺少的列按默认值追加
+    fn from_settings(settings: &SiteListSettings) -> Self {
+        let mut columns: Vec<ColumnState> = Vec::new();
+        for saved in &settings.columns {
+            if let Some(column) = SiteColumn::from_id(&saved.id) {
+                if columns.iter().all(|state| state.column != column) {
+                    columns.push(ColumnState {
+                        column,
+                        width: saved.width.max(MIN_COLUMN_WIDTH),
+                        visible: saved.visible,
+                    });
+                }
+            }
+        }
+        for column in SiteColumn::ALL {
+            if columns.iter().all(|state| state.column != column) {
+                columns.push(ColumnState { column, width: column.default_width(), visible: true });
+            }
+        }
+        if columns.iter().all(|state| !state.visible) {
+            columns[0].visible = true;
+        }
+        let sort = settings.sort_column.as_deref()
+            .and_then(SiteColumn::from_id)
+            .map(|column| (column, settings.sort_descending));
+        Self { columns, sort }
+    }
+
+    fn to_settings(&self) -> SiteListSettings {
+        SiteListSettings {
+            columns: self.columns.iter().map(|state| ColumnSettings {
+                id: state.column.id().to_string(),
+                width: state.width,
+                visible: state.visible,
+            }).collect(),
+            sort_column: self.sort.map(|(column, _)| column.id().to_string()),
+            sort_descending: self.sort.map(|(_, descending)| descending).unwrap_or(false),
+        }
+    }
+
+    fn visible(&self) -> impl Iterator<Item = &ColumnState> {
+        self.columns.iter().filter(|state| state.visible)
+    }
+
+    fn state_mut(&mut self, column: SiteColumn) -> Option<&mut ColumnState> {
+        self.columns.iter_mut().find(|state| state.column == column)
+    }
+
+    /// 显示或隐藏一列；至少保留一列可见
+    fn set_visible(&mut self, column: SiteColumn, visible: bool) {
+        if !visible && self.visible().count() <= 1 {
+            return;
+        }
+        if let Some(state) = self.state_mut(column) {
+            state.visible = visible;
+        }
+    }
+
+    /// 把 column 移到 before 之前（None 表示移到末尾）
+    fn move_column(&mut self, column: SiteColumn, before: Option<SiteColumn>) {
+        if before == Some(column) {
+            return;
+        }
+        let Some(from) = self.columns.iter().position(|state| state.column == column) else {
+            return;
+        };
+        let state = self.columns.remove(from);
+        let to = before
+            .and_then(|before| self.columns.iter().position(|state| state.column == before))
+            .unwrap_or(self.columns.len());
+        self.columns.insert(to, state);
+    }
+
+    /// 点击表头：先升序，再次点击切换为降序
+    fn toggle_sort(&mut self, column: SiteColumn) {
+        self.sort = match self.sort {
+            Some((current, descending)) if current == column => Some((column, !descending)),
+            _ => Some((column, false)),
+        };
+    }
+
+    /// 本帧各可见列的实际宽度 - 弹性列填充剩余空间
+    fn resolved_widths(&self, available_width: f32) -> Vec<(SiteColumn, f32)> {
+        let mut widths: Vec<(SiteColumn, f32)> = self.visible().map(|state| (state.column, state.width)).collect();
+        let extra = (available_width - content_width(&widths)).max(0.0);
+        for (column, width) in &mut widths {
+            if column.is_flexible() {
+                *width += extra;
+            }
+        }
+        widths
+    }
+
+    /// 按当前排序列排序行（站点下标）
+    fn sort_rows(&self, sites: &[Site], rows: &mut [usize]) {
+        if let Some((column, descending)) = self.sort {
+            rows.sort_by(|a, b| {
+                let (a, b) = (&sites[*a], &sites[*b]);
+                let order = column.compare(a, b).then_with(|| a.name.cmp(&b.name));
+                if descending { order.reverse() } else { order }
+            });
+        }
+    }
+}
+
+/// 搜索和过滤条件
+#[derive(Clone, Debug, Default, PartialEq)]
+struct SiteFilter {
+    query: String,
+    site_type: Option<SiteType>,
+    /// Some(true) 仅 HTTPS，Some(false) 仅 HTTP
+    https: Option<bool>,
+}
+
+impl SiteFilter {
+    fn matches(&self, site: &Site) -> bool {
+        let query = self.query.trim().to_lowercase();
+        (query.is_empty()
+            || site.name.to_lowercase().contains(&query)
+            || site.domain.to_lowercase().contains(&query))
+            && self.site_type.is_none_or(|site_type| site.site_type() == site_type)
+            && self.https.is_none_or(|https| site.enable_https == https)
+    }
+}
+
+/// 过滤、排序后的行；只在数据、过滤条件或排序变化时重建
+struct SiteView {
+    sites: Arc<Vec<Site>>,
+    filter: SiteFilter,
+    sort: Option<(SiteColumn, bool)>,
+    /// 显示顺序的站点下标
+    rows: Vec<usize>,
+}
+
+impl SiteView {
+    fn build(sites: Arc<Vec<Site>>, filter: &SiteFilter, layout: &ColumnLayout) -> Self {
+        let mut rows: Vec<usize> = (0..sites.len()).filter(|i| filter.matches(&sites[*i])).collect();
+        layout.sort_rows(&sites, &mut rows);
+        Self {
+            sites,
+            filter: filter.clone(),
+            sort: layout.sort,
+            rows,
+        }
+    }
+
+    fn is_current(&self, sites: &Arc<Vec<Site>>, filter: &SiteFilter, layout: &ColumnLayout) -> bool {
+        Arc::ptr_eq(&self.sites, sites) && &self.filter == filter && self.sort == layout.sort
+    }
+}
+
+/// 单元格文字的排版缓存
+///
+/// 排版时使用占位颜色，绘制时再指定颜色，选中状态变化不需要重新排版。
+/// 缓存同时保存文字，翻译晚到时（文字变化）会重新排版。
+#[derive(Default)]
+struct GalleyCache {
+    language: Option<Language>,
+    sites: Option<Arc<Vec<Site>>>,
+    widths: Vec<(SiteColumn, f32)>,
+    galleys: HashMap<(usize, SiteColumn), (String, Arc<egui::Galley>)>,
+}
+
+impl GalleyCache {
+    /// 语言、数据或列宽变化时清空
+    fn invalidate_if_changed(&mut self, language: &Language, sites: &Arc<Vec<Site>>, widths: &[(SiteColumn, f32)]) {
+        let current = self.language.as_ref() == Some(language)
+            && self.sites.as_ref().is_some_and(|cached| Arc::ptr_eq(cached, sites))
+            && self.widths == widths;
+        if !current {
+            self.language = Some(language.clone());
+            self.sites = Some(sites.clone());
+            self.widths = widths.to_vec();
+            self.galleys.clear();
+        }
+    }
+
+    fn get(&mut self, painter: &egui::Painter, index: usize, column: SiteColumn, text: String, width: f32) -> Arc<egui::Galley> {
+        match self.galleys.get(&(index, column)) {
+            Some((cached, galley)) if *cached == text => galley.clone(),
+            _ => {
+                let galley = layout_cell(painter, text.clone(), width, FONT_SIZE);
+                if self.galleys.len() >= MAX_CACHED_GALLEYS {
+                    self.galleys.clear();
+                }
+                self.galleys.insert((index, column), (text, galley.clone()));
+                galley
+            }
+        }
+    }
+}
+
+/// 单行排版，超出宽度的部分以 … 省略
+fn layout_cell(painter: &egui::Painter, text: String, width: f32, font_size: f32) -> Arc<egui::Galley> {
+    let format = egui::TextFormat::simple(egui::FontId::proportional(font_size), egui::Color32::PLACEHOLDER);
+    let mut job = egui::text::LayoutJob::single_section(text, format);
+    job.wrap = egui::text::TextWrapping {
+        max_width: width,
+        max_rows: 1,
+        break_anywhere: true,
+        ..Default::default()
+    };
+    painter.layout_job(job)
+}
+
+/// 在 [x, x + width] 中水平居中、以 center_y 垂直居中绘制
+fn paint_centered(painter: &egui::Painter, galley: Arc<egui::Galley>, x: f32, center_y: f32, width: f32, color: egui::Color32) {
+    let size = galley.size();
+    let pos = egui::pos2(x + (width - size.x).max(0.0) / 2.0, center_y - size.y / 2.0);
+    painter.galley(pos, galley, color);
+}
+
+/// 各列加间距和边距后的总宽度
+fn content_width(widths: &[(SiteColumn, f32)]) -> f32 {
+    widths.iter().map(|(_, width)| width).sum::<f32>()
+        + SPACING * widths.len().saturating_sub(1) as f32
+        + ROW_PADDING_LEFT * 2.0
+}
+
+// ==============================================================================
+// Site List Panel
+// ==============================================================================
+
+/// Site list panel component
+pub(super) struct SiteListPanel {
+    /// 站点列表 - 由 UiModule 根据 SitesUpdated 整体替换
+    sites: Arc<std::sync::RwLock<Arc<Vec<Site>>>>,
+    selected_site: Option<String>,
+    show_context_menu: bool,
+    /// 右键菜单所属站点是否由 easyNginx 管理
+    context_menu_managed: bool,
+    /// 右键菜单所属站点当前是否启用、能否在这里启用或停用
+    context_menu_enabled: bool,
+    context_menu_toggleable: bool,
+    context_menu_pos: egui::Pos2,
+    /// 本帧产生的操作
+    action: Option<SiteAction>,
+    /// 等待确认删除的站点
+    confirm_delete: Option<String>,
+    archive_on_delete: bool,
+    /// 上一次启用、停用或删除失败的原因
+    error: Option<String>,
+    filter: SiteFilter,
+    layout: ColumnLayout,
+    /// 正在拖动调整顺序的列
+    dragging_column: Option<SiteColumn>,
+    view: Option<SiteView>,
+    galleys: GalleyCache,
+    /// 上一帧列表区域的宽度和水平滚动位置（表头固定在列表上方，与列表对齐）
+    list_width: Option<f32>,
+    scroll_offset_x: f32,
+}
+
+impl SiteListPanel {
+    pub fn new(sites: Arc<std::sync::RwLock<Arc<Vec<Site>>>>) -> Self {
+        Self {
+            sites,
+            selected_site: None,
+            show_context_menu: false,
+            context_menu_managed: false,
+            context_menu_enabled: false,
+            context_menu_toggleable: false,
+            context_menu_pos: egui::Pos2::ZERO,
+            action: None,
+            confirm_delete: None,
+            archive_on_delete: true,
+            error: None,
+            filter: SiteFilter::default(),
+            layout: ColumnLayout::from_settings(&Settings::load().site_list),
+            dragging_column: None,
+            view: None,
+            galleys: GalleyCache::default(),
+            list_width: None,
+            scroll_offset_x: 0.0,
+        }
+    }
+    
+    /// 当前站点列表（共享，不复制）
+    pub fn sites(&self) -> Arc<Vec<Site>> {
+        self.sites.read()
+            .map(|sites| sites.clone())
+            .unwrap_or_else(|e| e.into_inner().clone())
+    }
+    
+    /// 取出本帧产生的操作
+    pub fn take_action(&mut self) -> Option<SiteAction> {
+        self.action.take()
+    }
+    
+    /// 显示操作失败的原因；None 清除
+    pub fn set_error(&mut self, error: Option<String>) {
+        self.error = error;
+    }
+    
+    pub fn set_language(&mut self, _language: Language) {
+        // 语言切换时，site list 会自动使用新语言的翻译
+        // 不需要额外操作，因为 translate_fn 按当前语言查找缓存
+    }
+    
+    /// 保存列布局和排序
+    fn save_layout(&self) {
+        let site_list = self.layout.to_settings();
+        if let Err(e) = Settings::update(|settings| settings.site_list = site_list) {
+            eprintln!("[UI] Failed to save site list layout: {}", e);
+        }
+    }
+    
+    /// 数据、过滤条件或排序变化时重建 SiteView
+    fn current_view(&mut self) -> SiteView {
+        let sites = self.sites();
+        match self.view.take() {
+            Some(view) if view.is_current(&sites, &self.filter, &self.layout) => view,
+            _ => SiteView::build(sites, &self.filter, &self.layout),
+        }
+    }
+    
+    pub fn ui(&mut self, ctx: &egui::Context, ui: &mut egui::Ui, language: &Language, translate_fn: &dyn Fn(Key) -> String) {
+        let view = self.current_view();
+        
+        self.render_toolbar(ui, view.rows.len(), view.sites.len(), language, translate_fn);
+        if let Some(error) = &self.error {
+            ui.colored_label(COLOR_ERROR, error);
+        }
+        ui.separator();
+        
+        let list_width = self.list_width.unwrap_or_else(|| ui.available_width());
+        let widths = self.layout.resolved_widths(list_width);
+        self.galleys.invalidate_if_changed(language, &view.sites, &widths);
+        
+        // 表头固定在列表上方，随列表水平滚动
+        let (header_rect, _) = ui.allocate_exact_size(egui::vec2(ui.available_width(), HEADER_HEIGHT), egui::Sense::hover());
+        let mut header_ui = ui.child_ui(header_rect, *ui.layout());
+        header_ui.set_clip_rect(header_rect.intersect(ui.clip_rect()));
+        let header_origin = header_rect.min - egui::vec2(self.scroll_offset_x, 0.0);
+        self.render_header(&mut header_ui, header_origin, &widths, translate_fn);
+        ui.separator();
+        
+        if view.rows.is_empty() && !view.sites.is_empty() {
+            ui.add_space(SPACING);
+            ui.vertical_centered(|ui| ui.weak(translate_fn(Key::SiteListNoMatch)));
+        }
+        
+        let output = egui::ScrollArea::both()
+            .auto_shrink([false, false])
+            .show_rows(ui, ROW_HEIGHT, view.rows.len(), |ui, range| {
+                self.render_rows(ui, ctx, &view, range, &widths, language, translate_fn);
+            });
+        self.list_width = Some(output.inner_rect.width());
+        self.scroll_offset_x = output.state.offset.x;
+        self.view = Some(view);
+        
+        self.render_delete_confirmation(ctx, language, translate_fn);
+    }
+    
+    fn render_toolbar(&mut self, ui: &mut egui::Ui, shown: usize, total: usize, language: &Language, translate_fn: &dyn Fn(Key) -> String) {
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut self.filter.query)
+                .hint_text(translate_fn(Key::SiteListSearchHint))
+                .desired_width(SEARCH_WIDTH));
+            
+            let type_text = |site_type: Option<SiteType>| match site_type {
+                Some(site_type) => translate_fn(site_type.translation_key()),
+                None => translate_fn(Key::SiteListFilterAllTypes),
+            };
+            egui::ComboBox::from_id_source("site_list_type_filter")
+                .selected_text(type_text(self.filter.site_type))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.filter.site_type, None, type_text(None));
+                    for site_type in SiteType::ALL {
+                        ui.selectable_value(&mut self.filter.site_type, Some(site_type), type_text(Some(site_type)));
+                    }
+                });
+            
+            let https_text = |https: Option<bool>| translate_fn(match https {
+                None => Key::SiteListFilterAnyHttps,
+                Some(true) => Key::SiteListFilterHttpsOnly,
+                Some(false) => Key::SiteListFilterHttpOnly,
+            });
+            egui::ComboBox::from_id_source("site_list_https_filter")
+                .selected_text(https_text(self.filter.https))
+                .show_ui(ui, |ui| {
+                    for https in [None, Some(true), Some(false)] {
+                        ui.selectable_value(&mut self.filter.https, https, https_text(https));
+                    }
+                });
+            
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.menu_button(translate_fn(Key::SiteListColumns), |ui| {
+                    self.render_column_menu(ui, translate_fn);
+                });
+                let args = TranslationArgs::new().count("shown", shown).count("total", total);
+                ui.weak(format_message(&translate_fn(Key::SiteListShowing), language, &args));
+            });
+        });
+    }
+    
+    /// 列的显示/隐藏和重置（工具栏菜单和表头右键菜单共用）
+    fn render_column_menu(&mut self, ui: &mut egui::Ui, translate_fn: &dyn Fn(Key) -> String) {
+        let mut changed = false;
+        for column in SiteColumn::ALL {
+            let mut visible = self.layout.columns.iter().any(|state| state.column == column && state.visible);
+            if ui.checkbox(&mut visible, translate_fn(column.title_key())).changed() {
+                self.layout.set_visible(column, visible);
+                changed = true;
+            }
+        }
+        ui.separator();
+        if ui.button(translate_fn(Key::SiteListResetLayout)).clicked() {
+            self.layout = ColumnLayout::default();
+            changed = true;
+            ui.close_menu();
+        }
+        if changed {
+            self.save_layout();
+        }
+    }
+    
+    fn render_header(&mut self, ui: &mut egui::Ui, origin: egui::Pos2, widths: &[(SiteColumn, f32)], translate_fn: &dyn Fn(Key) -> String) {
+        let rect = egui::Rect::from_min_size(origin, egui::vec2(content_width(widths), HEADER_HEIGHT));
+        
+        let center_y = rect.center().y;
+        let mut x = rect.left() + ROW_PADDING_LEFT;
+        let mut layout_changed = false;
+        // 拖动表头时的放置位置：(插入到此列之前, 标记的 x 坐标)
+        let pointer_x = ui.ctx().pointer_hover_pos().map(|pos| pos.x);
+        let mut drop_target: Option<(Option<SiteColumn>, f32)> = None;
+        
+        for (column, width) in widths {
+            let (column, width) = (*column, *width);
+            let cell = egui::Rect::from_min_size(egui::pos2(x, rect.top()), egui::vec2(width, HEADER_HEIGHT));
+            let response = ui.interact(cell, ui.id().with(("site_list_header", column.id())), egui::Sense::click_and_drag());
+            
+            let mut text = translate_fn(column.title_key());
+            if let Some((sorted, descending)) = self.layout.sort {
+                if sorted == column {
+                    text.push_str(if descending { " ▼" } else { " ▲" });
+                }
+            }
+            let color = if response.hovered() { ui.visuals().hyperlink_color } else { ui.visuals().strong_text_color() };
+            let galley = layout_cell(ui.painter(), text, width, HEADER_FONT_SIZE);
+            paint_centered(ui.painter(), galley, x, center_y, width, color);
+            
+            if response.clicked() {
+                self.layout.toggle_sort(column);
+                layout_changed = true;
+            }
+            if response.drag_started() {
+                self.dragging_column = Some(column);
+            }
+            if let Some(pointer_x) = pointer_x {
+                if drop_target.is_none() && pointer_x < cell.center().x {
+                    drop_target = Some((Some(column), x - SPACING / 2.0));
+                }
+            }
+            response.context_menu(|ui| self.render_column_menu(ui, translate_fn));
+            
+            // 列右侧的间隙用于拖动调整宽度
+            let handle = egui::Rect::from_min_size(egui::pos2(x + width, rect.top()), egui::vec2(SPACING, HEADER_HEIGHT));
+            let handle_response = ui.interact(handle, ui.id().with(("site_list_resize", column.id())), egui::Sense::drag())
+                .on_hover_cursor(egui::CursorIcon::ResizeHorizontal);
+            if handle_response.dragged() {
+                // 弹性列显示宽度大于保存的最小宽度，从显示宽度开始调整
+                let new_width = (width + handle_response.drag_delta().x).max(MIN_COLUMN_WIDTH);
+                if let Some(state) = self.layout.state_mut(column) {
+                    state.width = if column.is_flexible() { (state.width + handle_response.drag_delta().x).max(MIN_COLUMN_WIDTH) } else { new_width };
+                }
+            }
+            if handle_response.drag_released() {
+                layout_changed = true;
+            }
+            
+            x += width + SPACING;
+        }
+        
+        if let Some(dragging) = self.dragging_column {
+            let (before, marker_x) = drop_target.unwrap_or((None, x - SPACING / 2.0));
+            if ui.input(|i| i.pointer.any_down()) {
+                ui.painter().vline(marker_x, rect.y_range(), egui::Stroke::new(2.0, COLOR_DROP_MARKER));
+            } else {
+                self.dragging_column = None;
+                self.layout.move_column(dragging, before);
+                layout_changed = true;
+            }
+        }
+        
+        if layout_changed {
+            self.save_layout();
+        }
+    }
+    
+    #[allow(clippy::too_many_arguments)]
+    fn render_rows(&mut self, ui: &mut egui::Ui, ctx: &egui::Context, view: &SiteView, range: std::ops::Range<usize>, widths: &[(SiteColumn, f32)], language: &Language, translate_fn: &dyn Fn(Key) -> String) {
+        let mut context_menu_action: Option<(String, egui::Pos2)> = None;
+        let mut double_click_action: Option<String> = None;
+        
+        for &index in &view.rows[range] {
+            let site = &view.sites[index];
+            let selected = self.selected_site.as_ref() == Some(&site.name);
+            let row_rect = self.allocate_row_rect(ui, widths);
+            let mut row_response = ui.interact(row_rect, ui.id().with(&site.name), egui::Sense::click());
+            if let SiteOrigin::Discovered { path, line, .. } = &site.origin {
+                let args = TranslationArgs::new()
+                    .text("file", &path.display().to_string())
+                    .text("line", &line.to_string());
+                row_response = row_response.on_hover_text(format_message(&translate_fn(Key::SiteListDiscoveredFrom), language, &args));
+            } else if let Some(path) = &site.imported_from {
+                let args = TranslationArgs::new().text("file", &path.display().to_string());
+                row_response = row_response.on_hover_text(format_message(&translate_fn(Key::SiteListImportedFrom), language, &args));
+            }
+            
+            // Draw background
+            self.draw_row_background(ui, row_rect, selected, row_response.hovered());
+            
+            // Draw content
+            self.draw_row_content(ui, row_rect, index, site, selected, widths, translate_fn);
+            if let Some(enabled) = self.render_enabled_toggle(ui, row_rect, site, widths, translate_fn) {
+                self.set_enabled(&site.name, enabled);
+            }
+            
+            // Handle interactions
+            if row_response.clicked() {
+                self.selected_site = Some(site.name.clone());
+            }
+            if row_response.double_clicked() {
+                self.selected_site = Some(site.name.clone());
+                if site.is_managed() {
+                    double_click_action = Some(site.name.clone());
+                }
+            }
+            if row_response.secondary_clicked() {
+                self.selected_site = Some(site.name.clone());
+                self.context_menu_managed = site.is_managed();
+                self.context_menu_enabled = site.enabled;
+                self.context_menu_toggleable = is_toggleable(site);
+                let pos = row_response.interact_pointer_pos()
+                    .unwrap_or_else(|| row_rect.center());
+                context_menu_action = Some((site.name.clone(), pos));
+            }
+        }
+        
+        // Process actions after iteration
+        if let Some(name) = double_click_action {
+            self.edit_site(&name);
+        }
+        if let Some((name, pos)) = context_menu_action {
+            self.show_context_menu_at(ctx, ui, &name, pos);
+        }
+        
+        // Render context menu if open
+        if self.show_context_menu {
+            self.render_context_menu(ctx, ui, translate_fn);
+        }
+    }
+    
+    fn allocate_row_rect(&self, ui: &mut egui::Ui, widths: &[(SiteColumn, f32)]) -> egui::Rect {
+        let width = ui.available_width().max(content_width(widths));
+        let pos = ui.cursor().min;
+        let rect = egui::Rect::from_min_size(pos, egui::vec2(width, ROW_HEIGHT));
+        ui.advance_cursor_after_rect(rect);
+        rect
+    }
+    
+    fn draw_row_background(&self, ui: &egui::Ui, rect: egui::Rect, selected: bool, hovered: bool) {
+        let color = if selected {
+            COLOR_SELECTED
+        } else if hovered {
+            COLOR_HOVER
+        } else {
+            COLOR_TRANSPARENT
+        };
+        
+        if color != COLOR_TRANSPARENT {
+            ui.painter().rect_filled(rect, 4.0, color);
+        }
+    }
+    
+    #[allow(clippy::too_many_arguments)]
+    fn draw_row_content(&mut self, ui: &egui::Ui, rect: egui::Rect, index: usize, site: &Site, selected: bool, widths: &[(SiteColumn, f32)], translate_fn: &dyn Fn(Key) -> String) {
+        let painter = ui.painter();
+        let start_x = rect.left() + ROW_PADDING_LEFT;
+        let center_y = rect.center().y;
+        let mut x = start_x;
+        let text_color = row_text_color(ui.visuals(), site, selected);
+        
+        for (column, width) in widths {
+            if *column == SiteColumn::Enabled {
+                x += width + SPACING;
+                continue;
+            }
+            let text = Self::get_column_text(site, *column, translate_fn);
+            let galley = self.galleys.get(painter, index, *column, text, *width);
+            paint_centered(painter, galley, x, center_y, *width, text_color);
+            
+            x += width + SPACING;
+        }
+    }
+    
+    /// 在启用列中绘制复选框；点击时返回新的状态
+    fn render_enabled_toggle(&self, ui: &mut egui::Ui, rect: egui::Rect, site: &Site, widths: &[(SiteColumn, f32)], translate_fn: &dyn Fn(Key) -> String) -> Option<bool> {
+        let mut x = rect.left() + ROW_PADDING_LEFT;
+        for (column, width) in widths {
+            if *column == SiteColumn::Enabled {
+                let size = egui::vec2(ui.spacing().interact_size.y, ui.spacing().interact_size.y);
+                let cell = egui::Rect::from_center_size(egui::pos2(x + width / 2.0, rect.center().y), size);
+                let mut enabled = site.enabled;
+                let toggleable = is_toggleable(site);
+                let mut response = ui.put(cell, |ui: &mut egui::Ui| {
+                    ui.add_enabled(toggleable, egui::Checkbox::without_text(&mut enabled))
+                });
+                if site.is_managed() && !toggleable {
+                    response = response.on_disabled_hover_text(translate_fn(Key::SiteListToggleUnavailable));
+                }
+                return response.changed().then_some(enabled);
+            }
+            x += width + SPACING;
+        }
+        None
+    }
+    
+    fn get_column_text(site: &Site, column: SiteColumn, translate_fn: &dyn Fn(Key) -> String) -> String {
+        match column {
+            SiteColumn::Enabled => String::new(),
+            SiteColumn::Name => site.name.clone(),
+            SiteColumn::Type => translate_fn(site.site_type().translation_key()),
+            SiteColumn::Port => site.port_label(),
+            SiteColumn::Domain => site.domain.clone(),
+            SiteColumn::Https => {
+                if site.enable_https {
+                    translate_fn(Key::SiteListHttpsYes)
+                } else {
+                    translate_fn(Key::SiteListHttpsNo)
+                }
+            }
+        }
+    }
+    
+    fn show_context_menu_at(&mut self, ctx: &egui::Context, _ui: &egui::Ui, site_name: &str, pos: egui::Pos2) {
+        self.selected_site = Some(site_name.into());
+        self.show_context_menu = true;
+        
+        // Ensure menu doesn't go off screen
+        let screen_rect = ctx.screen_rect();
+        let menu_width = CONTEXT_MENU_WIDTH;
+        let menu_height = CONTEXT_MENU_BUTTON_HEIGHT * CONTEXT_MENU_BUTTONS + 8.0; // buttons + padding
+        
+        let mut adjusted_pos = pos;
+        if pos.x + menu_width > screen_rect.max.x {
+            adjusted_pos.x = screen_rect.max.x - menu_width - 10.0;
+        }
+        if pos.y + menu_height > screen_rect.max.y {
+            adjusted_pos.y = screen_rect.max.y - menu_height - 10.0;
+        }
+        
+        self.context_menu_pos = adjusted_pos;
+    }
+    
+    fn render_context_menu(&mut self, ctx: &egui::Context, ui: &egui::Ui, translate_fn: &dyn Fn(Key) -> String) {
+        if let Some(site) = self.selected_site.clone() {
+            egui::Window::new("site_context_menu")
+                .title_bar(false)
+                .resizable(false)
+                .fixed_pos(self.context_menu_pos)
+                .show(ctx, |ui| {
+                    ui.set_min_width(CONTEXT_MENU_WIDTH);
+                    
+                    ui.vertical(|ui| {
+                        ui.set_width(CONTEXT_MENU_WIDTH);
+                        
+                        // 从 nginx 配置中发现的站点只读
+                        let managed = self.context_menu_managed;
+                        if self.menu_button(ui, Key::SiteListEdit, managed, &translate_fn) {
+                            self.show_context_menu = false;
+                            self.edit_site(&site);
+                        }
+                        
+                        let enabled = self.context_menu_enabled;
+                        let toggle_key = if enabled { Key::SiteListDisable } else { Key::SiteListEnable };
+                        if self.menu_button(ui, toggle_key, self.context_menu_toggleable, &translate_fn) {
+                            self.show_context_menu = false;
+                            self.set_enabled(&site, !enabled);
+                        }
+                        
+                        if self.menu_button(ui, Key::SiteListDelete, managed, &translate_fn) {
+                            self.show_context_menu = false;
+                            self.confirm_delete = Some(site.clone());
+                        }
+                    });
+                });
+            
+            // Close menu when clicking outside
+            let clicked_outside = ui.input(|i| i.pointer.any_click())
+                && !ctx.is_pointer_over_area();
+            if clicked_outside {
+                self.show_context_menu = false;
+            }
+        } else {
+            self.show_context_menu = false;
+        }
+    }
+    
+    fn menu_button(&self, ui: &mut egui::Ui, key: Key, enabled: bool, translate_fn: &dyn Fn(Key) -> String) -> bool {
+        ui.add_enabled_ui(enabled, |ui| {
+            ui.add_sized(
+                [CONTEXT_MENU_WIDTH, CONTEXT_MENU_BUTTON_HEIGHT],
+                egui::Button::new(translate_fn(key))
+            ).clicked()
+        }).inner
+    }
+    
+    fn edit_site(&mut self, site: &str) {
+        self.action = Some(SiteAction::Edit(site.to_string()));
+    }
+    
+    fn set_enabled(&mut self, site: &str, enabled: bool) {
+        self.error = None;
+        self.action = Some(SiteAction::SetEnabled(site.to_string(), enabled));
+    }
+    
+    fn delete_site(&mut self, site: &str, archive: bool) {
+        self.error = None;
+        self.action = Some(SiteAction::Delete { name: site.to_string(), archive });
+        if self.selected_site.as_deref() == Some(site) {
+            self.selected_site = None;
+        }
+    }
+    
+    /// 删除确认对话框
+    fn render_delete_confirmation(&mut self, ctx: &egui::Context, language: &Language, translate_fn: &dyn Fn(Key) -> String) {
+        let Some(site) = self.confirm_delete.clone() else {
+            return;
+        };
+        render_backdrop(ctx);
+        
+        let mut confirmed = false;
+        let mut cancelled = false;
+        egui::Window::new(translate_fn(Key::SiteListDeleteTitle))
+            .id(egui::Id::new("site_delete_confirmation"))
+            .collapsible(false)
+            .resizable(false)
+            .default_width(DELETE_DIALOG_WIDTH)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                let args = TranslationArgs::new().text("name", &site);
+                ui.label(format_message(&translate_fn(Key::SiteListDeleteConfirm), language, &args));
+                ui.add_space(SPACING / 2.0);
+                ui.checkbox(&mut self.archive_on_delete, translate_fn(Key::SiteListDeleteArchive));
+                ui.add_space(SPACING / 2.0);
+                ui.horizontal(|ui| {
+                    confirmed = ui.button(translate_fn(Key::SiteListDeleteYes)).clicked();
+                    cancelled = ui.button(translate_fn(Key::SiteListDeleteCancel)).clicked();
+                });
+            });
+        
+        if confirmed {
+            self.confirm_delete = None;
+            self.delete_site(&site, self.archive_on_delete);
+        } else if cancelled {
+            self.confirm_delete = None;
+        }
+    }
+}
+
+/// 站点能否在列表中启用或停用 - 导入的站点由 nginx.conf 原有的 include 加载
+fn is_toggleable(site: &Site) -> bool {
+    site.is_managed() && site.imported_from.is_none()
+}
+
+/// 行文字颜色 - 停用的站点显示为灰色
+fn row_text_color(visuals: &egui::Visuals, site: &Site, selected: bool) -> egui::Color32 {
+    if !site.enabled {
+        visuals.weak_text_color()
+    } else if selected {
+        visuals.strong_text_color()
+    } else {
+        visuals.text_color()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout_round_trips_and_repairs_settings() {
+        let mut layout = ColumnLayout::default();
+        layout.move_column(SiteColumn::Https, Some(SiteColumn::Enabled));
+        layout.set_visible(SiteColumn::Type, false);
+        layout.toggle_sort(SiteColumn::Port);
+        layout.toggle_sort(SiteColumn::Port);
+
+        let mut settings = layout.to_settings();
+        assert_eq!(settings.columns[0].id, "https");
+        assert_eq!(settings.sort_column.as_deref(), Some("port"));
+        assert!(settings.sort_descending);
+
+        // 未知的列被忽略，缺少的列追加在末尾
+        settings.columns.retain(|column| column.id != "domain");
+        settings.columns.push(ColumnSettings { id: "removed".into(), width: 10.0, visible: true });
+        let restored = ColumnLayout::from_settings(&settings);
+        let order: Vec<SiteColumn> = restored.columns.iter().map(|state| state.column).collect();
+        assert_eq!(order, [SiteColumn::Https, SiteColumn::Enabled, SiteColumn::Name, SiteColumn::Type, SiteColumn::Port, SiteColumn::Domain]);
+        assert!(!restored.columns[3].visible);
+    }
+
+    #[test]
+    fn filter_and_sort() {
+        let mut sites = vec![Site::new("b", SiteType::Proxy), Site::new("A", SiteType::Static), Site::new("c", SiteType::Static)];
+        sites[0].domain = "api.example.com".into();
+        sites[1].enable_https = true;
+
+        let filter = SiteFilter { query: "API".into(), ..SiteFilter::default() };
+        assert_eq!(sites.iter().filter(|site| filter.matches(site)).count(), 1);
+        let filter = SiteFilter { site_type: Some(SiteType::Static), https: Some(false), ..SiteFilter::default() };
+        assert!(sites.iter().filter(|site| filter.matches(site)).all(|site| site.name == "c"));
+
+        let mut layout = ColumnLayout::default();
+        layout.toggle_sort(SiteColumn::Name);
+        let view = SiteView::build(Arc::new(sites), &SiteFilter::default(), &layout);
+        assert_eq!(view.rows, [1, 0, 2]);
+    }
+
+    #[test]
+    fn galley_cache_is_cleared_only_when_inputs_change() {
+        let ctx = egui::Context::default();
+        let _ = ctx.run(egui::RawInput::default(), |ctx| {
+            let painter = ctx.layer_painter(egui::LayerId::background());
+            let en = Language::source();
+            let sites = Arc::new(vec![Site::new("blog", SiteType::Static)]);
+            let widths = vec![(SiteColumn::Name, 120.0), (SiteColumn::Port, 60.0)];
+            let mut cache = GalleyCache::default();
+
+            cache.invalidate_if_changed(&en, &sites, &widths);
+            let galley = cache.get(&painter, 0, SiteColumn::Name, "blog".into(), 120.0);
+            cache.get(&painter, 0, SiteColumn::Port, "80".into(), 60.0);
+            // 输入不变时保留，同一文本复用排版结果
+            cache.invalidate_if_changed(&en, &sites.clone(), &widths);
+            assert_eq!(cache.galleys.len(), 2);
+            assert!(Arc::ptr_eq(&galley, &cache.get(&painter, 0, SiteColumn::Name, "blog".into(), 120.0)));
+            // 文本变化时重新排版
+            assert!(!Arc::ptr_eq(&galley, &cache.get(&painter, 0, SiteColumn::Name, "shop".into(), 120.0)));
+
+            cache.invalidate_if_changed(&Language::parse("de").unwrap(), &sites, &widths);
+            assert!(cache.galleys.is_empty());
+
+            cache.get(&painter, 0, SiteColumn::Name, "blog".into(), 120.0);
+            // 内容相同但被整体替换的列表也会清空
+            let replaced = Arc::new(sites.as_ref().clone());
+            cache.invalidate_if_changed(&Language::parse("de").unwrap(), &replaced, &widths);
+            assert!(cache.galleys.is_empty());
+
+            cache.get(&painter, 0, SiteColumn::Name, "blog".into(), 120.0);
+            let resized = vec![(SiteColumn::Name, 150.0), (SiteColumn::Port, 60.0)];
+            cache.invalidate_if_changed(&Language::parse("de").unwrap(), &replaced, &resized);
+            assert!(cache.galleys.is_empty());
+        });
+    }
+
+    #[test]
+    fn disabled_sites_are_greyed_out() {
+        let visuals = egui::Visuals::dark();
+        let mut site = Site::new("blog", SiteType::Static);
+        assert_eq!(row_text_color(&visuals, &site, false), visuals.text_color());
+        assert_eq!(row_text_color(&visuals, &site, true), visuals.strong_text_color());
+        site.enabled = false;
+        // 停用的站点即使选中也显示为灰色
+        assert_eq!(row_text_color(&visuals, &site, false), visuals.weak_text_color());
+        assert_eq!(row_text_color(&visuals, &site, true), visuals.weak_text_color());
+        assert!(is_toggleable(&site));
+
+        site.imported_from = Some("/etc/nginx/conf.d/blog.conf".into());
+        assert!(!is_toggleable(&site));
+    }
+}
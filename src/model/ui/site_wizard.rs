@@ -0,0 +1,374 @@
+// MIT License
+// 
+// Copyright (c) 2026 Laffinty
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+
+// ==============================================================================
+// Site Wizard - 新建站点向导
+// ==============================================================================
+// 文件菜单的"新建代理 / PHP / 静态"打开此向导，分步填写：
+//   域名和端口 -> 站点内容（根目录或上游地址）-> TLS -> 预览
+// 预览页显示将生成的 server 块以及与已有站点的端口、server_name 冲突。
+// 创建时发送带 reload 的 SiteChangeRequest：SiteModule 写入并启用站点，
+// nginx -t 通过后才重载 nginx，结果显示在向导中。
+
+use eframe::egui;
+use crate::model::l18n::{format_message, Key, Language, TranslationArgs};
+use crate::model::site::{config, next_request_id, site_conf_path, ReloadOutcome, Site, SiteChange, SiteChangeRequest, SiteChangeResponse, SiteConflict, SiteField, SiteType, ValidationError};
+use super::site_editor::{render_backdrop, render_general_fields, render_kind_fields, render_tls_fields, SiteDraft, COLOR_ERROR, LABEL_WIDTH};
+
+const WIZARD_WIDTH: f32 = 560.0;
+const PREVIEW_HEIGHT: f32 = 260.0;
+const COLOR_WARNING: egui::Color32 = egui::Color32::from_rgb(190, 130, 0);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum WizardStep {
+    Basics,
+    Content,
+    Tls,
+    Preview,
+}
+
+impl WizardStep {
+    const ALL: [WizardStep; 4] = [WizardStep::Basics, WizardStep::Content, WizardStep::Tls, WizardStep::Preview];
+
+    fn index(self) -> usize {
+        Self::ALL.iter().position(|step| *step == self).unwrap_or_default()
+    }
+
+    fn title_key(self) -> Key {
+        match self {
+            WizardStep::Basics => Key::WizardStepBasics,
+            WizardStep::Content => Key::WizardStepContent,
+            WizardStep::Tls => Key::WizardStepTls,
+            WizardStep::Preview => Key::WizardStepPreview,
+        }
+    }
+
+    /// 本步骤填写的字段 - 这些字段有错误时不能进入下一步
+    fn fields(self) -> &'static [SiteField] {
+        match self {
+            WizardStep::Basics => &[SiteField::Name, SiteField::Port, SiteField::Domain],
+            WizardStep::Content => &[SiteField::Root, SiteField::FpmSocket, SiteField::IndexFiles, SiteField::Upstream],
+            WizardStep::Tls => &[SiteField::Certificate, SiteField::CertificateKey],
+            WizardStep::Preview => &[],
+        }
+    }
+}
+
+/// 新建站点向导
+pub struct SiteWizard {
+    is_open: bool,
+    site_type: SiteType,
+    step: WizardStep,
+    draft: SiteDraft,
+    /// 打开向导时的站点列表（检查重名和冲突）
+    existing: Vec<Site>,
+    /// 已发送、等待响应的请求编号
+    pending: Option<u64>,
+    server_errors: Vec<ValidationError>,
+    save_error: Option<String>,
+    /// 站点已创建；显示重载结果
+    outcome: Option<ReloadOutcome>,
+}
+
+impl Default for SiteWizard {
+    fn default() -> Self {
+        Self {
+            is_open: false,
+            site_type: SiteType::Static,
+            step: WizardStep::Basics,
+            draft: SiteDraft::default(),
+            existing: Vec::new(),
+            pending: None,
+            server_errors: Vec::new(),
+            save_error: None,
+            outcome: None,
+        }
+    }
+}
+
+impl SiteWizard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    /// 打开向导创建指定类型的站点
+    pub fn open(&mut self, site_type: SiteType, existing: Vec<Site>) {
+        *self = Self {
+            is_open: true,
+            site_type,
+            draft: SiteDraft::new_site(site_type),
+            existing,
+            ..Self::default()
+        };
+    }
+
+    pub fn close(&mut self) {
+        self.is_open = false;
+        self.pending = None;
+    }
+
+    /// 处理 SiteModule 的响应：成功时显示重载结果，失败时留在预览页显示错误
+    pub fn handle_response(&mut self, response: &SiteChangeResponse) {
+        if self.pending != Some(response.id) {
+            return;
+        }
+        self.pending = None;
+        if response.is_ok() {
+            self.outcome = Some(response.reload.clone().unwrap_or(ReloadOutcome::Reloaded));
+        } else {
+            self.server_errors = response.validation_errors.clone();
+            self.save_error = response.error.clone();
+        }
+    }
+
+    fn title_key(&self) -> Key {
+        match self.site_type {
+            SiteType::Static | SiteType::Custom => Key::WizardTitleStatic,
+            SiteType::Php => Key::WizardTitlePhp,
+            SiteType::Proxy => Key::WizardTitleProxy,
+        }
+    }
+
+    /// 绘制向导；点击创建时返回要发布的请求
+    pub fn ui(&mut self, ctx: &egui::Context, language: &Language, translate_fn: &dyn Fn(Key) -> String) -> Option<SiteChangeRequest> {
+        if !self.is_open {
+            return None;
+        }
+
+        render_backdrop(ctx);
+
+        let site = self.draft.to_site();
+        let other_names: Vec<String> = self.existing.iter().map(|s| s.name.clone()).collect();
+        let mut errors = site.validate(&other_names);
+        errors.extend(self.server_errors.iter().filter(|e| !errors.contains(e)).copied().collect::<Vec<_>>());
+        let conflicts = site.conflicts(&self.existing);
+
+        let mut request = None;
+        let mut close = false;
+        let mut back = false;
+        let response = egui::Window::new(translate_fn(self.title_key()))
+            .id(egui::Id::new("site_wizard"))
+            .collapsible(false)
+            .resizable(false)
+            .default_width(WIZARD_WIDTH)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.set_width(WIZARD_WIDTH);
+                if let Some(outcome) = &self.outcome {
+                    Self::render_outcome(ui, outcome, language, translate_fn);
+                    ui.add_space(12.0);
+                    ui.separator();
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.button(translate_fn(Key::WizardClose)).clicked() {
+                            close = true;
+                        }
+                        // 修改没有生效时可以返回修改
+                        if !outcome.is_applied() && ui.button(translate_fn(Key::WizardBack)).clicked() {
+                            back = true;
+                        }
+                    });
+                    return;
+                }
+
+                let indicator = format_message(
+                    &translate_fn(Key::WizardStepIndicator),
+                    language,
+                    &TranslationArgs::new()
+                        .count("step", self.step.index() + 1)
+                        .count("total", WizardStep::ALL.len())
+                        .text("title", &translate_fn(self.step.title_key())),
+                );
+                ui.strong(indicator);
+                ui.add_space(8.0);
+
+                let before = self.draft.to_site();
+                ui.add_enabled_ui(self.pending.is_none(), |ui| {
+                    self.render_step(ui, &site, &errors, &conflicts, language, translate_fn);
+                });
+                if self.draft.to_site() != before {
+                    self.server_errors.clear();
+                    self.save_error = None;
+                }
+
+                ui.add_space(12.0);
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        let idle = self.pending.is_none();
+                        if self.step == WizardStep::Preview {
+                            let can_create = idle && errors.is_empty() && !conflicts.iter().any(SiteConflict::is_blocking);
+                            if ui.add_enabled(can_create, egui::Button::new(translate_fn(Key::WizardCreate))).clicked() {
+                                request = Some(self.create_request());
+                            }
+                        } else {
+                            let step_ok = !errors.iter().any(|e| self.step.fields().contains(&e.field))
+                                && (self.step != WizardStep::Basics || !conflicts.iter().any(SiteConflict::is_blocking));
+                            if ui.add_enabled(idle && step_ok, egui::Button::new(translate_fn(Key::WizardNext))).clicked() {
+                                self.step = WizardStep::ALL[self.step.index() + 1];
+                            }
+                        }
+                        if ui.add_enabled(idle && self.step != WizardStep::Basics, egui::Button::new(translate_fn(Key::WizardBack))).clicked() {
+                            self.step = WizardStep::ALL[self.step.index() - 1];
+                        }
+                        if ui.add_enabled(idle, egui::Button::new(translate_fn(Key::SiteEditorCancel))).clicked() {
+                            close = true;
+                        }
+                        if !idle {
+                            ui.spinner();
+                            ui.label(translate_fn(Key::WizardCreating));
+                        }
+                    });
+                });
+            });
+
+        if let Some(inner) = &response {
+            ctx.move_to_top(inner.response.layer_id);
+        }
+        if back {
+            self.outcome = None;
+        }
+        if close || (self.pending.is_none() && ctx.input(|i| i.key_pressed(egui::Key::Escape))) {
+            self.close();
+        }
+        request
+    }
+
+    fn render_step(&mut self, ui: &mut egui::Ui, site: &Site, errors: &[ValidationError], conflicts: &[SiteConflict], language: &Language, translate_fn: &dyn Fn(Key) -> String) {
+        let grid = |id: &str| egui::Grid::new(id).num_columns(2).spacing([12.0, 8.0]).min_col_width(LABEL_WIDTH);
+        match self.step {
+            WizardStep::Basics => {
+                grid("site_wizard_basics").show(ui, |ui| {
+                    render_general_fields(ui, &mut self.draft, errors, false, translate_fn);
+                });
+                Self::render_conflicts(ui, conflicts, language, translate_fn);
+            }
+            WizardStep::Content => {
+                grid("site_wizard_content").show(ui, |ui| {
+                    render_kind_fields(ui, &mut self.draft, errors, translate_fn);
+                });
+            }
+            WizardStep::Tls => {
+                grid("site_wizard_tls").show(ui, |ui| {
+                    render_tls_fields(ui, &mut self.draft, errors, translate_fn);
+                });
+            }
+            WizardStep::Preview => {
+                let path = site_conf_path(&site.name);
+                ui.label(format_message(
+                    &translate_fn(Key::WizardPreviewPath),
+                    language,
+                    &TranslationArgs::new().text("path", &path.display().to_string()),
+                ));
+                ui.add_space(4.0);
+                let preview = config::render(site);
+                egui::ScrollArea::vertical()
+                    .max_height(PREVIEW_HEIGHT)
+                    .show(ui, |ui| {
+                        ui.add(egui::TextEdit::multiline(&mut preview.as_str())
+                            .font(egui::TextStyle::Monospace)
+                            .desired_width(f32::INFINITY));
+                    });
+                Self::render_conflicts(ui, conflicts, language, translate_fn);
+                // 其它步骤的错误（如服务端返回的校验错误）
+                for error in errors {
+                    ui.colored_label(COLOR_ERROR, translate_fn(error.problem));
+                }
+                if let Some(error) = &self.save_error {
+                    let text = format_message(
+                        &translate_fn(Key::SiteEditorSaveFailed),
+                        language,
+                        &TranslationArgs::new().text("error", error),
+                    );
+                    ui.colored_label(COLOR_ERROR, text);
+                }
+            }
+        }
+    }
+
+    fn render_conflicts(ui: &mut egui::Ui, conflicts: &[SiteConflict], language: &Language, translate_fn: &dyn Fn(Key) -> String) {
+        if conflicts.is_empty() {
+            return;
+        }
+        ui.add_space(8.0);
+        for conflict in conflicts {
+            let (key, args, color) = match conflict {
+                SiteConflict::Port { site, port } => (
+                    Key::WizardConflictPort,
+                    TranslationArgs::new().text("site", site).text("port", &port.to_string()),
+                    COLOR_WARNING,
+                ),
+                SiteConflict::ServerName { site, port, domain } => (
+                    Key::WizardConflictServerName,
+                    TranslationArgs::new().text("site", site).text("port", &port.to_string()).text("domain", domain),
+                    COLOR_ERROR,
+                ),
+            };
+            ui.colored_label(color, format_message(&translate_fn(key), language, &args));
+        }
+    }
+
+    fn render_outcome(ui: &mut egui::Ui, outcome: &ReloadOutcome, language: &Language, translate_fn: &dyn Fn(Key) -> String) {
+        match outcome {
+            ReloadOutcome::Reloaded => {
+                ui.label(translate_fn(Key::WizardResultReloaded));
+            }
+            ReloadOutcome::NotRunning => {
+                ui.label(translate_fn(Key::WizardResultNotRunning));
+            }
+            ReloadOutcome::TestFailed(output) => {
+                ui.colored_label(COLOR_WARNING, translate_fn(Key::WizardResultTestFailed));
+                ui.add_space(4.0);
+                egui::ScrollArea::vertical()
+                    .max_height(PREVIEW_HEIGHT)
+                    .show(ui, |ui| {
+                        ui.add(egui::TextEdit::multiline(&mut output.as_str())
+                            .font(egui::TextStyle::Monospace)
+                            .desired_width(f32::INFINITY));
+                    });
+            }
+            ReloadOutcome::RolledBack(error) | ReloadOutcome::ReloadFailed(error) => {
+                let key = match outcome {
+                    ReloadOutcome::RolledBack(_) => Key::WizardResultRolledBack,
+                    _ => Key::WizardResultReloadFailed,
+                };
+                let text = format_message(&translate_fn(key), language, &TranslationArgs::new().text("error", error));
+                ui.colored_label(COLOR_WARNING, text);
+            }
+        }
+    }
+
+    fn create_request(&mut self) -> SiteChangeRequest {
+        let id = next_request_id();
+        self.pending = Some(id);
+        self.save_error = None;
+        let change = SiteChange::Save {
+            original_name: None,
+            site: Box::new(self.draft.to_site()),
+        };
+        SiteChangeRequest::new(id, change, "ui").with_reload()
+    }
+}
@@ -0,0 +1,257 @@
+// MIT License
+// 
+// Copyright (c) 2026 Laffinty
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+
+// ==============================================================================
+// Takeover Dialog - 接管已安装的 nginx
+// ==============================================================================
+// 打开时发布 TakeoverScanRequest，列出找到的 nginx（版本、路径、运行状态、模块）
+// 及接管时导入和保持只读的站点；已经接管时只提供撤销。
+
+use eframe::egui;
+use std::time::{Duration, SystemTime};
+use crate::model::l18n::{format_message, Key, Language, TranslationArgs};
+use crate::model::takeover::{Candidate, SkipReason, TakeoverAction, TakeoverRequest, TakeoverResponse, TakeoverScanned};
+use super::main_window::format_elapsed;
+use super::site_editor::COLOR_ERROR;
+use super::nginx_result::COLOR_WARNING;
+
+const DIALOG_WIDTH: f32 = 560.0;
+const LIST_HEIGHT: f32 = 160.0;
+
+#[derive(Default)]
+pub struct TakeoverDialog {
+    open: bool,
+    /// None 表示正在查找
+    scanned: Option<TakeoverScanned>,
+    selected: usize,
+    /// 等待响应的请求编号
+    pending: Option<u64>,
+    response: Option<TakeoverResponse>,
+}
+
+impl TakeoverDialog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 打开对话框；调用方随后发布 TakeoverScanRequest
+    pub fn open(&mut self) {
+        *self = Self {
+            open: true,
+            ..Self::default()
+        };
+    }
+
+    pub fn handle_scan(&mut self, scanned: &TakeoverScanned) {
+        if self.open && self.pending.is_none() && self.response.is_none() {
+            self.scanned = Some(scanned.clone());
+            self.selected = self.selected.min(scanned.candidates.len().saturating_sub(1));
+        }
+    }
+
+    pub fn handle_response(&mut self, response: &TakeoverResponse) {
+        if self.pending == Some(response.id) {
+            self.pending = None;
+            self.response = Some(response.clone());
+        }
+    }
+
+    /// 返回需要发布的请求
+    pub fn ui(&mut self, ctx: &egui::Context, language: &Language, translate_fn: &dyn Fn(Key) -> String) -> Option<TakeoverRequest> {
+        if !self.open {
+            return None;
+        }
+
+        let mut action = None;
+        let mut close = false;
+        egui::Window::new(translate_fn(Key::MenuTakeoverNginx))
+            .id(egui::Id::new("takeover_dialog"))
+            .collapsible(false)
+            .resizable(false)
+            .default_width(DIALOG_WIDTH)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.set_width(DIALOG_WIDTH);
+                if let Some(response) = &self.response {
+                    Self::render_response(ui, response, language, translate_fn);
+                } else if self.pending.is_some() {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(translate_fn(Key::NginxResultWorking));
+                    });
+                } else {
+                    match &self.scanned {
+                        None => {
+                            ui.horizontal(|ui| {
+                                ui.spinner();
+                                ui.label(translate_fn(Key::TakeoverScanning));
+                            });
+                        }
+                        Some(scanned) => action = self.render_scanned(ui, &scanned.clone(), language, translate_fn),
+                    }
+                }
+                ui.add_space(12.0);
+                ui.separator();
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.add_enabled(self.pending.is_none(), egui::Button::new(translate_fn(Key::TakeoverClose))).clicked() {
+                        close = true;
+                    }
+                });
+            });
+
+        if close || (self.pending.is_none() && ctx.input(|i| i.key_pressed(egui::Key::Escape))) {
+            self.open = false;
+        }
+        let action = action?;
+        let id = crate::model::site::next_request_id();
+        self.pending = Some(id);
+        Some(TakeoverRequest::new(id, action, "ui"))
+    }
+
+    fn render_scanned(&mut self, ui: &mut egui::Ui, scanned: &TakeoverScanned, language: &Language, translate_fn: &dyn Fn(Key) -> String) -> Option<TakeoverAction> {
+        if let Some(current) = &scanned.current {
+            let taken_at = SystemTime::UNIX_EPOCH + Duration::from_secs(current.taken_at);
+            let elapsed = SystemTime::now().duration_since(taken_at).unwrap_or_default();
+            let args = TranslationArgs::new()
+                .text("binary", &current.binary.display().to_string())
+                .text("elapsed", &format_elapsed(elapsed, language, translate_fn));
+            ui.label(format_message(&translate_fn(Key::TakeoverCurrent), language, &args));
+            ui.weak(translate_fn(Key::TakeoverUndoNote));
+            ui.add_space(8.0);
+            return ui.button(translate_fn(Key::TakeoverUndo)).clicked().then_some(TakeoverAction::Undo);
+        }
+        if scanned.candidates.is_empty() {
+            ui.label(translate_fn(Key::TakeoverNoneFound));
+            return None;
+        }
+
+        for (index, candidate) in scanned.candidates.iter().enumerate() {
+            let text = format!("{}  ({})", candidate.installation.binary.display(), candidate.installation.build.version);
+            ui.radio_value(&mut self.selected, index, text);
+        }
+        let candidate = scanned.candidates.get(self.selected)?;
+        ui.add_space(8.0);
+        Self::render_installation(ui, candidate, language, translate_fn);
+        ui.add_space(8.0);
+
+        let plan = match &candidate.plan {
+            Ok(plan) => plan,
+            Err(error) => {
+                let args = TranslationArgs::new().text("error", error);
+                ui.colored_label(COLOR_ERROR, format_message(&translate_fn(Key::TakeoverReadFailed), language, &args));
+                return None;
+            }
+        };
+        let args = TranslationArgs::new().count("count", plan.sites.len());
+        ui.strong(format_message(&translate_fn(Key::TakeoverImportHeading), language, &args));
+        egui::ScrollArea::vertical()
+            .id_source("takeover_plan")
+            .max_height(LIST_HEIGHT)
+            .show(ui, |ui| {
+                for site in &plan.sites {
+                    ui.horizontal(|ui| {
+                        ui.label(&site.name);
+                        if let Some(path) = &site.imported_from {
+                            ui.weak(path.display().to_string());
+                        }
+                    });
+                }
+                if !plan.skipped.is_empty() {
+                    ui.add_space(4.0);
+                    ui.strong(translate_fn(Key::TakeoverSkippedHeading));
+                    for skipped in &plan.skipped {
+                        let reason = match skipped.reason {
+                            SkipReason::MainConfig => translate_fn(Key::TakeoverSkipMain),
+                            SkipReason::Custom => translate_fn(Key::TakeoverSkipCustom),
+                            SkipReason::SeveralSites(count) => {
+                                let args = TranslationArgs::new().count("count", count);
+                                format_message(&translate_fn(Key::TakeoverSkipSeveral), language, &args)
+                            }
+                        };
+                        ui.horizontal(|ui| {
+                            ui.label(skipped.path.display().to_string());
+                            ui.weak(reason);
+                        });
+                    }
+                }
+            });
+        ui.weak(translate_fn(Key::TakeoverImportNote));
+        ui.add_space(8.0);
+        let binary = candidate.installation.binary.clone();
+        ui.button(translate_fn(Key::TakeoverConfirm)).clicked().then_some(TakeoverAction::TakeOver { binary })
+    }
+
+    fn render_installation(ui: &mut egui::Ui, candidate: &Candidate, language: &Language, translate_fn: &dyn Fn(Key) -> String) {
+        let installation = &candidate.installation;
+        egui::Grid::new("takeover_installation").num_columns(2).spacing([12.0, 4.0]).show(ui, |ui| {
+            ui.label(translate_fn(Key::TakeoverPrefix));
+            ui.label(installation.prefix.display().to_string());
+            ui.end_row();
+            ui.label(translate_fn(Key::TakeoverConfPath));
+            ui.label(installation.conf_path.display().to_string());
+            ui.end_row();
+            ui.label(translate_fn(Key::TakeoverPidPath));
+            ui.label(installation.pid_path.display().to_string());
+            ui.end_row();
+            ui.label(translate_fn(Key::TakeoverMaster));
+            match installation.master_pid {
+                Some(pid) => {
+                    let args = TranslationArgs::new().text("pid", &pid.to_string());
+                    ui.label(format_message(&translate_fn(Key::TakeoverMasterRunning), language, &args));
+                }
+                None => {
+                    ui.label(translate_fn(Key::TakeoverMasterStopped));
+                }
+            }
+            ui.end_row();
+        });
+        let modules = &installation.build.modules;
+        if !modules.is_empty() {
+            let args = TranslationArgs::new().count("count", modules.len());
+            ui.collapsing(format_message(&translate_fn(Key::TakeoverModules), language, &args), |ui| {
+                ui.label(modules.join(", "));
+            });
+        }
+    }
+
+    fn render_response(ui: &mut egui::Ui, response: &TakeoverResponse, language: &Language, translate_fn: &dyn Fn(Key) -> String) {
+        match (&response.error, &response.action) {
+            (Some(error), _) => {
+                let args = TranslationArgs::new().text("error", error);
+                ui.colored_label(COLOR_ERROR, format_message(&translate_fn(Key::TakeoverFailed), language, &args));
+            }
+            (None, TakeoverAction::TakeOver { .. }) => {
+                let args = TranslationArgs::new().count("count", response.sites.len());
+                ui.label(format_message(&translate_fn(Key::TakeoverDone), language, &args));
+            }
+            (None, TakeoverAction::Undo) => {
+                ui.label(translate_fn(Key::TakeoverUndone));
+                if !response.kept.is_empty() {
+                    let files: Vec<String> = response.kept.iter().map(|path| path.display().to_string()).collect();
+                    let args = TranslationArgs::new().text("files", &files.join(", "));
+                    ui.colored_label(COLOR_WARNING, format_message(&translate_fn(Key::TakeoverUndoneKept), language, &args));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,213 @@
+// MIT License
+// 
+// Copyright (c) 2026 Laffinty
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+
+// ==============================================================================
+// TranslationCache - UI 侧的翻译缓存
+// ==============================================================================
+// 按 (语言, 键) 缓存 l18n 模块返回的原始文本，由 UiModule（总线线程）写入，
+// MainWindow（egui 线程）同步读取。使用 std::sync::RwLock，临界区只有哈希表
+// 操作，不会跨 await 持有，因此 egui 线程可以直接加锁读取。
+//
+// generation 在翻译资源重新加载时递增：请求带上发出时的 generation，响应
+// 返回时 generation 已变化则丢弃，避免旧资源的译文覆盖新资源。重新加载时保留
+// 旧的条目，新的批量翻译到达后再替换，期间界面不会退回显示键名。切换语言不需要
+// 清空缓存 - 旧语言的迟到响应只会写入旧语言的条目，切回时还可以复用。
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use crate::model::l18n::{Key, Language, TranslationResponse};
+
+#[derive(Default)]
+struct CacheState {
+    generation: u64,
+    entries: HashMap<(Language, String), String>,
+    /// 当前 generation 下已经请求过批量翻译的语言
+    requested: HashSet<Language>,
+}
+
+#[derive(Default)]
+pub struct TranslationCache {
+    state: RwLock<CacheState>,
+}
+
+impl TranslationCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // 锁中毒说明另一线程在持锁时 panic；缓存内容仍然可用，继续使用
+    fn read(&self) -> RwLockReadGuard<'_, CacheState> {
+        self.state.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, CacheState> {
+        self.state.write().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.read().generation
+    }
+
+    /// 同步查找原始文本
+    pub fn get(&self, language: &Language, key: Key) -> Option<String> {
+        self.read().entries.get(&(language.clone(), key.as_str().to_string())).cloned()
+    }
+
+    /// 查找原始文本，缺失时返回键名（翻译尚未到达时显示）
+    pub fn get_or_key(&self, language: &Language, key: Key) -> String {
+        self.get(language, key).unwrap_or_else(|| key.as_str().to_string())
+    }
+
+    /// 标记语言的批量翻译已请求；返回请求应携带的 generation，已请求过时返回 None
+    pub fn begin_load(&self, language: &Language) -> Option<u64> {
+        let mut state = self.write();
+        if state.requested.insert(language.clone()) {
+            Some(state.generation)
+        } else {
+            None
+        }
+    }
+
+    /// 写入一个语言的批量翻译；generation 已过期时丢弃并返回 false
+    pub fn insert_batch(&self, generation: u64, language: &Language, translations: &HashMap<String, String>) -> bool {
+        let mut state = self.write();
+        if generation != state.generation {
+            return false;
+        }
+        for (key, value) in translations {
+            state.entries.insert((language.clone(), key.clone()), value.clone());
+        }
+        true
+    }
+
+    /// 写入单个翻译；generation 已过期时丢弃并返回 false
+    pub fn insert(&self, generation: u64, language: &Language, key: &str, value: &str) -> bool {
+        let mut state = self.write();
+        if generation != state.generation {
+            return false;
+        }
+        state.entries.insert((language.clone(), key.to_string()), value.to_string());
+        true
+    }
+
+    /// 写入单个翻译响应；只接受 requester 自己请求、未填充参数的原始文本
+    pub fn insert_response(&self, requester: &str, response: &TranslationResponse) -> bool {
+        response.requester == requester
+            && !response.formatted
+            && self.insert(response.generation, &response.language, &response.key, &response.translation)
+    }
+
+    /// 翻译资源已重新加载：使在途的响应失效，并允许重新请求各语言的翻译
+    ///
+    /// 旧的条目继续使用，直到新的批量翻译到达时被替换。
+    pub fn invalidate(&self) {
+        let mut state = self.write();
+        state.generation += 1;
+        state.requested.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn batch(key: Key, value: &str) -> HashMap<String, String> {
+        HashMap::from([(key.as_str().to_string(), value.to_string())])
+    }
+
+    #[test]
+    fn drops_responses_from_old_generation() {
+        let cache = TranslationCache::new();
+        let en = Language::source();
+        let generation = cache.begin_load(&en).unwrap();
+        assert_eq!(cache.begin_load(&en), None);
+
+        // 请求在途时翻译资源重新加载
+        cache.invalidate();
+        assert!(!cache.insert_batch(generation, &en, &batch(Key::AboutTitle, "stale")));
+        assert!(!cache.insert(generation, &en, Key::AboutTitle.as_str(), "stale"));
+        assert_eq!(cache.get(&en, Key::AboutTitle), None);
+
+        // 失效后重新请求，带新的 generation
+        let current = cache.begin_load(&en).unwrap();
+        assert_ne!(current, generation);
+        assert!(cache.insert_batch(current, &en, &batch(Key::AboutTitle, "About")));
+        assert_eq!(cache.get(&en, Key::AboutTitle).as_deref(), Some("About"));
+    }
+
+    #[test]
+    fn switching_language_does_not_show_old_strings() {
+        let cache = TranslationCache::new();
+        let en = Language::source();
+        let zh = Language::parse("zh-CN").unwrap();
+        let generation = cache.begin_load(&en).unwrap();
+        assert!(cache.insert_batch(generation, &en, &batch(Key::AboutTitle, "About")));
+
+        // 切换到中文：译文未到达前显示键名，而不是英文
+        assert!(cache.begin_load(&zh).is_some());
+        assert_eq!(cache.get_or_key(&zh, Key::AboutTitle), Key::AboutTitle.as_str());
+
+        // 英文的迟到响应只写入英文条目
+        assert!(cache.insert(generation, &en, Key::AboutAppName.as_str(), "Name"));
+        assert_eq!(cache.get(&zh, Key::AboutAppName), None);
+
+        assert!(cache.insert_batch(generation, &zh, &batch(Key::AboutTitle, "关于")));
+        assert_eq!(cache.get_or_key(&zh, Key::AboutTitle), "关于");
+        // 切回英文时复用缓存，不再重复请求
+        assert_eq!(cache.begin_load(&en), None);
+        assert_eq!(cache.get_or_key(&en, Key::AboutTitle), "About");
+    }
+
+    #[test]
+    fn keeps_old_entries_until_reloaded_batch_arrives() {
+        let cache = TranslationCache::new();
+        let en = Language::source();
+        let generation = cache.begin_load(&en).unwrap();
+        assert!(cache.insert_batch(generation, &en, &batch(Key::AboutTitle, "About")));
+
+        // 重新加载后仍显示旧的译文，而不是键名
+        cache.invalidate();
+        assert_eq!(cache.get_or_key(&en, Key::AboutTitle), "About");
+        let generation = cache.begin_load(&en).unwrap();
+        assert!(cache.insert_batch(generation, &en, &batch(Key::AboutTitle, "About easyNginx")));
+        assert_eq!(cache.get_or_key(&en, Key::AboutTitle), "About easyNginx");
+    }
+
+    #[test]
+    fn ignores_responses_for_other_requesters() {
+        let cache = TranslationCache::new();
+        let en = Language::source();
+        let generation = cache.begin_load(&en).unwrap();
+        let key = Key::AboutTitle.as_str();
+
+        // 其它模块请求的、已填充参数的译文不能当作原始文本缓存
+        let other = TranslationResponse::new(key, "About", en.clone(), "tray", generation);
+        assert!(!cache.insert_response("ui", &other));
+        let formatted = TranslationResponse { formatted: true, ..TranslationResponse::new(key, "About blog", en.clone(), "ui", generation) };
+        assert!(!cache.insert_response("ui", &formatted));
+        assert_eq!(cache.get(&en, Key::AboutTitle), None);
+
+        assert!(cache.insert_response("ui", &TranslationResponse::new(key, "About {name}", en.clone(), "ui", generation)));
+        assert_eq!(cache.get_or_key(&en, Key::AboutTitle), "About {name}");
+    }
+}